@@ -0,0 +1,74 @@
+// Example cv_check template reproducing the built-in look.
+//
+// Usage:
+//   cv build examples/cv.md --template examples/templates/default.typ
+//
+// cv_check defines `cv` before this file is evaluated - see docs/typst-templates.md
+// for every field. Copy this file and change whatever you like.
+
+#let colors = cv.theme.colors
+#let fonts = cv.theme.fonts
+
+#set document(title: cv.name, author: cv.name)
+#set page(paper: "a4", margin: (top: 1.5cm, bottom: 1.5cm, left: 2cm, right: 2cm))
+#set text(font: fonts.header.family, size: 11pt, fill: colors.text)
+
+// FontAwesome 4 icons, as used by the built-in header
+#let icon(code) = text(font: "FontAwesome", code)
+
+#let contact-parts = {
+  let parts = ()
+  if cv.phone != none { parts.push([#icon("\u{f095}") #cv.phone]) }
+  parts.push([#icon("\u{f0e0}") #cv.email])
+  if cv.website != none { parts.push([#icon("\u{f015}") #link(cv.website)[#cv.website]]) }
+  if cv.github != none {
+    parts.push([#icon("\u{f09b}") #link("https://github.com/" + cv.github)[github.com/#cv.github]])
+  }
+  if cv.linkedin != none {
+    parts.push([#icon("\u{f0e1}") #link("https://linkedin.com/in/" + cv.linkedin)[linkedin.com/in/#cv.linkedin]])
+  }
+  parts
+}
+
+#if cv.kind == "cv" [
+  #align(center)[
+    #text(size: 36pt, weight: "bold")[#cv.name]
+    #if cv.location != none [
+      #v(0.2em)
+      #text(style: "italic")[#cv.location]
+    ]
+    #v(0.3em)
+    #text(size: 10pt)[#contact-parts.join([ | ])]
+  ]
+  #v(0.5em)
+
+  // `cv.body` is the whole document rendered by cv_check. To reorder or restyle
+  // sections, loop over `cv.sections` instead, e.g.
+  //   #for section in cv.sections [ == #section.title #section.body ]
+  #cv.body
+] else [
+  #align(right)[
+    #text(size: 14pt, weight: "bold")[#cv.name]
+    #v(0.3em)
+    #if cv.location != none [#cv.location \ ]
+    #if cv.phone != none [#cv.phone \ ]
+    #cv.email
+  ]
+  #v(1.5em)
+  *#datetime.today().display("[day padding:none] [month repr:long] [year]")*
+  #v(1em)
+  #if cv.recipient != none {
+    let r = cv.recipient
+    let lines = (r.name, r.title, r.company).filter(line => line != none)
+    if r.address != none { lines += r.address.split("\n") }
+    lines.join(linebreak())
+  } else [To Whom It May Concern]
+  #v(1em)
+  #if cv.subject != none [*Subject: #cv.subject* #v(1em)]
+
+  #cv.body
+
+  #v(1em)
+  *#cv.name* \
+  #contact-parts.join(linebreak())
+]
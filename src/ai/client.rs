@@ -1,21 +1,33 @@
-//! OpenAI-compatible API client for CV tailoring
+//! AI client for CV tailoring, on top of a pluggable [`Provider`]
 
+use crate::ai::mock::MockProvider;
+use crate::ai::openai::OpenAiProvider;
 use crate::ai::prompts::{create_user_prompt, SYSTEM_PROMPT};
 use crate::ai::schema_gen::JsonSchema;
 use crate::ai::schemas::TailoredCV;
-use crate::ai::{AIError, Result};
+use crate::ai::{AIError, ChatRequest, Provider, Result};
+use crate::config::{AiConfig, AiProfile, ProviderKind};
 use log::{debug, error, info};
-use openai_api_rs::v1::api::OpenAIClient;
-use openai_api_rs::v1::chat_completion::{self, ChatCompletionRequest};
 
-/// OpenAI-compatible API client
+/// Environment variable naming the profile to use when `--profile` is not given
+pub const PROFILE_ENV: &str = "AI_PROFILE";
+
+/// Environment variable holding the API key when a profile names none
+const DEFAULT_API_KEY_ENV: &str = "AI_API_KEY";
+
+/// Client tailoring CVs through an AI provider
 pub struct AIClient {
-    pub(crate) client: OpenAIClient,
-    pub(crate) model: String,
+    provider: Box<dyn Provider>,
 }
 
 impl AIClient {
-    /// Create a new AI client from environment variables
+    /// Creates a client using `provider`.
+    #[must_use]
+    pub fn new(provider: Box<dyn Provider>) -> Self {
+        Self { provider }
+    }
+
+    /// Create a new AI client for an OpenAI-compatible API from environment variables
     ///
     /// # Errors
     ///
@@ -23,45 +35,105 @@ impl AIClient {
     pub fn from_env() -> Result<Self> {
         let endpoint =
             std::env::var("AI_ENDPOINT").map_err(|_| AIError::EnvVar("AI_ENDPOINT".to_string()))?;
-        let api_key =
-            std::env::var("AI_API_KEY").map_err(|_| AIError::EnvVar("AI_API_KEY".to_string()))?;
+        let api_key = std::env::var(DEFAULT_API_KEY_ENV)
+            .map_err(|_| AIError::EnvVar(DEFAULT_API_KEY_ENV.to_string()))?;
         let model =
             std::env::var("AI_MODEL").map_err(|_| AIError::EnvVar("AI_MODEL".to_string()))?;
 
-        info!("Creating AI client with endpoint: {endpoint}");
-        info!("Using model: {model}");
-        debug!("API key length: {}", api_key.len());
+        let provider = OpenAiProvider::new(&endpoint, Some(api_key), &model)?;
+        info!("AI client created successfully");
+        Ok(Self::new(Box::new(provider)))
+    }
 
-        let client = OpenAIClient::builder()
-            .with_endpoint(endpoint)
-            .with_api_key(api_key)
-            .build()
-            .map_err(|e| AIError::InvalidResponse(format!("Failed to build client: {e}")))?;
+    /// Creates a client from the profile named `profile`, else the `AI_PROFILE`
+    /// environment variable, else the config's default profile. Without any of
+    /// them, falls back to [`AIClient::from_env`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile does not exist or is incomplete, or, without a
+    /// profile, if the environment variables are not set.
+    pub fn from_config(config: &AiConfig, profile: Option<&str>) -> Result<Self> {
+        let name = profile
+            .map(str::to_string)
+            .or_else(|| std::env::var(PROFILE_ENV).ok())
+            .or_else(|| config.default_profile.clone());
+        let Some(name) = name else {
+            return Self::from_env();
+        };
 
-        info!("AI client created successfully");
-        Ok(Self { client, model })
+        let settings = config.profiles.get(&name).ok_or_else(|| {
+            let available: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+            AIError::Profile(format!(
+                "unknown profile '{name}' (available: {})",
+                if available.is_empty() {
+                    "none configured".to_string()
+                } else {
+                    available.join(", ")
+                }
+            ))
+        })?;
+        info!("Using AI profile: {name}");
+        Self::from_profile(&name, settings)
     }
 
-    /// Make a raw chat completion request with a custom response format
+    /// Creates a client from one profile's settings.
+    ///
+    /// # Errors
     ///
-    /// This method allows direct access to the `OpenAI` API with custom schemas,
+    /// Returns an error if an `openai` profile lacks its endpoint or model, its API
+    /// key variable is named but not set, or a `mock` fixture cannot be read.
+    pub fn from_profile(name: &str, profile: &AiProfile) -> Result<Self> {
+        let provider: Box<dyn Provider> = match profile.provider {
+            ProviderKind::OpenAi => {
+                let required = |value: &Option<String>, field: &str| {
+                    value.clone().ok_or_else(|| {
+                        AIError::Profile(format!("profile '{name}' has no '{field}'"))
+                    })
+                };
+                let endpoint = required(&profile.endpoint, "endpoint")?;
+                let model = required(&profile.model, "model")?;
+                // A named variable must be set; the default one is optional, for
+                // local servers that take no key
+                let api_key = match &profile.api_key_env {
+                    Some(var) => {
+                        Some(std::env::var(var).map_err(|_| AIError::EnvVar(var.clone()))?)
+                    }
+                    None => std::env::var(DEFAULT_API_KEY_ENV).ok(),
+                };
+                Box::new(OpenAiProvider::new(&endpoint, api_key, &model)?)
+            }
+            ProviderKind::Mock => match &profile.fixture {
+                Some(path) => Box::new(MockProvider::from_file(path)?),
+                None => Box::new(MockProvider::default()),
+            },
+        };
+        Ok(Self::new(provider))
+    }
+
+    /// Where requests go: an endpoint URL, or `mock`.
+    #[must_use]
+    pub fn provider_name(&self) -> String {
+        self.provider.name()
+    }
+
+    /// The model answering requests.
+    #[must_use]
+    pub fn model(&self) -> &str {
+        self.provider.model()
+    }
+
+    /// Make a raw chat completion request, returning the text of the reply
+    ///
+    /// This method allows direct access to the provider with custom schemas,
     /// useful for cases beyond CV tailoring such as extracting structured data
     /// from documents or implementing custom AI workflows.
     ///
-    /// ONLY USED IN INTEGRATION TESTS
-    ///
     /// # Errors
     ///
-    /// Returns an error if the API request fails
-    #[allow(dead_code)] // `allow(dead_code)` exception
-    pub async fn chat_completion_raw(
-        &mut self,
-        request: ChatCompletionRequest,
-    ) -> Result<chat_completion::ChatCompletionResponse> {
-        self.client
-            .chat_completion(request)
-            .await
-            .map_err(|e| AIError::InvalidResponse(format!("API request failed: {e}")))
+    /// Returns an error if the request fails
+    pub async fn complete(&mut self, request: &ChatRequest) -> Result<String> {
+        self.provider.complete(request).await
     }
 
     /// Tailor a CV for a specific job description
@@ -74,68 +146,23 @@ impl AIClient {
         cv_content: &str,
         job_description: &str,
     ) -> Result<TailoredCV> {
-        let mut request = ChatCompletionRequest::new(
-            self.model.clone(),
-            vec![
-                chat_completion::ChatCompletionMessage {
-                    role: chat_completion::MessageRole::system,
-                    content: chat_completion::Content::Text(SYSTEM_PROMPT.to_string()),
-                    name: None,
-                    tool_calls: None,
-                    tool_call_id: None,
-                },
-                chat_completion::ChatCompletionMessage {
-                    role: chat_completion::MessageRole::user,
-                    content: chat_completion::Content::Text(create_user_prompt(
-                        cv_content,
-                        job_description,
-                    )),
-                    name: None,
-                    tool_calls: None,
-                    tool_call_id: None,
-                },
-            ],
-        );
-
         // Set up structured output using the generated JSON schema
         let response_format = TailoredCV::response_format("tailored_cv");
         debug!(
             "Request response format: {}",
             serde_json::to_string_pretty(&response_format).unwrap_or_default()
         );
-        request = request.response_format(response_format);
-
-        info!("Sending request to AI API endpoint");
-        debug!("Request: {request:?}");
-
-        let response = match self.client.chat_completion(request).await {
-            Ok(resp) => {
-                debug!("Got successful response from API");
-                resp
-            }
-            Err(e) => {
-                error!("API request failed: {e}");
-                error!("Error type: {e:?}");
-                return Err(AIError::InvalidResponse(format!("API request failed: {e}")));
-            }
+        let request = ChatRequest {
+            system: SYSTEM_PROMPT.to_string(),
+            user: create_user_prompt(cv_content, job_description),
+            response_format: Some(response_format),
         };
 
-        debug!("Received response from API");
-
-        // Extract the content from the response
-        let content = response
-            .choices
-            .first()
-            .and_then(|choice| choice.message.content.as_ref())
-            .ok_or_else(|| {
-                error!("No content in API response");
-                debug!("Full response: {response:?}");
-                AIError::InvalidResponse("No content in response".to_string())
-            })?;
-
+        let content = self.complete(&request).await?;
         debug!("Response content: {content}");
 
         // Strip markdown code blocks if present
+        let content = content.trim();
         let json_content = if content.starts_with("```json") && content.ends_with("```") {
             info!("Stripping markdown JSON code block");
             content
@@ -171,3 +198,73 @@ impl AIClient {
         Ok(tailored_cv)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_profile() -> AiProfile {
+        AiProfile {
+            provider: ProviderKind::Mock,
+            endpoint: None,
+            model: None,
+            api_key_env: None,
+            fixture: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tailor_cv_with_mock_provider() {
+        let mut client = AIClient::from_profile("offline", &mock_profile()).expect("Mock client");
+        assert_eq!(client.provider_name(), "mock");
+
+        let tailored = client
+            .tailor_cv("# CV", "Rust engineer")
+            .await
+            .expect("Fixture parses");
+        assert_eq!(tailored.experiences.len(), 2);
+        assert!(tailored.keywords.contains(&"Rust".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_tailor_cv_strips_code_block() {
+        let reply = format!("```json\n{}\n```", crate::ai::mock::DEFAULT_FIXTURE);
+        let mut client = AIClient::new(Box::new(MockProvider::with_reply(&reply)));
+        assert!(client.tailor_cv("# CV", "Job").await.is_ok());
+
+        let mut client = AIClient::new(Box::new(MockProvider::with_reply("not json")));
+        assert!(matches!(
+            client.tailor_cv("# CV", "Job").await,
+            Err(AIError::JsonParse(_))
+        ));
+    }
+
+    #[test]
+    fn test_profile_selection() {
+        let mut config = AiConfig::default();
+        config
+            .profiles
+            .insert("offline".to_string(), mock_profile());
+        config.profiles.insert(
+            "local".to_string(),
+            AiProfile {
+                provider: ProviderKind::OpenAi,
+                endpoint: Some("http://localhost:11434/v1".to_string()),
+                ..mock_profile()
+            },
+        );
+
+        let client = AIClient::from_config(&config, Some("offline")).expect("Profile exists");
+        assert_eq!(client.provider_name(), "mock");
+
+        let Err(error) = AIClient::from_config(&config, Some("cloud")) else {
+            panic!("Unknown profile should fail");
+        };
+        assert!(error.to_string().contains("available: local, offline"));
+
+        let Err(error) = AIClient::from_config(&config, Some("local")) else {
+            panic!("Profile without a model should fail");
+        };
+        assert!(error.to_string().contains("no 'model'"));
+    }
+}
@@ -0,0 +1,69 @@
+//! Offline provider answering every request with a fixture
+//!
+//! Selected by a `mock` profile in the global config. Replies are deterministic, so
+//! `cv tailor` and its tests run without network access or an API key.
+
+use crate::ai::provider::{ChatRequest, Provider, ProviderFuture};
+use crate::ai::{AIError, Result};
+use log::info;
+use std::path::Path;
+
+/// `TailoredCV` JSON returned when a profile names no fixture
+pub const DEFAULT_FIXTURE: &str = include_str!("fixtures/tailored_cv.json");
+
+pub struct MockProvider {
+    reply: String,
+    source: String,
+}
+
+impl Default for MockProvider {
+    fn default() -> Self {
+        Self {
+            reply: DEFAULT_FIXTURE.to_string(),
+            source: "built-in fixture".to_string(),
+        }
+    }
+}
+
+impl MockProvider {
+    /// A provider replying with `reply`.
+    #[must_use]
+    #[allow(dead_code)] // `allow(dead_code)` exception
+    pub fn with_reply(reply: &str) -> Self {
+        Self {
+            reply: reply.to_string(),
+            source: "inline fixture".to_string(),
+        }
+    }
+
+    /// A provider replying with the contents of `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fixture cannot be read.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let reply = std::fs::read_to_string(path).map_err(|e| {
+            AIError::Profile(format!("cannot read fixture {}: {e}", path.display()))
+        })?;
+        Ok(Self {
+            reply,
+            source: path.display().to_string(),
+        })
+    }
+}
+
+impl Provider for MockProvider {
+    fn name(&self) -> String {
+        "mock".to_string()
+    }
+
+    fn model(&self) -> &str {
+        &self.source
+    }
+
+    fn complete<'a>(&'a mut self, _request: &'a ChatRequest) -> ProviderFuture<'a> {
+        info!("Answering from the mock provider ({})", self.source);
+        let reply = self.reply.clone();
+        Box::pin(async move { Ok(reply) })
+    }
+}
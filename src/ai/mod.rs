@@ -1,16 +1,21 @@
 //! AI-powered CV tailoring module
 //!
 //! This module provides functionality to optimize CVs for specific job descriptions
-//! using OpenAI-compatible APIs with structured outputs.
+//! through a pluggable [`Provider`]: any OpenAI-compatible API with structured outputs,
+//! or an offline mock.
 
 pub mod client;
+pub mod mock;
+pub mod openai;
 pub mod pdf_parser;
 pub mod prompts;
+pub mod provider;
 pub mod schema_gen;
 pub mod schemas;
 
 pub use client::AIClient;
 pub use pdf_parser::extract_text_from_pdf;
+pub use provider::{ChatRequest, Provider};
 
 use thiserror::Error;
 
@@ -25,6 +30,9 @@ pub enum AIError {
     #[error("Environment variable not set: {0}")]
     EnvVar(String),
 
+    #[error("AI profile error: {0}")]
+    Profile(String),
+
     #[error("JSON parsing error: {0}")]
     JsonParse(#[from] serde_json::Error),
 }
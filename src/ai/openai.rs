@@ -0,0 +1,103 @@
+//! Provider for OpenAI-compatible chat completion APIs
+//!
+//! Works with `OpenAI` itself and with local servers exposing the same API, such as
+//! Ollama (`http://localhost:11434/v1`) and llama.cpp (`http://localhost:8080/v1`),
+//! which need no API key.
+
+use crate::ai::provider::{ChatRequest, Provider, ProviderFuture};
+use crate::ai::AIError;
+use log::{debug, error, info};
+use openai_api_rs::v1::api::OpenAIClient;
+use openai_api_rs::v1::chat_completion::{self, ChatCompletionRequest};
+
+pub struct OpenAiProvider {
+    client: OpenAIClient,
+    endpoint: String,
+    model: String,
+}
+
+impl OpenAiProvider {
+    /// Creates a provider for `endpoint`, sending `api_key` as a bearer token when
+    /// one is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built.
+    pub fn new(endpoint: &str, api_key: Option<String>, model: &str) -> crate::ai::Result<Self> {
+        info!("Creating AI client with endpoint: {endpoint}");
+        info!("Using model: {model}");
+
+        let mut builder = OpenAIClient::builder().with_endpoint(endpoint);
+        if let Some(api_key) = api_key {
+            debug!("API key length: {}", api_key.len());
+            builder = builder.with_api_key(api_key);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| AIError::InvalidResponse(format!("Failed to build client: {e}")))?;
+
+        Ok(Self {
+            client,
+            endpoint: endpoint.to_string(),
+            model: model.to_string(),
+        })
+    }
+
+    fn message(
+        role: chat_completion::MessageRole,
+        text: &str,
+    ) -> chat_completion::ChatCompletionMessage {
+        chat_completion::ChatCompletionMessage {
+            role,
+            content: chat_completion::Content::Text(text.to_string()),
+            name: None,
+            tool_calls: None,
+            tool_call_id: None,
+        }
+    }
+}
+
+impl Provider for OpenAiProvider {
+    fn name(&self) -> String {
+        self.endpoint.clone()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn complete<'a>(&'a mut self, request: &'a ChatRequest) -> ProviderFuture<'a> {
+        Box::pin(async move {
+            let mut chat = ChatCompletionRequest::new(
+                self.model.clone(),
+                vec![
+                    Self::message(chat_completion::MessageRole::system, &request.system),
+                    Self::message(chat_completion::MessageRole::user, &request.user),
+                ],
+            );
+            if let Some(format) = &request.response_format {
+                chat = chat.response_format(format.clone());
+            }
+
+            info!("Sending request to AI API endpoint");
+            debug!("Request: {chat:?}");
+
+            let response = self.client.chat_completion(chat).await.map_err(|e| {
+                error!("API request failed: {e}");
+                error!("Error type: {e:?}");
+                AIError::InvalidResponse(format!("API request failed: {e}"))
+            })?;
+
+            debug!("Received response from API");
+            response
+                .choices
+                .first()
+                .and_then(|choice| choice.message.content.clone())
+                .ok_or_else(|| {
+                    error!("No content in API response");
+                    debug!("Full response: {response:?}");
+                    AIError::InvalidResponse("No content in response".to_string())
+                })
+        })
+    }
+}
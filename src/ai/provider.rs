@@ -0,0 +1,38 @@
+//! Chat completion providers behind [`AIClient`](crate::ai::AIClient)
+//!
+//! A provider turns a [`ChatRequest`] into the text of the model's reply. The
+//! OpenAI-compatible provider talks to a remote or local server; the mock provider
+//! answers from a fixture, so the tailoring pipeline runs without network access.
+
+use crate::ai::Result;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The reply a provider is working on
+pub type ProviderFuture<'a> = Pin<Box<dyn Future<Output = Result<String>> + 'a>>;
+
+/// A chat completion request, independent of any provider's wire format
+#[derive(Debug, Clone)]
+pub struct ChatRequest {
+    /// Instructions for the model
+    pub system: String,
+    /// The user's message
+    pub user: String,
+    /// Structured output format, as built by
+    /// [`JsonSchema::response_format`](crate::ai::schema_gen::JsonSchema::response_format)
+    pub response_format: Option<Value>,
+}
+
+/// A chat completion backend
+pub trait Provider {
+    /// Where requests go, shown to the user: an endpoint URL, or `mock`.
+    fn name(&self) -> String;
+
+    /// The model answering requests.
+    fn model(&self) -> &str;
+
+    /// Sends `request` and returns the text of the reply. With a response format,
+    /// the reply is JSON matching its schema, possibly inside a code block.
+    fn complete<'a>(&'a mut self, request: &'a ChatRequest) -> ProviderFuture<'a>;
+}
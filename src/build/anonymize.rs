@@ -0,0 +1,410 @@
+//! Anonymized output for blind hiring (`cv build --anonymize`)
+//!
+//! Anonymizing works on a copy of the parsed document, so the source file is never
+//! touched. The candidate's name is replaced, and their contact details, location
+//! and social links are removed, from the frontmatter and wherever they appear in
+//! the body. Custom fields lose photos, dates of birth and gender details, and
+//! gendered pronouns in the remaining ones are made neutral. A mapping file may
+//! also swap employer names for descriptors such as "Tier-1 bank":
+//!
+//! ```yaml
+//! name: Candidate 17
+//! employers:
+//!   Goldman Sachs: Tier-1 bank
+//!   Acme Corp: Series B startup
+//! ```
+
+use crate::error::CvError;
+use crate::parser::Document;
+use anyhow::Result;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Name shown when the mapping file gives none
+pub const ANONYMOUS_NAME: &str = "Candidate";
+
+/// Shown in the body where contact details were
+pub const REDACTED: &str = "[redacted]";
+
+/// Custom fields removed outright
+const REMOVED_FIELDS: &[&str] = &[
+    "photo",
+    "picture",
+    "image",
+    "avatar",
+    "headshot",
+    "dob",
+    "date_of_birth",
+    "birth_date",
+    "birthdate",
+    "birthday",
+    "born",
+    "age",
+    "gender",
+    "sex",
+    "pronouns",
+    "marital_status",
+];
+
+/// Pronoun sets such as "(she/her)" or "he/him/his"
+static PRONOUN_SET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\(?\b(?:she|he)/(?:her|him)(?:/(?:hers|his))?\b\)?[.,;]?")
+        .expect("Invalid pronoun set pattern")
+});
+
+/// Single gendered pronouns
+static PRONOUN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:he|she|him|his|her|hers|himself|herself)\b")
+        .expect("Invalid pronoun pattern")
+});
+
+/// "Born 1 May 1990", "DOB: 01.05.1990" and the like, up to the end of the clause
+static DATE_OF_BIRTH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:born|date of birth|d\.?o\.?b\.?)(?:\s+on)?\s*:?\s*(?:[^,;.\n]|\.\S)*[.,;]?",
+    )
+    .expect("Invalid date of birth pattern")
+});
+
+/// How to anonymize a document, as read from a mapping file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Anonymizer {
+    /// Shown instead of the candidate's name
+    #[serde(default = "default_name")]
+    pub name: String,
+    /// Employer names and the descriptors shown instead
+    #[serde(default)]
+    pub employers: BTreeMap<String, String>,
+}
+
+fn default_name() -> String {
+    ANONYMOUS_NAME.to_string()
+}
+
+impl Default for Anonymizer {
+    fn default() -> Self {
+        Self {
+            name: default_name(),
+            employers: BTreeMap::new(),
+        }
+    }
+}
+
+impl Anonymizer {
+    /// Loads a mapping file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid mapping.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(CvError::FileNotFound(path.to_path_buf()).into());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            CvError::InvalidAnonymizeMap {
+                path: path.to_path_buf(),
+                reason: e.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Returns an anonymized copy of `doc`.
+    #[must_use]
+    pub fn apply(&self, doc: &Document) -> Document {
+        let mut meta = doc.metadata.clone();
+
+        // Values scrubbed from free text, longest first so that an email address
+        // goes before the name it contains. A location goes whole and by its parts,
+        // so "London" goes from the body as well as "London, UK".
+        let location = meta.location.take();
+        let location_parts = location
+            .iter()
+            .flat_map(|location| location.split(','))
+            .map(|part| part.trim().to_string());
+        let mut replacements: Vec<(String, &str)> = [
+            meta.phone.take(),
+            meta.linkedin.take(),
+            meta.github.take(),
+            meta.website.take(),
+            Some(std::mem::take(&mut meta.email)),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(location.clone())
+        .chain(location_parts)
+        .map(|value| (value, REDACTED))
+        .chain([(meta.name.clone(), self.name.as_str())])
+        .chain(
+            self.employers
+                .iter()
+                .map(|(employer, descriptor)| (employer.clone(), descriptor.as_str())),
+        )
+        .filter(|(value, _)| !value.trim().is_empty())
+        .collect();
+        replacements.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+        let name_parts = name_parts(&meta.name);
+
+        let scrub = |text: &str| {
+            let text = replacements
+                .iter()
+                .fold(text.to_string(), |text, (value, with)| {
+                    replace_words(&text, value, with)
+                });
+            // Then "Jane led the migration" and other mentions of part of the name
+            match &name_parts {
+                Some(parts) => parts
+                    .replace_all(&text, regex::NoExpand(&self.name))
+                    .into_owned(),
+                None => text,
+            }
+        };
+
+        meta.name.clone_from(&self.name);
+        meta.subject = meta.subject.as_deref().map(scrub);
+        meta.keywords = meta.keywords.iter().map(|k| scrub(k)).collect();
+        for page_text in [&mut meta.header, &mut meta.footer].into_iter().flatten() {
+            for slot in [
+                &mut page_text.left,
+                &mut page_text.center,
+                &mut page_text.right,
+            ] {
+                *slot = slot.as_deref().map(scrub);
+            }
+        }
+
+        meta.custom
+            .retain(|key, _| !REMOVED_FIELDS.contains(&key.to_ascii_lowercase().as_str()));
+        for value in meta.custom.values_mut() {
+            scrub_custom(value, &scrub);
+        }
+        meta.custom
+            .retain(|_, value| value.as_str().is_none_or(|s| !s.trim().is_empty()));
+
+        let mut anonymized = doc.with_content(scrub(&doc.content));
+        anonymized.metadata = meta;
+        anonymized
+    }
+}
+
+/// Replaces `value` in `text` where it is not part of a longer word, so a handle
+/// such as "ann" leaves "Annual" alone.
+fn replace_words(text: &str, value: &str, with: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let starts_word = value.starts_with(is_word);
+    let ends_word = value.ends_with(is_word);
+
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, _) in text.match_indices(value) {
+        let end = start + value.len();
+        if (starts_word && text[..start].ends_with(is_word))
+            || (ends_word && text[end..].starts_with(is_word))
+        {
+            continue;
+        }
+        result.push_str(&text[copied..start]);
+        result.push_str(with);
+        copied = end;
+    }
+    result.push_str(&text[copied..]);
+    result
+}
+
+/// Matches each word of `name` of at least two characters, as a whole word and in
+/// any case.
+fn name_parts(name: &str) -> Option<Regex> {
+    let parts: Vec<String> = name
+        .split_whitespace()
+        .map(|part| part.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|part| part.chars().count() >= 2)
+        .map(regex::escape)
+        .collect();
+    if parts.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"(?i)\b(?:{})\b", parts.join("|"))).ok()
+}
+
+/// Scrubs the strings in a custom field, recursing into lists and maps.
+fn scrub_custom(value: &mut serde_yaml::Value, scrub: &impl Fn(&str) -> String) {
+    match value {
+        serde_yaml::Value::String(text) => *text = neutral_text(&scrub(text)),
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                scrub_custom(item, scrub);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for item in map.values_mut() {
+                scrub_custom(item, scrub);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Removes pronoun sets and dates of birth from `text`, and makes the remaining
+/// gendered pronouns neutral.
+fn neutral_text(text: &str) -> String {
+    let leading = |pattern: &Regex, text: &str| {
+        pattern
+            .find(text.trim_start())
+            .is_some_and(|found| found.start() == 0)
+    };
+    let mut capitalize = leading(&PRONOUN_SET, text);
+    let text = PRONOUN_SET.replace_all(text, "");
+    capitalize |= leading(&DATE_OF_BIRTH, &text);
+    let text = DATE_OF_BIRTH.replace_all(&text, "");
+    let text = PRONOUN.replace_all(&text, |caps: &Captures| {
+        let word = &caps[0];
+        let neutral = match word.to_ascii_lowercase().as_str() {
+            "he" | "she" => "they",
+            "him" => "them",
+            "his" | "her" => "their",
+            "hers" => "theirs",
+            _ => "themselves",
+        };
+        if word.starts_with(char::is_uppercase) {
+            let mut chars = neutral.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        } else {
+            neutral.to_string()
+        }
+    });
+
+    // Tidy what removals leave behind, starting the sentence again with a capital
+    // when its first words went
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !capitalize {
+        return text;
+    }
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> Document {
+        Document::from_string(
+            "---\n\
+             name: Jane Doe\n\
+             email: jane@example.com\n\
+             phone: +44 1234 567890\n\
+             location: London, UK\n\
+             github: janedoe\n\
+             website: https://jane.dev\n\
+             photo: jane.jpg\n\
+             date_of_birth: 1 May 1990\n\
+             bio: She/her. Born 1 May 1990, she led her team.\n\
+             ---\n\n\
+             # Experience\n\n\
+             ## Goldman Sachs (London)\n\n\
+             Jane Doe built tools; see https://jane.dev or call +44 1234 567890.\n\n\
+             Jane led the migration to DOE's platform; Janet reviewed it.\n",
+            Path::new("cv.md"),
+        )
+        .expect("Valid document")
+    }
+
+    fn anonymizer() -> Anonymizer {
+        Anonymizer {
+            name: "Candidate 17".to_string(),
+            employers: BTreeMap::from([("Goldman Sachs".to_string(), "Tier-1 bank".to_string())]),
+        }
+    }
+
+    #[test]
+    fn test_contact_details_are_removed() {
+        let doc = anonymizer().apply(&doc());
+        let meta = &doc.metadata;
+
+        assert_eq!(meta.name, "Candidate 17");
+        assert!(meta.email.is_empty());
+        assert_eq!(meta.phone, None);
+        assert_eq!(meta.location, None);
+        assert_eq!(meta.github, None);
+        assert_eq!(meta.website, None);
+        assert!(!meta.custom.contains_key("photo"));
+        assert!(!meta.custom.contains_key("date_of_birth"));
+    }
+
+    #[test]
+    fn test_body_is_scrubbed_and_employers_mapped() {
+        let doc = anonymizer().apply(&doc());
+
+        assert!(doc.content.contains("## Tier-1 bank ([redacted])"));
+        assert!(doc
+            .content
+            .contains("Candidate 17 built tools; see [redacted] or call [redacted]."));
+        assert!(!doc.content.contains("Goldman"));
+        // Parts of the name too, but not longer words that contain them
+        assert!(doc
+            .content
+            .contains("Candidate 17 led the migration to Candidate 17's platform; Janet"));
+    }
+
+    #[test]
+    fn test_short_values_only_replace_whole_words() {
+        let doc = Document::from_string(
+            "---\nname: Ann Lee\nemail: ann@example.com\ngithub: lee\nlocation: Bath\n---\n\n\
+             Ann Lee wrote the Annual report with leeway in Bath; github.com/lee\n",
+            Path::new("cv.md"),
+        )
+        .expect("Valid document");
+        let doc = anonymizer().apply(&doc);
+
+        assert!(doc.content.contains(
+            "Candidate 17 wrote the Annual report with leeway in [redacted]; github.com/[redacted]"
+        ));
+        assert_eq!(
+            replace_words("ann, annual, ann", "ann", "x"),
+            "x, annual, x"
+        );
+        assert_eq!(replace_words("+44 1, +44 12", "+44 1", "x"), "x, +44 12");
+    }
+
+    #[test]
+    fn test_custom_fields_lose_pronouns_and_birth_dates() {
+        let doc = anonymizer().apply(&doc());
+        let bio = doc.metadata.custom["bio"]
+            .as_str()
+            .expect("Bio is a string");
+
+        assert_eq!(bio, "They led their team.");
+        assert_eq!(
+            neutral_text("He/him. Reports to his manager"),
+            "Reports to their manager"
+        );
+        assert_eq!(
+            neutral_text("Mentor; DOB: 01/05/1990. He ran it himself"),
+            "Mentor; They ran it themselves"
+        );
+        // Nothing removed from the start, so nothing is capitalised
+        assert_eq!(
+            neutral_text("rust, https://jane.dev"),
+            "rust, https://jane.dev"
+        );
+    }
+
+    #[test]
+    fn test_default_mapping() {
+        let anonymizer: Anonymizer = serde_yaml::from_str("{}").expect("Empty mapping parses");
+        assert_eq!(anonymizer, Anonymizer::default());
+        assert!(serde_yaml::from_str::<Anonymizer>("employer: x").is_err());
+    }
+}
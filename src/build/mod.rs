@@ -0,0 +1,106 @@
+//! Build jobs, shared by the command line and the project manifest
+//!
+//! A [`BuildJob`] is one document rendered to one format. The CLI makes jobs from
+//! its arguments and `cv.toml` from its documents; both run them the same way.
+
+pub mod anonymize;
+
+use crate::error::CvError;
+use crate::render::review::Review;
+use crate::render::{output_extension, SUPPORTED_FORMATS};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+pub use anonymize::Anonymizer;
+
+/// One document rendered to one format
+#[derive(Debug, Clone)]
+pub struct BuildJob {
+    pub input: PathBuf,
+    pub format: String,
+    pub output: PathBuf,
+    pub font_theme: String,
+    pub color_theme: String,
+    pub layout: Option<String>,
+    pub template: Option<PathBuf>,
+    pub dpi: u32,
+    pub pdf_a: bool,
+    pub print_safe: bool,
+    pub review: Option<Review>,
+    pub max_pages: Option<usize>,
+    pub anonymize: Option<Anonymizer>,
+}
+
+/// Parses a comma-separated format list such as `pdf,html,txt`.
+///
+/// # Errors
+///
+/// Returns an error if the list is empty or names an unsupported format.
+pub fn parse_formats(spec: &str) -> Result<Vec<String>> {
+    let mut formats: Vec<String> = Vec::new();
+    for format in spec.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        if !SUPPORTED_FORMATS.contains(&format) {
+            return Err(CvError::InvalidFormat {
+                format: format.to_string(),
+            }
+            .into());
+        }
+        if !formats.iter().any(|f| f == format) {
+            formats.push(format.to_string());
+        }
+    }
+
+    if formats.is_empty() {
+        anyhow::bail!("No output format given");
+    }
+    Ok(formats)
+}
+
+/// Chooses where one input/format pair is written.
+///
+/// With a single output, `-o` names the file. With several, `-o` is a directory.
+#[must_use]
+pub fn output_path(input: &Path, format: &str, output: Option<&Path>, multiple: bool) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("output");
+    let file_name = format!("{stem}.{}", output_extension(format));
+
+    match output {
+        Some(path) if !multiple => path.to_path_buf(),
+        Some(dir) => dir.join(file_name),
+        None => PathBuf::from(file_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formats() {
+        let formats = parse_formats("pdf, html,txt,pdf").expect("Valid formats");
+        assert_eq!(formats, vec!["pdf", "html", "txt"]);
+
+        assert!(parse_formats("pdf,rtf").is_err());
+        assert!(parse_formats(" , ").is_err());
+    }
+
+    #[test]
+    fn test_output_path() {
+        let input = Path::new("docs/cv.md");
+        assert_eq!(
+            output_path(input, "pdf", None, true),
+            PathBuf::from("cv.pdf")
+        );
+        assert_eq!(
+            output_path(input, "typst", Some(Path::new("out")), true),
+            PathBuf::from("out/cv.typ")
+        );
+        assert_eq!(
+            output_path(input, "html", Some(Path::new("final.html")), false),
+            PathBuf::from("final.html")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Building several documents and formats in one invocation
+//!
+//! Inputs may be paths or glob patterns. Each document is parsed once and rendered
+//! to every requested format in parallel; failures are collected per output rather
+//! than aborting the whole run.
+
+use crate::build::Anonymizer;
+use crate::cli::CacheMode;
+use crate::render::RenderOptions;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub struct BatchOptions<'a> {
+    pub inputs: &'a [PathBuf],
+    pub formats: &'a [String],
+    pub font_theme: &'a str,
+    pub color_theme: &'a str,
+    /// Layout theme; the color theme's layout when not given
+    pub layout: Option<&'a str>,
+    pub output: Option<&'a Path>,
+    pub template: Option<&'a Path>,
+    pub render: RenderOptions,
+    pub max_pages: Option<usize>,
+    pub fail_on_warnings: bool,
+    pub cache: CacheMode,
+    /// Strip personal details from every output
+    pub anonymize: Option<&'a Anonymizer>,
+    pub verbose: bool,
+    pub quiet: bool,
+}
+
+/// Result of rendering one document to one format
+#[derive(Debug)]
+pub struct BuildOutcome {
+    pub input: PathBuf,
+    pub format: String,
+    pub output: PathBuf,
+    /// Page count, for formats that have pages
+    pub pages: Option<usize>,
+    /// Warnings reported by the renderer
+    pub warnings: Vec<String>,
+    /// The output was up to date and not rendered again
+    pub cached: bool,
+    pub elapsed: Duration,
+    pub error: Option<String>,
+}
+
+impl BuildOutcome {
+    #[must_use]
+    pub const fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Expands glob patterns in the input list, keeping plain paths as given.
+///
+/// # Errors
+///
+/// Returns an error if a pattern is invalid or matches no files.
+pub fn expand_inputs(patterns: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut inputs: Vec<PathBuf> = Vec::new();
+
+    for pattern in patterns {
+        let text = pattern.to_string_lossy();
+        if !text.contains(['*', '?', '[']) {
+            inputs.push(pattern.clone());
+            continue;
+        }
+
+        let mut matched: Vec<PathBuf> = glob::glob(&text)?
+            .filter_map(Result::ok)
+            .filter(|p| p.is_file())
+            .collect();
+        if matched.is_empty() {
+            anyhow::bail!("No files match '{text}'");
+        }
+        matched.sort();
+        inputs.extend(matched);
+    }
+
+    // The same file given twice (e.g. a path plus a glob) is only built once
+    let mut seen = std::collections::HashSet::new();
+    inputs.retain(|p| seen.insert(p.clone()));
+    Ok(inputs)
+}
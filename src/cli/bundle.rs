@@ -0,0 +1,301 @@
+//! Application packs: a cover letter, a CV and any attachments in one file
+//!
+//! The letter and CV are rendered with the same theme, then either merged with any
+//! PDF attachments into a single PDF with one bookmark per part, or written side by
+//! side, each as PDF and plain text, into a zip archive.
+
+use anyhow::{Context, Result};
+use lopdf::{dictionary, Bookmark, Dictionary, Object, ObjectId};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+/// Default output name, without extension
+pub const DEFAULT_BUNDLE_NAME: &str = "application";
+
+/// Page attributes a page may inherit from the page tree
+const INHERITED_PAGE_KEYS: &[&[u8]] = &[b"MediaBox", b"CropBox", b"Resources", b"Rotate"];
+
+pub struct BundleOptions<'a> {
+    pub cv: &'a Path,
+    pub letter: Option<&'a Path>,
+    /// Existing PDFs appended after the CV, such as certificates
+    pub attachments: &'a [PathBuf],
+    pub font_theme: &'a str,
+    pub color_theme: &'a str,
+    pub output: &'a Path,
+    /// Write a zip of separate PDF and text files instead of one merged PDF
+    pub zip: bool,
+}
+
+/// One document in the bundle
+#[derive(Debug, Clone)]
+pub struct BundlePart {
+    /// Bookmark title in the merged PDF
+    pub title: String,
+    pub pdf: PathBuf,
+    /// Plain text version, for rendered documents in a zip bundle
+    pub txt: Option<PathBuf>,
+}
+
+/// What was written
+#[derive(Debug)]
+pub struct BundleReport {
+    pub output: PathBuf,
+    /// Page count of the merged PDF; `None` for a zip
+    pub pages: Option<usize>,
+    /// Files in the zip, or part titles in the merged PDF
+    pub contents: Vec<String>,
+}
+
+/// Merges `parts` into one PDF at `output`, adding a top-level bookmark for the
+/// first page of each part. Returns the total page count.
+///
+/// # Errors
+///
+/// Returns an error if a part cannot be read, is encrypted or has no pages, or the
+/// result cannot be written.
+pub fn merge_pdfs(parts: &[BundlePart], output: &Path, title: &str) -> Result<usize> {
+    let mut merged = lopdf::Document::with_version("1.7");
+    let mut pages: Vec<(ObjectId, Dictionary)> = Vec::new();
+    let mut objects = BTreeMap::new();
+    let mut next_id = 1;
+
+    for part in parts {
+        let path = &part.pdf;
+        let mut doc = lopdf::Document::load(path)
+            .with_context(|| format!("Could not read PDF {}", path.display()))?;
+        if doc.is_encrypted() {
+            anyhow::bail!("{} is encrypted and cannot be bundled", path.display());
+        }
+
+        // Give every document its own range of object ids
+        doc.renumber_objects_with(next_id);
+        next_id = doc.max_id + 1;
+
+        let page_ids: Vec<ObjectId> = doc.get_pages().into_values().collect();
+        let Some(&first_page) = page_ids.first() else {
+            anyhow::bail!("{} has no pages", path.display());
+        };
+        merged.add_bookmark(
+            Bookmark::new(part.title.clone(), [0.0, 0.0, 0.0], 0, first_page),
+            None,
+        );
+
+        for id in page_ids {
+            let mut page = doc.get_dictionary(id)?.clone();
+            inherit_page_attributes(&doc, &mut page);
+            pages.push((id, page));
+        }
+
+        // The old catalogs and page trees are replaced below
+        doc.objects
+            .retain(|_, object| !matches!(object.type_name(), Ok(b"Catalog" | b"Pages")));
+        objects.append(&mut doc.objects);
+    }
+
+    merged.objects = objects;
+    merged.max_id = next_id;
+
+    let pages_id = merged.new_object_id();
+    let page_count = pages.len();
+    let mut kids = Vec::with_capacity(page_count);
+    for (id, mut page) in pages {
+        page.set("Parent", pages_id);
+        merged.objects.insert(id, Object::Dictionary(page));
+        kids.push(Object::Reference(id));
+    }
+    merged.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => i64::try_from(page_count)?,
+        }),
+    );
+
+    let mut catalog = dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    };
+    if let Some(outline) = merged.build_outline() {
+        catalog.set("Outlines", outline);
+        catalog.set("PageMode", "UseOutlines");
+    }
+    let catalog_id = merged.add_object(catalog);
+    let info_id = merged.add_object(dictionary! {
+        "Title" => Object::string_literal(title),
+        "Producer" => Object::string_literal(format!("cv {}", env!("CARGO_PKG_VERSION"))),
+    });
+    merged.trailer.set("Root", catalog_id);
+    merged.trailer.set("Info", info_id);
+
+    // Drop what only the old catalogs referred to, such as their outlines
+    merged.prune_objects();
+    merged.renumber_objects();
+    merged.compress();
+    merged
+        .save(output)
+        .with_context(|| format!("Could not write {}", output.display()))?;
+
+    Ok(page_count)
+}
+
+/// Copies attributes that `page` inherits from its page tree, since the tree itself
+/// is not carried over into the merged document.
+fn inherit_page_attributes(doc: &lopdf::Document, page: &mut Dictionary) {
+    for &key in INHERITED_PAGE_KEYS {
+        if page.has(key) {
+            continue;
+        }
+
+        let mut parent = page.get(b"Parent").and_then(Object::as_reference).ok();
+        // Bounded in case of a malformed, cyclic page tree
+        for _ in 0..32 {
+            let Some(node) = parent.and_then(|id| doc.get_dictionary(id).ok()) else {
+                break;
+            };
+            if let Ok(value) = node.get(key) {
+                page.set(key, value.clone());
+                break;
+            }
+            parent = node.get(b"Parent").and_then(Object::as_reference).ok();
+        }
+    }
+}
+
+/// Writes every part, and its text version if any, into a zip archive at `output`.
+/// Returns the names of the files in the archive.
+///
+/// # Errors
+///
+/// Returns an error if two files would have the same name, a part cannot be read,
+/// or the archive cannot be written.
+pub fn write_zip(parts: &[BundlePart], output: &Path) -> Result<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    let mut files = Vec::new();
+    for path in parts
+        .iter()
+        .flat_map(|part| std::iter::once(&part.pdf).chain(&part.txt))
+    {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", path.display()))?;
+        if names.contains(&name) {
+            anyhow::bail!("Two files in the bundle are named {name}");
+        }
+        names.push(name);
+        files.push(path);
+    }
+
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(output)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, path) in names.iter().zip(files) {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(&std::fs::read(path)?)?;
+    }
+    zip.finish()?;
+
+    Ok(names)
+}
+
+/// Default output path for a bundle.
+#[must_use]
+pub fn default_output(zip: bool) -> PathBuf {
+    PathBuf::from(format!(
+        "{DEFAULT_BUNDLE_NAME}.{}",
+        if zip { "zip" } else { "pdf" }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Writes a minimal PDF with `pages` empty pages, with the media box set on the
+    /// page tree rather than the pages.
+    fn write_pdf(path: &Path, pages: usize) {
+        let mut doc = lopdf::Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = (0..pages)
+            .map(|_| {
+                doc.add_object(dictionary! {
+                    "Type" => "Page",
+                    "Parent" => pages_id,
+                })
+                .into()
+            })
+            .collect();
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => i64::try_from(pages).expect("Small count"),
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog = doc.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        doc.trailer.set("Root", catalog);
+        doc.save(path).expect("Failed to write PDF");
+    }
+
+    fn part(dir: &Path, name: &str, pages: usize) -> BundlePart {
+        let pdf = dir.join(format!("{name}.pdf"));
+        write_pdf(&pdf, pages);
+        BundlePart {
+            title: name.to_string(),
+            pdf,
+            txt: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_pdfs_keeps_order_and_bookmarks() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let parts = [
+            part(dir.path(), "letter", 1),
+            part(dir.path(), "cv", 2),
+            part(dir.path(), "certificate", 1),
+        ];
+        let output = dir.path().join("application.pdf");
+
+        let pages = merge_pdfs(&parts, &output, "Application").expect("Merge succeeds");
+        assert_eq!(pages, 4);
+
+        let merged = lopdf::Document::load(&output).expect("Merged PDF loads");
+        assert_eq!(merged.get_pages().len(), 4);
+        for id in merged.get_pages().into_values() {
+            let page = merged.get_dictionary(id).expect("Page exists");
+            assert!(page.has(b"MediaBox"), "Inherited media box is copied");
+        }
+
+        let catalog = merged.catalog().expect("Catalog exists");
+        let outlines = catalog
+            .get(b"Outlines")
+            .and_then(Object::as_reference)
+            .expect("Outline exists");
+        let outlines = merged.get_dictionary(outlines).expect("Outline dictionary");
+        assert_eq!(
+            outlines.get(b"Count").and_then(Object::as_i64).ok(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_write_zip_rejects_duplicate_names() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let letter = part(dir.path(), "letter", 1);
+        let output = dir.path().join("application.zip");
+
+        let names = write_zip(std::slice::from_ref(&letter), &output).expect("Zip is written");
+        assert_eq!(names, vec!["letter.pdf".to_string()]);
+        assert!(write_zip(&[letter.clone(), letter], &output).is_err());
+    }
+}
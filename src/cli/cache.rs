@@ -0,0 +1,225 @@
+//! Skipping renders whose inputs have not changed
+//!
+//! Every output gets a stamp in a `.cv-cache` directory beside it. The stamp holds
+//! a SHA-256 key over everything the render depends on (document source, themes,
+//! template, format, render options, anonymization and the tool version) together
+//! with the page count and warnings of that render, so a skipped build reports the
+//! same result. Outputs that print the build date are also keyed on that date, so
+//! they are rendered again the next day.
+
+use crate::build::BuildJob;
+use crate::parser::Document;
+use crate::render::{primary_output, RenderReport};
+use crate::themes::Theme;
+use anyhow::Result;
+use chrono::NaiveDate;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Directory, next to each output, that holds the cache stamps
+pub const CACHE_DIR: &str = ".cv-cache";
+
+/// Whether a build may reuse unchanged outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Skip outputs whose inputs have not changed
+    #[default]
+    Reuse,
+    /// Render everything and refresh the stamps (`--force`)
+    Refresh,
+}
+
+/// What is recorded for one output
+#[derive(Debug, Serialize, Deserialize)]
+struct Stamp {
+    key: String,
+    pages: Option<usize>,
+    warnings: Vec<String>,
+}
+
+/// Whether rendering `doc` prints the build date: a cover letter or a `{date}` in the
+/// header or footer when the frontmatter has no `date`, or a review build's footer.
+fn shows_build_date(job: &BuildJob, doc: &Document) -> bool {
+    let meta = &doc.metadata;
+    let date_placeholder = [&meta.header, &meta.footer]
+        .into_iter()
+        .flatten()
+        .flat_map(|text| [&text.left, &text.center, &text.right])
+        .flatten()
+        .any(|slot| slot.contains("{date}"));
+    let undated = meta.date.is_none() && (meta.recipient.is_some() || date_placeholder);
+    undated || job.review.is_some()
+}
+
+/// Computes the cache key for rendering `doc` as `job` with `theme`. `today` is the
+/// date the render would print, if it prints one.
+///
+/// # Errors
+///
+/// Returns an error if the input or template cannot be read.
+pub fn key(job: &BuildJob, doc: &Document, theme: &Theme, today: NaiveDate) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut add = |part: &[u8]| {
+        // Length-prefixed so that adjacent parts cannot run into each other
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    };
+
+    add(env!("CARGO_PKG_VERSION").as_bytes());
+    add(&std::fs::read(&job.input)?);
+    add(&serde_json::to_vec(theme)?);
+    match &job.template {
+        Some(template) => add(&std::fs::read(template)?),
+        None => add(b""),
+    }
+    add(job.format.as_bytes());
+    add(&job.dpi.to_le_bytes());
+    add(&[u8::from(job.pdf_a)]);
+    add(&[u8::from(job.print_safe)]);
+    add(&serde_json::to_vec(&job.review)?);
+    add(format!("{:?}", job.max_pages).as_bytes());
+    add(&serde_json::to_vec(&job.anonymize)?);
+    // A fixed build date changes the output; today's date only does when it is
+    // printed, such as on an undated cover letter
+    add(std::env::var("SOURCE_DATE_EPOCH")
+        .unwrap_or_default()
+        .as_bytes());
+    if shows_build_date(job, doc) {
+        add(today.to_string().as_bytes());
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Path of the stamp recorded for `output`.
+#[must_use]
+pub fn stamp_path(output: &Path) -> PathBuf {
+    let name = output
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    output
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(CACHE_DIR)
+        .join(format!("{name}.json"))
+}
+
+/// Returns the report of the previous render when `output` is still present and
+/// was rendered with the same `key`.
+#[must_use]
+pub fn lookup(output: &Path, format: &str, key: &str) -> Option<RenderReport> {
+    if !primary_output(format, output).exists() {
+        return None;
+    }
+
+    let content = std::fs::read_to_string(stamp_path(output)).ok()?;
+    let stamp: Stamp = serde_json::from_str(&content).ok()?;
+    (stamp.key == key).then_some(RenderReport {
+        pages: stamp.pages,
+        warnings: stamp.warnings,
+    })
+}
+
+/// Records that `output` was rendered with `key`. Failing to write the stamp only
+/// costs a rebuild next time, so it is logged rather than returned.
+pub fn store(output: &Path, key: &str, report: &RenderReport) {
+    let stamp = Stamp {
+        key: key.to_string(),
+        pages: report.pages,
+        warnings: report.warnings.clone(),
+    };
+    let path = stamp_path(output);
+
+    let written = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, serde_json::to_string(&stamp)?));
+    if let Err(e) = written {
+        warn!("Could not write cache stamp {}: {e}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn job(dir: &Path) -> BuildJob {
+        let input = dir.join("cv.md");
+        std::fs::write(&input, "---\nname: Jane\nemail: jane@example.com\n---\n")
+            .expect("Failed to write input");
+        BuildJob {
+            input,
+            format: "txt".to_string(),
+            output: dir.join("out/cv.txt"),
+            font_theme: "modern".to_string(),
+            color_theme: "modern".to_string(),
+            layout: None,
+            template: None,
+            dpi: 144,
+            pdf_a: false,
+            print_safe: false,
+            review: None,
+            max_pages: None,
+            anonymize: None,
+        }
+    }
+
+    #[test]
+    fn test_key_changes_with_inputs() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let job = job(dir.path());
+        let theme = Theme::new("modern", "modern").expect("Theme loads");
+        let doc = Document::from_file(&job.input).expect("Document parses");
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).expect("Valid date");
+        let key_of = |job: &BuildJob, theme: &Theme| {
+            super::key(job, &doc, theme, today).expect("Key computes")
+        };
+        let key = key_of(&job, &theme);
+
+        assert_eq!(key_of(&job, &theme), key);
+
+        let other_format = BuildJob {
+            format: "html".to_string(),
+            ..job.clone()
+        };
+        assert_ne!(key_of(&other_format, &theme), key);
+
+        let other_theme = Theme::new("modern", "sharp").expect("Theme loads");
+        assert_ne!(key_of(&job, &other_theme), key);
+
+        // A CV does not print the build date
+        let next_day = NaiveDate::from_ymd_opt(2026, 10, 19).expect("Valid date");
+        assert_eq!(
+            super::key(&job, &doc, &theme, next_day).expect("Key computes"),
+            key
+        );
+
+        std::fs::write(&job.input, "changed").expect("Failed to write input");
+        assert_ne!(key_of(&job, &theme), key);
+    }
+
+    #[test]
+    fn test_lookup_needs_output_and_matching_key() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let output = dir.path().join("cv.txt");
+        let report = RenderReport {
+            pages: Some(2),
+            warnings: vec!["warning".to_string()],
+        };
+
+        store(&output, "abc", &report);
+        assert!(stamp_path(&output).exists());
+        // The stamp alone is not enough
+        assert!(lookup(&output, "txt", "abc").is_none());
+
+        std::fs::write(&output, "cv").expect("Failed to write output");
+        let cached = lookup(&output, "txt", "abc").expect("Stamp matches");
+        assert_eq!(cached.pages, Some(2));
+        assert_eq!(cached.warnings, report.warnings);
+        assert!(lookup(&output, "txt", "def").is_none());
+    }
+}
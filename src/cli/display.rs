@@ -1,8 +1,10 @@
 //! Display utilities for CLI output
 //!
 //! This module provides styled display components for the CLI,
-//! including the suggestions box for CV tailoring feedback.
+//! including the suggestions box for CV tailoring feedback and the
+//! summary table printed after a multi-output build.
 
+use crate::cli::batch::BuildOutcome;
 use colored::Colorize;
 use log::{debug, info};
 use std::fmt::Write as FmtWrite;
@@ -178,7 +180,7 @@ impl<'a> SuggestionsBox<'a> {
 
         // Title line
         let title = "CV Suggestions";
-        writeln!(&mut output, "{VERTICAL}{title:^content_width$}{VERTICAL}",)
+        writeln!(&mut output, "{VERTICAL}{title:^content_width$}{VERTICAL}")
             .expect("Failed to write to string");
 
         // Separator
@@ -321,9 +323,153 @@ pub fn show_suggestions(suggestions: &[String]) {
     display_box.display();
 }
 
+/// Status column of the build summary
+fn build_status(outcome: &BuildOutcome) -> String {
+    let status = match (outcome.succeeded(), outcome.cached) {
+        (false, _) => return "failed".to_string(),
+        (true, false) => "ok",
+        (true, true) => "unchanged",
+    };
+    match outcome.warnings.len() {
+        0 => status.to_string(),
+        1 => format!("{status}, 1 warning"),
+        n => format!("{status}, {n} warnings"),
+    }
+}
+
+/// Formats build outcomes as an aligned table, followed by any error details
+#[must_use]
+pub fn format_build_summary(outcomes: &[BuildOutcome]) -> String {
+    let headers = ["Input", "Format", "Output", "Pages", "Time", "Status"];
+    let rows: Vec<[String; 6]> = outcomes
+        .iter()
+        .map(|outcome| {
+            [
+                outcome.input.display().to_string(),
+                outcome.format.clone(),
+                outcome.output.display().to_string(),
+                outcome
+                    .pages
+                    .map_or_else(|| "-".to_string(), |p| p.to_string()),
+                format!("{:.2}s", outcome.elapsed.as_secs_f64()),
+                build_status(outcome),
+            ]
+        })
+        .collect();
+
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    let header_line: Vec<String> = headers
+        .iter()
+        .zip(widths)
+        .map(|(h, w)| format!("{h:<w$}"))
+        .collect();
+    writeln!(&mut output, "{}", header_line.join("  ").trim_end().bold())
+        .expect("Failed to write to string");
+
+    for (row, outcome) in rows.iter().zip(outcomes) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, w)| format!("{cell:<w$}"))
+            .collect();
+        let line = cells.join("  ");
+        let line = line.trim_end();
+        if outcome.succeeded() {
+            writeln!(&mut output, "{line}").expect("Failed to write to string");
+        } else {
+            writeln!(&mut output, "{}", line.red()).expect("Failed to write to string");
+        }
+    }
+
+    for outcome in outcomes {
+        for warning in &outcome.warnings {
+            writeln!(
+                &mut output,
+                "\n{} {} ({}): {}",
+                "⚠".yellow(),
+                outcome.input.display(),
+                outcome.format,
+                warning
+            )
+            .expect("Failed to write to string");
+        }
+        if let Some(error) = &outcome.error {
+            writeln!(
+                &mut output,
+                "\n{} {} ({}): {}",
+                "✗".red(),
+                outcome.input.display(),
+                outcome.format,
+                error.trim_end()
+            )
+            .expect("Failed to write to string");
+        }
+    }
+
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn test_format_build_summary() {
+        colored::control::set_override(false);
+        let outcomes = [
+            BuildOutcome {
+                input: PathBuf::from("cv.md"),
+                format: "pdf".to_string(),
+                output: PathBuf::from("cv.pdf"),
+                pages: Some(2),
+                warnings: vec!["unknown font family: inter".to_string()],
+                cached: false,
+                elapsed: Duration::from_millis(1500),
+                error: None,
+            },
+            BuildOutcome {
+                input: PathBuf::from("cv.md"),
+                format: "html".to_string(),
+                output: PathBuf::from("cv.html"),
+                pages: None,
+                warnings: Vec::new(),
+                cached: false,
+                elapsed: Duration::ZERO,
+                error: Some("disk full".to_string()),
+            },
+            BuildOutcome {
+                input: PathBuf::from("cv.md"),
+                format: "txt".to_string(),
+                output: PathBuf::from("cv.txt"),
+                pages: None,
+                warnings: Vec::new(),
+                cached: true,
+                elapsed: Duration::ZERO,
+                error: None,
+            },
+        ];
+
+        let summary = format_build_summary(&outcomes);
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines[0], "Input  Format  Output   Pages  Time   Status");
+        assert_eq!(
+            lines[1],
+            "cv.md  pdf     cv.pdf   2      1.50s  ok, 1 warning"
+        );
+        assert_eq!(lines[2], "cv.md  html    cv.html  -      0.00s  failed");
+        assert_eq!(lines[3], "cv.md  txt     cv.txt   -      0.00s  unchanged");
+        assert!(summary.contains("cv.md (pdf): unknown font family: inter"));
+        assert!(summary.contains("cv.md (html): disk full"));
+    }
 
     #[test]
     fn test_wrap_text_short() {
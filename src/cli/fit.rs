@@ -0,0 +1,408 @@
+//! Fitting a document onto a maximum number of pages (`cv build --max-pages`)
+//!
+//! Fitting works in stages, re-measuring after every step and stopping as soon as
+//! the document fits:
+//!
+//! 1. Tighten the heading spacing, down to the color theme's minimum scale.
+//! 2. Shrink the body text in half-point steps, down to the font theme's minimum.
+//! 3. Drop bullets, starting with the last bullet of the oldest entry, keeping at
+//!    least one bullet per entry.
+//! 4. Drop the oldest entries, keeping at least one entry per section.
+//!
+//! Entries are expected in reverse chronological order, so later entries and later
+//! bullets within an entry are treated as the lowest priority.
+
+use crate::parser::Document;
+use crate::themes::Theme;
+use anyhow::Result;
+use std::ops::Range;
+
+/// Heading spacing scales tried in turn, largest first
+const SPACING_STEPS: &[f32] = &[0.9, 0.8, 0.7, 0.6, 0.5, 0.4];
+
+/// Body text size reduction per step, in points
+const FONT_STEP: f32 = 0.5;
+
+/// Longest bullet excerpt shown in the list of changes
+const EXCERPT_LENGTH: usize = 40;
+
+/// The document and theme after fitting
+#[derive(Debug, Clone)]
+pub struct FitResult {
+    pub doc: Document,
+    pub theme: Theme,
+    /// Page count of the last measured render
+    pub pages: usize,
+    /// Description of each change made, in order
+    pub changes: Vec<String>,
+}
+
+/// Tightens and trims `doc` until `measure` reports at most `max_pages` pages.
+///
+/// `measure` renders a candidate and returns its page count. When every step has
+/// been tried and the document is still too long, the result holds the shortest
+/// version reached; callers compare `pages` against `max_pages`.
+///
+/// # Errors
+///
+/// Returns an error if `max_pages` is zero or `measure` fails.
+pub fn fit_to_pages<F>(
+    doc: &Document,
+    theme: &Theme,
+    max_pages: usize,
+    mut measure: F,
+) -> Result<FitResult>
+where
+    F: FnMut(&Document, &Theme) -> Result<usize>,
+{
+    if max_pages == 0 {
+        anyhow::bail!("The page limit must be at least 1");
+    }
+
+    let mut result = FitResult {
+        doc: doc.clone(),
+        theme: theme.clone(),
+        pages: measure(doc, theme)?,
+        changes: Vec::new(),
+    };
+
+    tighten_spacing(&mut result, theme, max_pages, &mut measure)?;
+    shrink_text(&mut result, theme, max_pages, &mut measure)?;
+
+    while result.pages > max_pages {
+        let Some((content, change)) =
+            drop_bullet(&result.doc.content).or_else(|| drop_entry(&result.doc.content))
+        else {
+            break;
+        };
+        result.doc = result.doc.with_content(content);
+        result.pages = measure(&result.doc, &result.theme)?;
+        result.changes.push(change);
+    }
+
+    Ok(result)
+}
+
+fn tighten_spacing<F>(
+    result: &mut FitResult,
+    theme: &Theme,
+    max_pages: usize,
+    measure: &mut F,
+) -> Result<()>
+where
+    F: FnMut(&Document, &Theme) -> Result<usize>,
+{
+    let min_scale = theme.layout.get_min_spacing_scale();
+    let mut applied = None;
+
+    for &scale in SPACING_STEPS {
+        if result.pages <= max_pages || scale < min_scale - f32::EPSILON {
+            break;
+        }
+        result.theme.layout = theme.layout.with_spacing_scale(scale);
+        result.pages = measure(&result.doc, &result.theme)?;
+        applied = Some(scale);
+    }
+
+    if let Some(scale) = applied {
+        result.changes.push(format!(
+            "Tightened heading spacing to {:.0}% of the theme",
+            scale * 100.0
+        ));
+    }
+    Ok(())
+}
+
+fn shrink_text<F>(
+    result: &mut FitResult,
+    theme: &Theme,
+    max_pages: usize,
+    measure: &mut F,
+) -> Result<()>
+where
+    F: FnMut(&Document, &Theme) -> Result<usize>,
+{
+    let body = &theme.font.body;
+    let (Some(start), Some(min)) = (points(&body.size_normal), points(&body.min_size_normal))
+    else {
+        return Ok(());
+    };
+
+    let mut size = start;
+    while result.pages > max_pages && size - FONT_STEP >= min - f32::EPSILON {
+        size -= FONT_STEP;
+        result.theme.font.body.size_normal = format!("{size}pt");
+        result.pages = measure(&result.doc, &result.theme)?;
+    }
+
+    if size < start {
+        result
+            .changes
+            .push(format!("Reduced body text from {start}pt to {size}pt"));
+    }
+    Ok(())
+}
+
+/// Parses a size such as `11pt` into points.
+fn points(size: &str) -> Option<f32> {
+    size.trim().strip_suffix("pt")?.trim().parse().ok()
+}
+
+/// A `##` entry and the line ranges of its top-level bullets
+#[derive(Debug)]
+struct Entry {
+    title: String,
+    lines: Range<usize>,
+    bullets: Vec<Range<usize>>,
+}
+
+/// Splits markdown into `#` sections holding their `##` entries.
+fn outline(lines: &[&str]) -> Vec<Vec<Entry>> {
+    let mut sections: Vec<Vec<Entry>> = vec![Vec::new()];
+    let mut current: Option<Entry> = None;
+    let mut in_code = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if in_code {
+            continue;
+        }
+
+        let section = line.starts_with("# ");
+        let entry = line.starts_with("## ");
+        if section || entry || is_page_break(line) {
+            if let Some(mut done) = current.take() {
+                done.lines.end = i;
+                if let Some(last) = sections.last_mut() {
+                    last.push(done);
+                }
+            }
+        }
+        if section {
+            sections.push(Vec::new());
+        }
+        if entry {
+            current = Some(Entry {
+                title: line[3..].trim().to_string(),
+                lines: i..lines.len(),
+                bullets: Vec::new(),
+            });
+        }
+
+        if let Some(entry) = current.as_mut() {
+            if is_list_item(line) {
+                entry.bullets.push(i..i + 1);
+            } else if let Some(bullet) = entry.bullets.last_mut() {
+                // Indented and lazy continuation lines belong to the bullet above
+                let continues =
+                    !line.trim().is_empty() && !line.starts_with('#') && bullet.end == i;
+                if continues {
+                    bullet.end = i + 1;
+                }
+            }
+        }
+    }
+
+    if let Some(done) = current {
+        if let Some(last) = sections.last_mut() {
+            last.push(done);
+        }
+    }
+    sections
+}
+
+fn is_list_item(line: &str) -> bool {
+    if ["- ", "* ", "+ "]
+        .iter()
+        .any(|marker| line.starts_with(marker))
+    {
+        return true;
+    }
+    line.split_once(". ")
+        .is_some_and(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_page_break(line: &str) -> bool {
+    matches!(line.trim(), "<!-- pagebreak -->" | "\\pagebreak")
+}
+
+/// Removes the last bullet of the oldest entry that has more than one.
+fn drop_bullet(content: &str) -> Option<(String, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let sections = outline(&lines);
+
+    let (entry, bullet) = sections
+        .iter()
+        .flat_map(|entries| entries.iter().enumerate())
+        .filter(|(_, entry)| entry.bullets.len() > 1)
+        .max_by_key(|(age, entry)| (*age, entry.lines.start))
+        .and_then(|(_, entry)| entry.bullets.last().map(|bullet| (entry, bullet.clone())))?;
+
+    let change = format!(
+        "Dropped bullet from \"{}\": {}",
+        entry.title,
+        excerpt(lines[bullet.start])
+    );
+    Some((remove_lines(&lines, &bullet), change))
+}
+
+/// Removes the oldest entry of a section that has more than one.
+fn drop_entry(content: &str) -> Option<(String, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let sections = outline(&lines);
+
+    let entry = sections
+        .iter()
+        .filter(|entries| entries.len() > 1)
+        .filter_map(|entries| entries.last().map(|entry| (entries.len(), entry)))
+        .max_by_key(|(age, entry)| (*age, entry.lines.start))
+        .map(|(_, entry)| entry)?;
+
+    let change = format!("Dropped entry \"{}\"", entry.title);
+    Some((remove_lines(&lines, &entry.lines), change))
+}
+
+fn remove_lines(lines: &[&str], range: &Range<usize>) -> String {
+    let mut content = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !range.contains(&i) {
+            content.push_str(line);
+            content.push('\n');
+        }
+    }
+    content
+}
+
+/// Shortened bullet text without its list marker.
+fn excerpt(line: &str) -> String {
+    let text = line
+        .trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, '-' | '*' | '+' | '.'))
+        .trim();
+    if text.chars().count() > EXCERPT_LENGTH {
+        let short: String = text.chars().take(EXCERPT_LENGTH).collect();
+        format!("\"{}...\"", short.trim_end())
+    } else {
+        format!("\"{text}\"")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const CV: &str = "---\nname: Jane\nemail: jane@example.com\n---\n\
+# Experience\n\n\
+## Lead Engineer\n\n\
+- Led the platform team\n\
+- Cut deploy time by 70%\n\n\
+## Engineer\n\n\
+- Built the billing service\n  with Stripe\n\
+- Wrote the onboarding docs\n\n\
+# Education\n\n\
+## BSc Computer Science\n\n\
+- First class honours\n";
+
+    fn doc() -> Document {
+        Document::from_string(CV, Path::new("cv.md")).expect("Valid document")
+    }
+
+    fn theme() -> Theme {
+        Theme::new("modern", "modern").expect("Valid theme")
+    }
+
+    #[test]
+    fn test_fitting_document_is_unchanged() {
+        let result = fit_to_pages(&doc(), &theme(), 2, |_, _| Ok(1)).expect("Fit succeeds");
+        assert_eq!(result.pages, 1);
+        assert!(result.changes.is_empty());
+        assert_eq!(result.doc.content, doc().content);
+    }
+
+    #[test]
+    fn test_spacing_is_tightened_first() {
+        // Fits once the heading spacing is at 80%
+        let result = fit_to_pages(&doc(), &theme(), 1, |_, theme| {
+            Ok(if theme.layout.get_h1_spacing_above() > 2.0 {
+                2
+            } else {
+                1
+            })
+        })
+        .expect("Fit succeeds");
+
+        assert_eq!(result.pages, 1);
+        assert_eq!(
+            result.changes,
+            vec!["Tightened heading spacing to 80% of the theme"]
+        );
+        assert_eq!(result.theme.font.body.size_normal, "11pt");
+    }
+
+    #[test]
+    fn test_text_shrinks_after_spacing() {
+        let result = fit_to_pages(&doc(), &theme(), 1, |_, theme| {
+            Ok(if theme.font.body.size_normal == "10pt" {
+                1
+            } else {
+                2
+            })
+        })
+        .expect("Fit succeeds");
+
+        assert_eq!(result.changes.len(), 2);
+        assert_eq!(result.changes[1], "Reduced body text from 11pt to 10pt");
+    }
+
+    #[test]
+    fn test_oldest_bullets_then_entries_are_dropped() {
+        // Only fits once the second Experience entry is gone
+        let result = fit_to_pages(&doc(), &theme(), 1, |doc, _| {
+            Ok(if doc.content.contains("## Engineer") {
+                2
+            } else {
+                1
+            })
+        })
+        .expect("Fit succeeds");
+
+        let content = &result.doc.content;
+        assert_eq!(result.pages, 1);
+        assert!(result.changes.contains(
+            &"Dropped bullet from \"Engineer\": \"Wrote the onboarding docs\"".to_string()
+        ));
+        assert!(result.changes.contains(
+            &"Dropped bullet from \"Lead Engineer\": \"Cut deploy time by 70%\"".to_string()
+        ));
+        assert_eq!(
+            result.changes.last().map(String::as_str),
+            Some("Dropped entry \"Engineer\"")
+        );
+        assert!(content.contains("- Led the platform team"));
+        assert!(content.contains("## BSc Computer Science"));
+        assert!(!content.contains("with Stripe"));
+    }
+
+    #[test]
+    fn test_unreachable_target_returns_shortest_version() {
+        let result = fit_to_pages(&doc(), &theme(), 1, |_, _| Ok(3)).expect("Fit runs");
+        assert_eq!(result.pages, 3);
+        assert!(result.doc.content.contains("## Lead Engineer"));
+        assert!(result.doc.content.contains("## BSc Computer Science"));
+        assert!(!result.doc.content.contains("## Engineer\n"));
+    }
+
+    #[test]
+    fn test_zero_pages_is_rejected() {
+        assert!(fit_to_pages(&doc(), &theme(), 0, |_, _| Ok(1)).is_err());
+    }
+
+    #[test]
+    fn test_bullet_continuation_lines() {
+        let lines = ["## Role", "- One", "  more", "- Two", "", "Text"];
+        let sections = outline(&lines);
+        assert_eq!(sections[0][0].bullets, vec![1..3, 3..4]);
+    }
+}
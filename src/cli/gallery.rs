@@ -0,0 +1,205 @@
+//! Theme gallery (`cv themes --gallery`)
+//!
+//! Renders a thumbnail of the sample CV for every font and colour theme pairing,
+//! built-in and custom, and an HTML index page showing each thumbnail with the
+//! `-f`/`-c` flags that select it.
+
+use crate::render::html::escape_html;
+use crate::themes::overrides::is_theme_file;
+use crate::themes::Theme;
+use anyhow::Result;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the gallery's index page
+pub const INDEX_FILE: &str = "index.html";
+
+/// A font or colour theme shown in the gallery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GalleryTheme {
+    /// Short name used in captions and file names, unique within a gallery
+    pub label: String,
+    /// Built-in theme name or theme file path, as passed to `-f`/`-c`
+    pub name: String,
+}
+
+/// One font and colour pairing, and the file name of its thumbnail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GalleryEntry {
+    pub font: GalleryTheme,
+    pub color: GalleryTheme,
+    pub thumbnail: String,
+}
+
+/// The built-in themes, followed by the theme files in `custom_dir` (sorted by
+/// name). Each custom file is offered as both a font and a colour theme, labelled
+/// by its file stem, with a `-2`, `-3`, ... suffix when that label is taken.
+///
+/// # Errors
+///
+/// Returns an error if `custom_dir` exists but cannot be read.
+pub fn gallery_themes(custom_dir: Option<&Path>) -> Result<Vec<GalleryTheme>> {
+    let (built_in, _) = Theme::available_themes();
+    let mut themes: Vec<GalleryTheme> = built_in
+        .into_iter()
+        .map(|name| GalleryTheme {
+            label: name.to_string(),
+            name: name.to_string(),
+        })
+        .collect();
+
+    let Some(dir) = custom_dir.filter(|dir| dir.is_dir()) else {
+        return Ok(themes);
+    };
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_theme_file(&path.to_string_lossy()))
+        .collect();
+    files.sort();
+
+    for path in files {
+        let stem = path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        // A custom `modern.yaml` must not share the built-in modern's thumbnails
+        let mut label = stem.clone();
+        for n in 2.. {
+            if !themes.iter().any(|theme| theme.label == label) {
+                break;
+            }
+            label = format!("{stem}-{n}");
+        }
+        themes.push(GalleryTheme {
+            label,
+            name: path.to_string_lossy().into_owned(),
+        });
+    }
+    Ok(themes)
+}
+
+/// Every font and colour pairing of `themes`, fonts outermost.
+#[must_use]
+pub fn gallery_entries(themes: &[GalleryTheme]) -> Vec<GalleryEntry> {
+    themes
+        .iter()
+        .flat_map(|font| {
+            themes.iter().map(|color| GalleryEntry {
+                font: font.clone(),
+                color: color.clone(),
+                thumbnail: format!("{}-{}.png", font.label, color.label),
+            })
+        })
+        .collect()
+}
+
+/// The command-line flags selecting an entry's themes.
+#[must_use]
+pub fn theme_flags(entry: &GalleryEntry) -> String {
+    let quote = |name: &str| {
+        if name.contains(char::is_whitespace) {
+            format!("\"{name}\"")
+        } else {
+            name.to_string()
+        }
+    };
+    format!(
+        "-f {} -c {}",
+        quote(&entry.font.name),
+        quote(&entry.color.name)
+    )
+}
+
+/// HTML index page showing each entry's thumbnail with its flags.
+#[must_use]
+pub fn index_html(entries: &[GalleryEntry]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+    <meta charset=\"UTF-8\">
+    <title>Theme gallery</title>
+    <style>
+        body { font-family: sans-serif; margin: 2rem; color: #333333; }
+        main { display: grid; grid-template-columns: repeat(auto-fill, minmax(14rem, 1fr)); gap: 1.5rem; }
+        figure { margin: 0; }
+        img { width: 100%; border: 1px solid #E5E7EB; }
+        figcaption { margin-top: 0.5rem; }
+        code { display: block; margin-top: 0.25rem; color: #666666; }
+    </style>
+</head>
+<body>
+    <h1>Theme gallery</h1>
+    <p>Build with the flags under a thumbnail, e.g. <code>cv build cv.md -f modern -c sharp</code></p>
+    <main>
+",
+    );
+    for entry in entries {
+        let font = escape_html(&entry.font.label);
+        let color = escape_html(&entry.color.label);
+        let _ = writeln!(
+            html,
+            "        <figure>
+            <img src=\"{}\" alt=\"Sample CV with {font} fonts and {color} colours\" loading=\"lazy\">
+            <figcaption><strong>{font} × {color}</strong><code>{}</code></figcaption>
+        </figure>",
+            escape_html(&entry.thumbnail),
+            escape_html(&theme_flags(entry))
+        );
+    }
+    html.push_str("    </main>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gallery_includes_custom_themes() {
+        let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        std::fs::write(dir.path().join("brand.yaml"), "extends: sharp\n").expect("Write theme");
+        std::fs::write(dir.path().join("notes.txt"), "not a theme").expect("Write file");
+
+        let themes = gallery_themes(Some(dir.path())).expect("Themes listed");
+        let labels: Vec<_> = themes.iter().map(|theme| theme.label.as_str()).collect();
+        assert_eq!(labels, ["classic", "modern", "sharp", "brand"]);
+
+        let entries = gallery_entries(&themes);
+        assert_eq!(entries.len(), 16);
+        assert_eq!(entries[1].thumbnail, "classic-modern.png");
+
+        let custom = entries.last().expect("Entries exist");
+        let brand = dir.path().join("brand.yaml").to_string_lossy().into_owned();
+        assert_eq!(theme_flags(custom), format!("-f {brand} -c {brand}"));
+    }
+
+    #[test]
+    fn test_custom_theme_named_like_a_built_in_gets_its_own_thumbnails() {
+        let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        std::fs::write(dir.path().join("modern.yaml"), "extends: sharp\n").expect("Write theme");
+        std::fs::write(dir.path().join("modern.yml"), "extends: classic\n").expect("Write theme");
+
+        let themes = gallery_themes(Some(dir.path())).expect("Themes listed");
+        let labels: Vec<_> = themes.iter().map(|theme| theme.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["classic", "modern", "sharp", "modern-2", "modern-3"]
+        );
+
+        let entries = gallery_entries(&themes);
+        let mut thumbnails: Vec<_> = entries.iter().map(|entry| &entry.thumbnail).collect();
+        thumbnails.sort();
+        thumbnails.dedup();
+        assert_eq!(thumbnails.len(), entries.len());
+    }
+
+    #[test]
+    fn test_index_lists_thumbnails_and_flags() {
+        let themes = gallery_themes(None).expect("Themes listed");
+        let html = index_html(&gallery_entries(&themes));
+
+        assert_eq!(html.matches("<figure>").count(), 9);
+        assert!(html.contains("<img src=\"modern-sharp.png\""));
+        assert!(html.contains("<strong>modern × sharp</strong><code>-f modern -c sharp</code>"));
+    }
+}
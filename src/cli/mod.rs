@@ -1,28 +1,64 @@
+pub mod batch;
+pub mod bundle;
+pub mod cache;
 pub mod display;
+pub mod fit;
+pub mod gallery;
+pub mod theme_list;
 
 use crate::ai::{extract_text_from_pdf, AIClient};
+use crate::build::{output_path, Anonymizer, BuildJob};
 use crate::config::GlobalConfig;
+use crate::error::CvError;
+use crate::manifest::Manifest;
 use crate::parser::Document;
-use crate::render::Renderer;
-use crate::themes::{get_theme_info, Theme};
+use crate::render::pdf::creation_date;
+use crate::render::{output_extension, primary_output, RenderOptions, RenderReport, Renderer};
+use crate::themes::{contrast, generate, Theme};
 use anyhow::Result;
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use log::{info, warn};
 use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use theme_list::{ThemeSections, TypstFonts};
+
+pub use batch::{BatchOptions, BuildOutcome};
+pub use bundle::{BundleOptions, BundleReport};
+pub use cache::CacheMode;
 
 pub struct BuildOptions<'a> {
     pub input: &'a Path,
     pub font_theme: &'a str,
     pub color_theme: &'a str,
+    /// Layout theme; the color theme's layout when not given
+    pub layout: Option<&'a str>,
     pub output: Option<&'a Path>,
     pub format: &'a str,
     pub template: Option<&'a Path>,
+    pub render: RenderOptions,
+    /// Tighten and trim the document until it fits on this many pages
+    pub max_pages: Option<usize>,
+    /// Treat renderer warnings as a build failure
+    pub fail_on_warnings: bool,
+    /// Whether an up-to-date output may be left as it is
+    pub cache: CacheMode,
+    /// Strip personal details from the output
+    pub anonymize: Option<&'a Anonymizer>,
     pub verbose: bool,
     pub quiet: bool,
 }
 
+/// Formats that have pages and so support `--max-pages`
+pub const PAGED_FORMATS: &[&str] = &["pdf", "png", "svg"];
+
+/// Resolution used for theme preview thumbnails
+pub const THUMBNAIL_DPI: u32 = 48;
+
 pub struct TailorOptions<'a> {
     pub cv_path: &'a Path,
     pub job_description_path: &'a Path,
@@ -30,6 +66,8 @@ pub struct TailorOptions<'a> {
     pub font_theme: &'a str,
     pub color_theme: &'a str,
     pub format: &'a str,
+    /// Named AI profile from the global config
+    pub profile: Option<&'a str>,
     pub verbose: bool,
     pub quiet: bool,
 }
@@ -38,6 +76,15 @@ pub struct CvGenerator {
     config: GlobalConfig,
 }
 
+/// What running one build job produced
+struct JobResult {
+    report: RenderReport,
+    /// Changes made to fit the page limit
+    changes: Vec<String>,
+    /// The output was up to date and left as it was
+    cached: bool,
+}
+
 impl CvGenerator {
     /// Creates a new CV generator instance.
     ///
@@ -59,36 +106,61 @@ impl CvGenerator {
         let doc = Document::from_file(options.input)?;
         doc.validate()?;
 
-        // Load theme
-        let theme = Theme::new(options.font_theme, options.color_theme)?;
-
-        // Determine output path
-        let output_path = if let Some(path) = options.output {
-            path.to_path_buf()
-        } else {
-            let stem = options
-                .input
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("output");
-            let ext = match options.format {
-                "pdf" | "docx" | "html" => options.format,
-                _ => "pdf",
-            };
-            PathBuf::from(format!("{stem}.{ext}"))
+        // A single build is a batch of one job, so the cache, page limit and
+        // anonymizing work the same way
+        let job = BuildJob {
+            input: options.input.to_path_buf(),
+            format: options.format.to_string(),
+            output: output_path(options.input, options.format, options.output, false),
+            font_theme: options.font_theme.to_string(),
+            color_theme: options.color_theme.to_string(),
+            layout: options.layout.map(str::to_string),
+            template: options.template.map(Path::to_path_buf),
+            dpi: options.render.dpi,
+            pdf_a: options.render.pdf_a,
+            print_safe: options.render.print_safe,
+            review: options.render.review.clone(),
+            max_pages: options.max_pages,
+            anonymize: options.anonymize.cloned(),
         };
+        let JobResult {
+            report,
+            changes,
+            cached,
+        } = Self::render_job_cached(&doc, &job, options.cache)?;
 
-        // Create output directory if needed
-        if let Some(parent) = output_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        // Render document
-        let renderer = Renderer::new(options.format, options.template)?;
-        renderer.render(&doc, &theme, &output_path)?;
+        // Image formats write one file per page; report and open the first one
+        let output_path = primary_output(options.format, &job.output);
 
         if !options.quiet {
-            println!("{} Output: {}", "→".blue(), output_path.display());
+            if let (Some(max_pages), false) = (options.max_pages, changes.is_empty()) {
+                println!("{} Changes made to fit {max_pages} page(s):", "→".blue());
+                for change in &changes {
+                    println!("  • {change}");
+                }
+            }
+            if cached {
+                println!(
+                    "{} Output: {} (unchanged, use --force to rebuild)",
+                    "→".blue(),
+                    output_path.display()
+                );
+            } else {
+                println!("{} Output: {}", "→".blue(), output_path.display());
+            }
+            if let Some(pages) = report.pages {
+                println!("  Pages: {pages}");
+            }
+            for warning in &report.warnings {
+                eprintln!("{} {warning}", "⚠".yellow());
+            }
+        }
+
+        if options.fail_on_warnings && !report.warnings.is_empty() {
+            return Err(CvError::RenderWarnings {
+                count: report.warnings.len(),
+            }
+            .into());
         }
 
         if options.verbose && !options.quiet {
@@ -96,6 +168,10 @@ impl CvGenerator {
             println!("  Color theme: {}", options.color_theme);
             println!("  Format: {}", options.format);
             println!("  Auto-open: {}", self.config.auto_open.unwrap_or(true));
+            println!("  Resolved theme:");
+            for line in serde_yaml::to_string(&Self::job_theme(&doc, &job)?)?.lines() {
+                println!("    {line}");
+            }
         }
 
         info!("Output path: {}", output_path.display());
@@ -110,6 +186,395 @@ impl CvGenerator {
         Ok(())
     }
 
+    /// Builds every input in every requested format, rendering in parallel.
+    ///
+    /// Parse and render failures are recorded in the returned outcomes rather than
+    /// stopping the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the theme cannot be loaded or two renders would write the
+    /// same output file.
+    pub fn build_batch(options: &BatchOptions) -> Result<Vec<BuildOutcome>> {
+        // Fail fast on a bad theme rather than once per output
+        Theme::new(options.font_theme, options.color_theme)?.with_layout(options.layout)?;
+
+        let multiple = options.inputs.len() * options.formats.len() > 1;
+        let mut jobs = Vec::new();
+        for input in options.inputs {
+            for format in options.formats {
+                jobs.push(BuildJob {
+                    input: input.clone(),
+                    format: format.clone(),
+                    output: output_path(input, format, options.output, multiple),
+                    font_theme: options.font_theme.to_string(),
+                    color_theme: options.color_theme.to_string(),
+                    layout: options.layout.map(str::to_string),
+                    template: options.template.map(Path::to_path_buf),
+                    dpi: options.render.dpi,
+                    pdf_a: options.render.pdf_a,
+                    print_safe: options.render.print_safe,
+                    review: options.render.review.clone(),
+                    max_pages: options.max_pages,
+                    anonymize: options.anonymize.cloned(),
+                });
+            }
+        }
+
+        Self::run_jobs(&jobs, options.cache)
+    }
+
+    /// Renders one job, timing it and capturing any error in the outcome.
+    fn build_outcome(
+        doc: Option<&Result<Document, String>>,
+        job: &BuildJob,
+        cache: CacheMode,
+    ) -> BuildOutcome {
+        let started = Instant::now();
+        let result = match doc {
+            Some(Ok(doc)) => Self::render_job_cached(doc, job, cache),
+            Some(Err(e)) => Err(anyhow::anyhow!("{e}")),
+            None => Err(anyhow::anyhow!("Document was not parsed")),
+        };
+        let elapsed = started.elapsed();
+        let (report, cached, error) = match result {
+            Ok(result) => (result.report, result.cached, None),
+            Err(e) => (RenderReport::default(), false, Some(e.to_string())),
+        };
+        BuildOutcome {
+            input: job.input.clone(),
+            format: job.format.clone(),
+            output: job.output.clone(),
+            pages: report.pages,
+            warnings: report.warnings,
+            cached,
+            elapsed,
+            error,
+        }
+    }
+
+    /// Runs build jobs in parallel, at most one per CPU, parsing each input document
+    /// only once. With
+    /// [`CacheMode::Reuse`], outputs whose inputs have not changed are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two jobs would write the same output file.
+    pub fn run_jobs(jobs: &[BuildJob], cache: CacheMode) -> Result<Vec<BuildOutcome>> {
+        // Catch collisions before anything is written
+        for (i, job) in jobs.iter().enumerate() {
+            if let Some(other) = jobs[..i].iter().find(|o| o.output == job.output) {
+                anyhow::bail!(
+                    "{} and {} would both write {}",
+                    other.input.display(),
+                    job.input.display(),
+                    job.output.display()
+                );
+            }
+        }
+
+        // Parse each document once
+        let mut docs: Vec<(&Path, Result<Document, String>)> = Vec::new();
+        for job in jobs {
+            if !docs.iter().any(|(path, _)| *path == job.input) {
+                let doc = Document::from_file(&job.input)
+                    .and_then(|doc| doc.validate().map(|()| doc))
+                    .map_err(|e| e.to_string());
+                docs.push((&job.input, doc));
+            }
+        }
+
+        // A fixed pool of workers takes jobs in turn, so large batches don't start a
+        // Typst process per job all at once
+        let workers = std::thread::available_parallelism()
+            .map_or(1, std::num::NonZero::get)
+            .min(jobs.len());
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                let sender = sender.clone();
+                let (next, docs) = (&next, &docs);
+                scope.spawn(move || {
+                    while let Some(job) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        let doc = docs
+                            .iter()
+                            .find(|(path, _)| *path == job.input)
+                            .map(|(_, doc)| doc);
+                        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                            Self::build_outcome(doc, job, cache)
+                        }))
+                        .unwrap_or_else(|_| BuildOutcome {
+                            input: job.input.clone(),
+                            format: job.format.clone(),
+                            output: job.output.clone(),
+                            pages: None,
+                            warnings: Vec::new(),
+                            cached: false,
+                            elapsed: Duration::ZERO,
+                            error: Some("Renderer panicked".to_string()),
+                        });
+                        if sender.send((job.output.clone(), outcome)).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        drop(sender);
+
+        // Report outcomes in job order; outputs are unique, checked above
+        let mut finished: Vec<(PathBuf, BuildOutcome)> = receiver.into_iter().collect();
+        let outcomes: Vec<BuildOutcome> = jobs
+            .iter()
+            .filter_map(|job| {
+                let i = finished
+                    .iter()
+                    .position(|(output, _)| *output == job.output)?;
+                Some(finished.swap_remove(i).1)
+            })
+            .collect();
+
+        for outcome in &outcomes {
+            info!(
+                "{} -> {} ({:?})",
+                outcome.input.display(),
+                outcome.output.display(),
+                outcome.elapsed
+            );
+        }
+
+        Ok(outcomes)
+    }
+
+    /// The theme `job` renders `doc` with, including the document's overrides.
+    fn job_theme(doc: &Document, job: &BuildJob) -> Result<Theme> {
+        Ok(Theme::new(&job.font_theme, &job.color_theme)?
+            .with_layout(job.layout.as_deref())?
+            .with_overrides(doc.metadata.theme_overrides.as_ref())?
+            .with_icons(doc.metadata.icons))
+    }
+
+    /// Renders `job` unless its output is up to date.
+    fn render_job_cached(doc: &Document, job: &BuildJob, cache: CacheMode) -> Result<JobResult> {
+        let theme = Self::job_theme(doc, job)?;
+        let key = cache::key(job, doc, &theme, creation_date())?;
+
+        if cache == CacheMode::Reuse {
+            if let Some(report) = cache::lookup(&job.output, &job.format, &key) {
+                return Ok(JobResult {
+                    report,
+                    changes: Vec::new(),
+                    cached: true,
+                });
+            }
+        }
+
+        let scrubbed;
+        let doc = match &job.anonymize {
+            Some(anonymizer) => {
+                scrubbed = anonymizer.apply(doc);
+                &scrubbed
+            }
+            None => doc,
+        };
+
+        let (report, changes) = Self::render_job(doc, job, theme)?;
+        cache::store(&job.output, &key, &report);
+        Ok(JobResult {
+            report,
+            changes,
+            cached: false,
+        })
+    }
+
+    /// Fits `doc` to the job's page limit, if any, and renders it. Returns the report
+    /// and the changes made to fit.
+    fn render_job(
+        doc: &Document,
+        job: &BuildJob,
+        theme: Theme,
+    ) -> Result<(RenderReport, Vec<String>)> {
+        let fitted;
+        let (doc, theme, changes) = match job.max_pages {
+            Some(max_pages) => {
+                fitted = Self::fit_document(
+                    doc,
+                    &theme,
+                    &job.format,
+                    job.template.as_deref(),
+                    max_pages,
+                    job.print_safe,
+                )?;
+                for change in &fitted.changes {
+                    info!("{}: {change}", job.input.display());
+                }
+                if fitted.pages > max_pages {
+                    return Err(CvError::PageLimit {
+                        max_pages,
+                        pages: fitted.pages,
+                    }
+                    .into());
+                }
+                (&fitted.doc, fitted.theme.clone(), fitted.changes.clone())
+            }
+            None => (doc, theme, Vec::new()),
+        };
+
+        if let Some(parent) = job.output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let render_options = RenderOptions {
+            dpi: job.dpi,
+            pdf_a: job.pdf_a,
+            review: job.review.clone(),
+            print_safe: job.print_safe,
+            ..RenderOptions::default()
+        };
+        let renderer =
+            Renderer::with_options(&job.format, job.template.as_deref(), &render_options)?;
+        let report = renderer.render(doc, &theme, &job.output)?;
+        Ok((report, changes))
+    }
+
+    /// Fits `doc` onto `max_pages` pages, measuring each attempt with a PDF render
+    /// (print-safe when the output will be, as its heavier rules take space). The
+    /// measuring renders skip the layout check; the final render still runs it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `format` has no pages or a measuring render fails.
+    fn fit_document(
+        doc: &Document,
+        theme: &Theme,
+        format: &str,
+        template: Option<&Path>,
+        max_pages: usize,
+        print_safe: bool,
+    ) -> Result<fit::FitResult> {
+        if !PAGED_FORMATS.contains(&format) {
+            anyhow::bail!(
+                "A page limit needs a paged format ({}), not {format}",
+                PAGED_FORMATS.join(", ")
+            );
+        }
+
+        let options = RenderOptions {
+            print_safe,
+            layout_warnings: false,
+            ..RenderOptions::default()
+        };
+        let renderer = Renderer::with_options("pdf", template, &options)?;
+        let scratch = tempfile::tempdir()?;
+        let probe = scratch.path().join("fit.pdf");
+
+        fit::fit_to_pages(doc, theme, max_pages, |doc, theme| {
+            renderer
+                .render(doc, theme, &probe)?
+                .pages
+                .ok_or_else(|| anyhow::anyhow!("Could not count the pages of {}", probe.display()))
+        })
+    }
+
+    /// Renders a cover letter and CV with one theme and bundles them with any PDF
+    /// attachments, as a single PDF or as a zip.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a document is invalid or fails to render, an attachment is
+    /// missing or not a PDF, or the bundle cannot be written.
+    pub fn bundle(options: &BundleOptions) -> Result<BundleReport> {
+        // Check the attachments before spending time on rendering
+        let mut attachments = Vec::new();
+        for attachment in options.attachments {
+            if !attachment.exists() {
+                return Err(CvError::FileNotFound(attachment.clone()).into());
+            }
+            if attachment.extension().and_then(|e| e.to_str()) != Some("pdf") {
+                anyhow::bail!("Attachments must be PDF files: {}", attachment.display());
+            }
+            attachments.push(bundle::BundlePart {
+                title: attachment
+                    .file_stem()
+                    .map_or_else(String::new, |s| s.to_string_lossy().into_owned()),
+                pdf: attachment.clone(),
+                txt: None,
+            });
+        }
+
+        let theme = Theme::new(options.font_theme, options.color_theme)?;
+        let pdf = Renderer::new("pdf", None)?;
+        let txt = Renderer::new("txt", None)?;
+        let scratch = tempfile::tempdir()?;
+
+        let documents = options
+            .letter
+            .map(|letter| (letter, "Cover Letter"))
+            .into_iter()
+            .chain([(options.cv, "Curriculum Vitae")]);
+
+        let mut name = String::new();
+        let mut parts = Vec::new();
+        for (index, (input, title)) in documents.enumerate() {
+            let doc = Document::from_file(input)?;
+            doc.validate()?;
+            name.clone_from(&doc.metadata.name);
+            let doc_theme = theme
+                .clone()
+                .with_overrides(doc.metadata.theme_overrides.as_ref())?
+                .with_icons(doc.metadata.icons);
+
+            // A directory per document, in case the letter and CV share a file name
+            let dir = scratch.path().join(index.to_string());
+            std::fs::create_dir_all(&dir)?;
+            let stem = input
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("document");
+
+            let pdf_path = dir.join(format!("{stem}.pdf"));
+            for warning in pdf.render(&doc, &doc_theme, &pdf_path)?.warnings {
+                warn!("{}: {warning}", input.display());
+            }
+            let txt_path = if options.zip {
+                let path = dir.join(format!("{stem}.txt"));
+                txt.render(&doc, &doc_theme, &path)?;
+                Some(path)
+            } else {
+                None
+            };
+
+            parts.push(bundle::BundlePart {
+                title: title.to_string(),
+                pdf: pdf_path,
+                txt: txt_path,
+            });
+        }
+
+        parts.append(&mut attachments);
+
+        if let Some(parent) = options.output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let (pages, contents) = if options.zip {
+            (None, bundle::write_zip(&parts, options.output)?)
+        } else {
+            let title = format!("{name} - Application");
+            let pages = bundle::merge_pdfs(&parts, options.output, &title)?;
+            (
+                Some(pages),
+                parts.into_iter().map(|part| part.title).collect(),
+            )
+        };
+
+        Ok(BundleReport {
+            output: options.output.to_path_buf(),
+            pages,
+            contents,
+        })
+    }
+
     /// Creates a new CV template file.
     ///
     /// # Errors
@@ -132,41 +597,205 @@ impl CvGenerator {
         Ok(())
     }
 
-    /// Lists available font and/or color themes.
-    pub fn list_themes(fonts: bool, colors: bool) {
-        let (font_themes, color_themes) = Theme::available_themes();
+    /// Lists the selected kinds of theme, with colour swatches in truecolor terminals
+    /// and whether each font family is installed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a built-in theme fails to load.
+    pub fn list_themes(sections: ThemeSections) -> Result<()> {
+        let listing = theme_list::listing(sections, TypstFonts::detect().as_ref())?;
+        println!(
+            "{}",
+            theme_list::listing_text(&listing, theme_list::supports_truecolor())
+        );
+        Ok(())
+    }
 
-        if fonts {
-            println!("{}", "Font Themes:".bold());
-            for theme in font_themes {
-                let description =
-                    get_theme_info(theme).map_or("Unknown theme", |info| info.font_description);
-                println!("  • {} - {}", theme.cyan(), description);
-            }
-            if colors {
-                println!();
-            }
+    /// The selected kinds of theme as pretty-printed JSON, for theme pickers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a built-in theme fails to load.
+    pub fn themes_json(sections: ThemeSections) -> Result<String> {
+        let listing = theme_list::listing(sections, TypstFonts::detect().as_ref())?;
+        Ok(serde_json::to_string_pretty(&listing)?)
+    }
+
+    /// Renders a first-page PNG thumbnail of the sample CV in each theme.
+    ///
+    /// Returns the paths of the thumbnails written to `output_dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a theme cannot be loaded or rendering fails.
+    pub fn preview_themes(output_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut thumbnail = Self::thumbnail_renderer(output_dir)?;
+        let (theme_names, _) = Theme::available_themes();
+
+        theme_names
+            .into_iter()
+            .map(|name| thumbnail(&Theme::new(name, name)?, &format!("{name}.png")))
+            .collect()
+    }
+
+    /// Renders the sample CV in every pairing of font and colour theme, including
+    /// the theme files in the configured `custom_themes_dir`, and writes an HTML
+    /// index of the thumbnails with the flags selecting each pairing.
+    ///
+    /// Returns the path of the index page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a theme cannot be loaded or rendering fails.
+    pub fn theme_gallery(&self, output_dir: &Path) -> Result<PathBuf> {
+        let custom_dir = self.config.custom_themes_path();
+        let entries = gallery::gallery_entries(&gallery::gallery_themes(custom_dir.as_deref())?);
+
+        let mut thumbnail = Self::thumbnail_renderer(output_dir)?;
+        for entry in &entries {
+            let theme = Theme::new(&entry.font.name, &entry.color.name)?;
+            thumbnail(&theme, &entry.thumbnail)?;
         }
 
-        if colors {
-            println!("{}", "Color Themes:".bold());
-            for theme in color_themes {
-                let description =
-                    get_theme_info(theme).map_or("Unknown theme", |info| info.color_description);
-                println!("  • {} - {}", theme.cyan(), description);
+        let index = output_dir.join(gallery::INDEX_FILE);
+        std::fs::write(&index, gallery::index_html(&entries))?;
+        Ok(index)
+    }
+
+    /// Returns a function rendering the first page of the sample CV in a theme to a
+    /// PNG thumbnail in `output_dir`, under the given file name.
+    fn thumbnail_renderer(
+        output_dir: &Path,
+    ) -> Result<impl FnMut(&Theme, &str) -> Result<PathBuf> + '_> {
+        let sample = include_str!("../templates/cv_template.md");
+        let doc = Document::from_string(sample, Path::new("cv_template.md"))?;
+        let options = RenderOptions {
+            dpi: THUMBNAIL_DPI,
+            ..RenderOptions::default()
+        };
+        let renderer = Renderer::with_options("png", None, &options)?;
+
+        std::fs::create_dir_all(output_dir)?;
+        let scratch = tempfile::tempdir()?;
+
+        Ok(move |theme: &Theme, file_name: &str| {
+            let pages = scratch.path().join(file_name);
+            renderer.render(&doc, theme, &pages)?;
+
+            let thumbnail = output_dir.join(file_name);
+            std::fs::copy(primary_output("png", &pages), &thumbnail)?;
+            Ok(thumbnail)
+        })
+    }
+
+    /// Prints the WCAG contrast of each colour pair in a colour theme or theme file,
+    /// or in every built-in colour theme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the theme cannot be loaded.
+    pub fn check_contrast(theme: Option<&str>) -> Result<()> {
+        let (_, built_in) = Theme::available_themes();
+        let names = theme.map_or(built_in, |theme| vec![theme]);
+
+        println!("{}", "Color Contrast (WCAG AA):".bold());
+        for name in names {
+            let colors = Theme::new(crate::constants::DEFAULT_THEME, name)?.color;
+            let checks = contrast::check(&colors);
+            let failed = checks.iter().filter(|pair| !pair.passes()).count();
+            let summary = if failed == 0 {
+                "all pairs pass".green()
+            } else {
+                format!("{failed} of {} pairs fail", checks.len()).yellow()
+            };
+            println!("  • {} - {summary}", name.cyan());
+            for pair in &checks {
+                let mark = if pair.passes() {
+                    "✓".green()
+                } else {
+                    "✗".red()
+                };
+                println!("    {mark} {pair}");
             }
         }
+        Ok(())
+    }
+
+    /// Writes a colour theme derived from a brand colour to a custom theme file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the brand colour is not a hex colour or the file cannot be
+    /// written.
+    pub fn generate_theme(name: &str, brand: &str, output: &Path) -> Result<()> {
+        std::fs::write(output, generate::theme_file(name, brand)?)?;
+        Ok(())
     }
 
     /// Validates the structure and content of a markdown document.
     ///
+    /// Returns warnings that do not make the document invalid: colour pairs whose
+    /// contrast its `theme_overrides` bring below the WCAG AA minimum.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the document cannot be parsed or is invalid.
-    pub fn check(input: &Path) -> Result<()> {
+    /// Returns an error if the document cannot be parsed or is invalid, or its
+    /// themes cannot be loaded.
+    pub fn check(input: &Path) -> Result<Vec<String>> {
         let doc = Document::from_file(input)?;
         doc.validate()?;
-        Ok(())
+
+        let meta = &doc.metadata;
+        let Some(overrides) = &meta.theme_overrides else {
+            return Ok(Vec::new());
+        };
+        let base = Theme::new(&meta.font_theme, &meta.color_theme)?;
+        let themed = base.clone().with_overrides(Some(overrides))?;
+        Ok(contrast::regressions(&base.color, &themed.color)
+            .iter()
+            .map(|pair| format!("Low contrast from theme_overrides: {pair}"))
+            .collect())
+    }
+
+    /// Validates every document selected from a manifest.
+    ///
+    /// Each document is parsed and validated, and the themes and templates its
+    /// build would use are checked. Returns each input with its first problem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest's build settings cannot be resolved.
+    pub fn check_manifest(
+        manifest: &Manifest,
+        tags: &[String],
+    ) -> Result<Vec<(PathBuf, Option<String>)>> {
+        let jobs = manifest.jobs(tags)?;
+
+        let results = manifest
+            .inputs(tags)
+            .into_iter()
+            .map(|input| {
+                let problem = Self::check(&input).err().or_else(|| {
+                    jobs.iter()
+                        .filter(|job| job.input == input)
+                        .find_map(|job| {
+                            if let Err(e) = Theme::new(&job.font_theme, &job.color_theme)
+                                .and_then(|theme| theme.with_layout(job.layout.as_deref()))
+                            {
+                                return Some(e);
+                            }
+                            job.template
+                                .as_ref()
+                                .filter(|t| !t.exists())
+                                .map(|t| anyhow::anyhow!("Template not found: {}", t.display()))
+                        })
+                });
+                (input, problem.map(|e| e.to_string()))
+            })
+            .collect();
+
+        Ok(results)
     }
 
     /// Starts a preview server for the document (not yet implemented).
@@ -184,8 +813,7 @@ impl CvGenerator {
             duration
                 .split_whitespace()
                 .filter_map(|word| word.parse::<u32>().ok())
-                .filter(|&year| (1900..=2100).contains(&year))
-                .next_back()
+                .rfind(|&year| (1900..=2100).contains(&year))
                 .unwrap_or(0)
         }
     }
@@ -242,7 +870,14 @@ impl CvGenerator {
     }
 
     /// Generates the frontmatter for a tailored CV.
-    fn generate_frontmatter(original_doc: &Document, options: &TailorOptions) -> Result<String> {
+    ///
+    /// The ATS keywords from the AI are stored as `keywords` so they end up in the
+    /// PDF metadata.
+    fn generate_frontmatter(
+        original_doc: &Document,
+        keywords: &[String],
+        options: &TailorOptions,
+    ) -> Result<String> {
         let mut frontmatter = String::from("---\n");
 
         writeln!(&mut frontmatter, "name: {}", original_doc.metadata.name)?;
@@ -263,6 +898,31 @@ impl CvGenerator {
         if let Some(website) = &original_doc.metadata.website {
             writeln!(&mut frontmatter, "website: {website}")?;
         }
+        if !keywords.is_empty() {
+            // A JSON array is valid YAML and quotes any awkward characters
+            writeln!(
+                &mut frontmatter,
+                "keywords: {}",
+                serde_json::to_string(keywords)?
+            )?;
+        }
+        if let Some(lang) = &original_doc.metadata.lang {
+            writeln!(&mut frontmatter, "lang: {lang}")?;
+        }
+        if let Some(header) = &original_doc.metadata.header {
+            writeln!(
+                &mut frontmatter,
+                "header: {}",
+                serde_json::to_string(header)?
+            )?;
+        }
+        if let Some(footer) = &original_doc.metadata.footer {
+            writeln!(
+                &mut frontmatter,
+                "footer: {}",
+                serde_json::to_string(footer)?
+            )?;
+        }
 
         writeln!(&mut frontmatter, "\n# AI-Tailored CV")?;
         writeln!(
@@ -384,15 +1044,13 @@ impl CvGenerator {
             println!("{} Connecting to AI service...", "→".blue());
         }
 
-        // Create AI client from environment
-        let mut ai_client = AIClient::from_env()
-            .map_err(|e| anyhow::anyhow!("Failed to create AI client: {e}. Make sure AI_ENDPOINT, AI_API_KEY, and AI_MODEL are set."))?;
+        // Create AI client from the selected profile, or the environment
+        let mut ai_client = AIClient::from_config(&self.config.ai, options.profile)
+            .map_err(|e| anyhow::anyhow!("Failed to create AI client: {e}. Choose a profile with --profile, or set AI_ENDPOINT, AI_API_KEY, and AI_MODEL."))?;
 
         if !options.quiet {
-            let endpoint = std::env::var("AI_ENDPOINT").unwrap_or_else(|_| "not set".to_string());
-            let model = &ai_client.model;
-            println!("  Provider: {}", endpoint.dimmed());
-            println!("  Model: {}", model.dimmed());
+            println!("  Provider: {}", ai_client.provider_name().dimmed());
+            println!("  Model: {}", ai_client.model().dimmed());
         }
 
         // Create a progress spinner for AI processing
@@ -428,7 +1086,8 @@ impl CvGenerator {
         }
 
         // Generate the tailored markdown
-        let frontmatter = Self::generate_frontmatter(&original_doc, options)?;
+        let frontmatter =
+            Self::generate_frontmatter(&original_doc, &tailored_cv.keywords, options)?;
         let content = Self::generate_tailored_content(&tailored_cv, &original_doc)?;
         let tailored_markdown = frontmatter + &content;
 
@@ -462,15 +1121,21 @@ impl CvGenerator {
             }
 
             // Create the output path for the final format
-            let final_output_path = output_path.with_extension(options.format);
+            let final_output_path = output_path.with_extension(output_extension(options.format));
 
             let build_options = BuildOptions {
                 input: &output_path,
                 font_theme: options.font_theme,
                 color_theme: options.color_theme,
+                layout: None,
                 output: Some(&final_output_path),
                 format: options.format,
                 template: None,
+                render: RenderOptions::default(),
+                max_pages: None,
+                fail_on_warnings: false,
+                cache: CacheMode::default(),
+                anonymize: None,
                 verbose: options.verbose,
                 quiet: options.quiet,
             };
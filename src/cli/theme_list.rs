@@ -0,0 +1,346 @@
+//! Theme listing (`cv themes`)
+//!
+//! Collects the built-in font, colour and layout themes with their descriptions,
+//! each colour theme's palette and whether each font family is available to Typst.
+//! The listing prints as text, with truecolor swatches in terminals that support
+//! them, or as JSON for theme pickers (`--json`).
+
+use crate::constants::AVAILABLE_LAYOUTS;
+use crate::render::pdf::typst_fonts;
+use crate::themes::color::ColorTheme;
+use crate::themes::contrast::rgb8;
+use crate::themes::font::FontTheme;
+use crate::themes::get_theme_info;
+use crate::themes::layout::layout_description;
+use crate::themes::Theme;
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+/// Which kinds of theme to list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeSections {
+    pub fonts: bool,
+    pub colors: bool,
+    pub layouts: bool,
+}
+
+impl ThemeSections {
+    /// Every kind of theme
+    pub const ALL: Self = Self {
+        fonts: true,
+        colors: true,
+        layouts: true,
+    };
+
+    /// These sections, or all of them when none is selected.
+    #[must_use]
+    pub fn or_all(self) -> Self {
+        if self.fonts || self.colors || self.layouts {
+            self
+        } else {
+            Self::ALL
+        }
+    }
+}
+
+/// Whether Typst can use a font family
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FontStatus {
+    /// Embedded in Typst or in the project `./fonts` directory
+    Bundled,
+    /// Installed on the system
+    Installed,
+    /// Not available; Typst substitutes another font
+    Missing,
+    /// Typst is not installed, so fonts can't be checked
+    Unknown,
+}
+
+impl FontStatus {
+    fn mark(self) -> colored::ColoredString {
+        match self {
+            Self::Bundled => "✓ bundled".green(),
+            Self::Installed => "✓ installed".green(),
+            Self::Missing => "✗ missing".red(),
+            Self::Unknown => "? unknown (Typst not found)".yellow(),
+        }
+    }
+}
+
+/// The font families Typst reports, split into bundled and all
+#[derive(Debug, Clone, Default)]
+pub struct TypstFonts {
+    pub bundled: Vec<String>,
+    pub all: Vec<String>,
+}
+
+impl TypstFonts {
+    /// Asks Typst for its fonts, or returns `None` when Typst is not installed.
+    #[must_use]
+    pub fn detect() -> Option<Self> {
+        Some(Self {
+            bundled: typst_fonts(true)?,
+            all: typst_fonts(false)?,
+        })
+    }
+
+    /// Status of `family`, compared case-insensitively as Typst does.
+    #[must_use]
+    pub fn status(&self, family: &str) -> FontStatus {
+        let listed = |families: &[String]| families.iter().any(|f| f.eq_ignore_ascii_case(family));
+        if listed(&self.bundled) {
+            FontStatus::Bundled
+        } else if listed(&self.all) {
+            FontStatus::Installed
+        } else {
+            FontStatus::Missing
+        }
+    }
+}
+
+/// A font family and whether it is available
+#[derive(Debug, Clone, Serialize)]
+pub struct FontFamily {
+    pub family: String,
+    pub status: FontStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FontListing {
+    pub name: String,
+    pub description: String,
+    pub header: FontFamily,
+    pub body: FontFamily,
+}
+
+/// One colour of a palette, such as `primary`
+#[derive(Debug, Clone, Serialize)]
+pub struct Swatch {
+    pub role: String,
+    pub hex: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColorListing {
+    pub name: String,
+    pub description: String,
+    pub palette: Vec<Swatch>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LayoutListing {
+    pub name: String,
+    pub description: String,
+}
+
+/// The selected kinds of theme; unselected kinds are left out of the JSON
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ThemeListing {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fonts: Option<Vec<FontListing>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub colors: Option<Vec<ColorListing>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layouts: Option<Vec<LayoutListing>>,
+}
+
+/// The colours of a theme in display order, with the heading colours it sets.
+#[must_use]
+pub fn palette(colors: &ColorTheme) -> Vec<Swatch> {
+    let base = [
+        ("primary", Some(&colors.primary)),
+        ("secondary", Some(&colors.secondary)),
+        ("accent", Some(&colors.accent)),
+        ("text", Some(&colors.text)),
+        ("muted", Some(&colors.muted)),
+        ("background", Some(&colors.background)),
+        ("surface", Some(&colors.surface)),
+        ("border", Some(&colors.border)),
+        ("h1", colors.h1_color.as_ref()),
+        ("h2", colors.h2_color.as_ref()),
+        ("h3", colors.h3_color.as_ref()),
+    ];
+    base.into_iter()
+        .filter_map(|(role, hex)| {
+            hex.map(|hex| Swatch {
+                role: role.to_string(),
+                hex: hex.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Lists the built-in themes of the selected kinds. Font families get
+/// [`FontStatus::Unknown`] when `fonts` is `None`.
+///
+/// # Errors
+///
+/// Returns an error if a built-in theme fails to load.
+pub fn listing(sections: ThemeSections, fonts: Option<&TypstFonts>) -> Result<ThemeListing> {
+    let (font_themes, color_themes) = Theme::available_themes();
+    let status = |family: &str| FontFamily {
+        family: family.to_string(),
+        status: fonts.map_or(FontStatus::Unknown, |fonts| fonts.status(family)),
+    };
+
+    let mut listing = ThemeListing::default();
+    if sections.fonts {
+        listing.fonts = Some(
+            font_themes
+                .into_iter()
+                .map(|name| {
+                    let theme = FontTheme::load(name)?;
+                    Ok(FontListing {
+                        name: name.to_string(),
+                        description: get_theme_info(name)
+                            .map_or("Unknown theme", |info| info.font_description)
+                            .to_string(),
+                        header: status(&theme.header.family),
+                        body: status(&theme.body.family),
+                    })
+                })
+                .collect::<Result<_>>()?,
+        );
+    }
+    if sections.colors {
+        listing.colors = Some(
+            color_themes
+                .into_iter()
+                .map(|name| {
+                    Ok(ColorListing {
+                        name: name.to_string(),
+                        description: get_theme_info(name)
+                            .map_or("Unknown theme", |info| info.color_description)
+                            .to_string(),
+                        palette: palette(&ColorTheme::load(name)?),
+                    })
+                })
+                .collect::<Result<_>>()?,
+        );
+    }
+    if sections.layouts {
+        listing.layouts = Some(
+            AVAILABLE_LAYOUTS
+                .iter()
+                .map(|name| LayoutListing {
+                    name: (*name).to_string(),
+                    description: layout_description(name).to_string(),
+                })
+                .collect(),
+        );
+    }
+    Ok(listing)
+}
+
+/// Whether the terminal shows 24-bit colour: colour output is on and `COLORTERM`
+/// is `truecolor` or `24bit`.
+#[must_use]
+pub fn supports_truecolor() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+        && std::env::var("COLORTERM")
+            .is_ok_and(|value| matches!(value.as_str(), "truecolor" | "24bit"))
+}
+
+/// A palette colour as `role #RRGGBB`, after a block of the colour when `truecolor`.
+#[must_use]
+pub fn swatch(swatch: &Swatch, truecolor: bool) -> String {
+    match rgb8(&swatch.hex).filter(|_| truecolor) {
+        Some([r, g, b]) => format!(
+            "{} {} {}",
+            "  ".on_truecolor(r, g, b),
+            swatch.role,
+            swatch.hex
+        ),
+        None => format!("{} {}", swatch.role, swatch.hex),
+    }
+}
+
+/// Text form of a listing, one section per kind of theme.
+#[must_use]
+pub fn listing_text(listing: &ThemeListing, truecolor: bool) -> String {
+    let mut sections = Vec::new();
+
+    if let Some(fonts) = &listing.fonts {
+        let mut lines = vec!["Font Themes:".bold().to_string()];
+        for theme in fonts {
+            lines.push(format!("  • {} - {}", theme.name.cyan(), theme.description));
+            for (role, font) in [("header", &theme.header), ("body", &theme.body)] {
+                lines.push(format!(
+                    "      {role}: {} {}",
+                    font.family,
+                    font.status.mark()
+                ));
+            }
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    if let Some(colors) = &listing.colors {
+        let mut lines = vec!["Color Themes:".bold().to_string()];
+        for theme in colors {
+            lines.push(format!("  • {} - {}", theme.name.cyan(), theme.description));
+            // Four swatches to a line keeps the palette within 80 columns
+            for row in theme.palette.chunks(4) {
+                let row: Vec<String> = row.iter().map(|s| swatch(s, truecolor)).collect();
+                lines.push(format!("      {}", row.join("  ")));
+            }
+        }
+        sections.push(lines.join("\n"));
+    }
+
+    if let Some(layouts) = &listing.layouts {
+        let mut lines = vec!["Layout Themes:".bold().to_string()];
+        for layout in layouts {
+            lines.push(format!(
+                "  • {} - {}",
+                layout.name.cyan(),
+                layout.description
+            ));
+        }
+        lines.push("  (default: the layout of the color theme)".to_string());
+        sections.push(lines.join("\n"));
+    }
+
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_status() {
+        let fonts = TypstFonts {
+            bundled: vec!["Inter".to_string()],
+            all: vec!["Inter".to_string(), "Georgia".to_string()],
+        };
+        assert_eq!(fonts.status("inter"), FontStatus::Bundled);
+        assert_eq!(fonts.status("Georgia"), FontStatus::Installed);
+        assert_eq!(fonts.status("Roboto"), FontStatus::Missing);
+    }
+
+    #[test]
+    fn test_palette_skips_unset_heading_colours() {
+        let colors = ColorTheme::load("classic").expect("Theme loads");
+        let roles: Vec<String> = palette(&colors).into_iter().map(|s| s.role).collect();
+        assert_eq!(roles[0], "primary");
+        assert_eq!(
+            roles.len(),
+            8 + [&colors.h1_color, &colors.h2_color, &colors.h3_color]
+                .iter()
+                .filter(|c| c.is_some())
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_swatch_without_truecolor_is_plain() {
+        let primary = Swatch {
+            role: "primary".to_string(),
+            hex: "#2C3E50".to_string(),
+        };
+        assert_eq!(swatch(&primary, false), "primary #2C3E50");
+    }
+}
@@ -17,6 +17,10 @@ pub struct DocumentMetadata {
     pub font_theme: String,
     #[serde(default = "default_color_theme")]
     pub color_theme: String,
+    /// Individual theme fields patched over the chosen themes
+    pub theme_overrides: Option<crate::themes::overrides::ThemeOverrides>,
+    /// Icon set for the contact details, replacing the theme's
+    pub icons: Option<crate::themes::layout::IconSet>,
 
     // Layout Options
     #[serde(default)]
@@ -27,6 +31,15 @@ pub struct DocumentMetadata {
     pub date: Option<String>,
     pub subject: Option<String>,
 
+    // PDF metadata
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    pub lang: Option<String>,
+
+    // Running header and footer
+    pub header: Option<PageText>,
+    pub footer: Option<PageText>,
+
     // Custom fields
     #[serde(flatten)]
     pub custom: HashMap<String, serde_yaml::Value>,
@@ -40,6 +53,22 @@ pub struct RecipientInfo {
     pub address: Option<String>,
 }
 
+/// Text repeated at the top or bottom of every page.
+///
+/// Each slot may use the `{name}`, `{page}`, `{pages}` and `{date}` placeholders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageText {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<String>,
+    /// Whether the first page shows it too
+    #[serde(default = "default_first_page")]
+    pub first_page: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct LayoutOptions {
@@ -87,6 +116,13 @@ fn default_color_theme() -> String {
     DEFAULT_THEME.to_string()
 }
 
+const fn default_first_page() -> bool {
+    true
+}
+
+/// Environment variable overriding the path of the global config file
+pub const CONFIG_ENV: &str = "CV_CHECK_CONFIG";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalConfig {
     pub default_font_theme: Option<String>,
@@ -95,6 +131,58 @@ pub struct GlobalConfig {
     pub custom_themes_dir: Option<String>,
     pub output_dir: Option<String>,
     pub auto_open: Option<bool>,
+    /// AI providers for `cv tailor`
+    #[serde(default)]
+    pub ai: AiConfig,
+}
+
+/// Named AI profiles, one of which `cv tailor` uses
+///
+/// ```yaml
+/// ai:
+///   default_profile: local
+///   profiles:
+///     local:
+///       provider: openai
+///       endpoint: http://localhost:11434/v1
+///       model: llama3.1
+///     offline:
+///       provider: mock
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AiConfig {
+    /// Profile used when neither `--profile` nor `AI_PROFILE` picks one
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: std::collections::BTreeMap<String, AiProfile>,
+}
+
+/// Kind of AI backend a profile uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// An OpenAI-compatible chat completion API, remote or local
+    OpenAi,
+    /// Offline replies from a fixture file
+    Mock,
+}
+
+/// One AI profile: a provider and its settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AiProfile {
+    pub provider: ProviderKind,
+    /// API base URL (`openai` only)
+    pub endpoint: Option<String>,
+    /// Model name (`openai` only)
+    pub model: Option<String>,
+    /// Environment variable holding the API key (`openai` only); local servers
+    /// usually need none
+    pub api_key_env: Option<String>,
+    /// `TailoredCV` JSON to reply with (`mock` only; default: a built-in fixture),
+    /// relative to the config file
+    pub fixture: Option<std::path::PathBuf>,
 }
 
 impl Default for GlobalConfig {
@@ -106,6 +194,7 @@ impl Default for GlobalConfig {
             custom_themes_dir: None,
             output_dir: Some("./output".to_string()),
             auto_open: Some(true),
+            ai: AiConfig::default(),
         }
     }
 }
@@ -117,14 +206,43 @@ impl GlobalConfig {
     ///
     /// Returns an error if the config file exists but cannot be read or parsed.
     pub fn load() -> anyhow::Result<Self> {
-        if let Some(config_dir) = dirs::config_dir() {
-            let config_path = config_dir.join("cv_gen").join("config.yaml");
+        if let Some(config_path) = Self::path() {
             if config_path.exists() {
-                let content = std::fs::read_to_string(config_path)?;
-                let config: GlobalConfig = serde_yaml::from_str(&content)?;
+                let content = std::fs::read_to_string(&config_path)?;
+                let mut config: GlobalConfig = serde_yaml::from_str(&content)?;
+                // Relative fixture paths are relative to the config file, not the CWD
+                if let Some(config_dir) = config_path.parent() {
+                    for profile in config.ai.profiles.values_mut() {
+                        if let Some(fixture) = profile.fixture.as_mut() {
+                            if fixture.is_relative() {
+                                *fixture = config_dir.join(&*fixture);
+                            }
+                        }
+                    }
+                }
                 return Ok(config);
             }
         }
         Ok(Self::default())
     }
+
+    /// Path of the global config file: `CV_CHECK_CONFIG` if set, else
+    /// `cv_gen/config.yaml` in the user's config directory.
+    #[must_use]
+    pub fn path() -> Option<std::path::PathBuf> {
+        std::env::var_os(CONFIG_ENV)
+            .map(std::path::PathBuf::from)
+            .or_else(|| dirs::config_dir().map(|dir| dir.join("cv_gen").join("config.yaml")))
+    }
+
+    /// Directory of custom theme files, with a leading `~/` expanded to the home
+    /// directory.
+    #[must_use]
+    pub fn custom_themes_path(&self) -> Option<std::path::PathBuf> {
+        let dir = self.custom_themes_dir.as_deref()?;
+        match (dir.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => Some(home.join(rest)),
+            _ => Some(dir.into()),
+        }
+    }
 }
@@ -5,6 +5,9 @@ pub mod layout;
 /// Available theme names
 pub const AVAILABLE_THEMES: &[&str] = &["classic", "modern", "sharp"];
 
+/// Available layout theme names; the first three match the colour themes
+pub const AVAILABLE_LAYOUTS: &[&str] = &["classic", "modern", "sharp", "compact", "banner"];
+
 /// Default theme name
 pub const DEFAULT_THEME: &str = "modern";
 
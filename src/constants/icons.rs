@@ -1,4 +1,6 @@
-//! FontAwesome icon constants used throughout the application
+//! Icon constants for the contact details, one module per icon set
+//!
+//! The top-level constants are the `FontAwesome` 4 codepoints, the default set.
 
 /// `FontAwesome` font name
 pub const FONT_NAME: &str = "FontAwesome";
@@ -17,3 +19,32 @@ pub const GITHUB: &str = "\u{f09b}";
 
 /// `LinkedIn` icon
 pub const LINKEDIN: &str = "\u{f0e1}";
+
+/// `FontAwesome` 6, which splits the icons between a solid and a brands font
+pub mod fa6 {
+    /// Font holding the solid icons (phone, email, website)
+    pub const SOLID_FONT: &str = "Font Awesome 6 Free";
+
+    /// The free solid icons only exist in this weight
+    pub const SOLID_WEIGHT: u16 = 900;
+
+    /// Font holding the brand icons (GitHub, `LinkedIn`)
+    pub const BRANDS_FONT: &str = "Font Awesome 6 Brands";
+
+    pub const PHONE: &str = "\u{f095}";
+    pub const EMAIL: &str = "\u{f0e0}";
+    pub const WEBSITE: &str = "\u{f015}";
+    pub const GITHUB: &str = "\u{f09b}";
+    pub const LINKEDIN: &str = "\u{f08c}";
+}
+
+/// Bundled outline icons, the contents of a 24x24 `<svg>` drawn with a stroke
+pub mod svg {
+    pub const PHONE: &str =
+        r#"<rect x="7" y="2" width="10" height="20" rx="2"/><path d="M11 18h2"/>"#;
+    pub const EMAIL: &str =
+        r#"<rect x="2" y="5" width="20" height="14" rx="2"/><path d="M2 7l10 7 10-7"/>"#;
+    pub const WEBSITE: &str = r#"<circle cx="12" cy="12" r="10"/><path d="M2 12h20M12 2a15 15 0 0 1 0 20M12 2a15 15 0 0 0 0 20"/>"#;
+    pub const GITHUB: &str = r#"<path d="M8 7l-5 5 5 5M16 7l5 5-5 5M14 4l-4 16"/>"#;
+    pub const LINKEDIN: &str = r#"<rect x="2" y="2" width="20" height="20" rx="3"/><path d="M7 10v7M7 7v.01M11 17v-7M11 13a3 3 0 0 1 6 0v4"/>"#;
+}
@@ -13,6 +13,30 @@ pub mod margins {
 
     /// Right margin
     pub const RIGHT: &str = "2cm";
+
+    /// Right margin of review builds with margin notes
+    pub const NOTES_RIGHT: &str = "5cm";
+}
+
+/// Review build constants
+pub mod review {
+    /// Watermark text size
+    pub const WATERMARK_SIZE: &str = "96pt";
+
+    /// How far the watermark colour is faded
+    pub const WATERMARK_TRANSPARENCY: &str = "80%";
+
+    /// Size of the footer stamp and margin notes
+    pub const NOTE_SIZE: &str = "8pt";
+
+    /// Width of a margin note
+    pub const NOTE_WIDTH: &str = "4.2cm";
+
+    /// Gap between the body text and a margin note
+    pub const NOTE_GAP: &str = "0.4cm";
+
+    /// Distance of the footer stamp from the bottom edge
+    pub const STAMP_OFFSET: &str = "0.5cm";
 }
 
 /// Vertical spacing constants
@@ -36,6 +60,18 @@ pub mod spacing {
     pub const LARGE: &str = "1.5em";
 }
 
+/// Layout theme constants
+pub mod layout_styles {
+    /// Length of a `line` section separator
+    pub const SEPARATOR_FULL: &str = "100%";
+
+    /// Length of a `short` section separator
+    pub const SEPARATOR_SHORT: &str = "3em";
+
+    /// Padding inside a `banner` header
+    pub const BANNER_INSET: &str = "1em";
+}
+
 /// Font size constants
 pub mod font_sizes {
 
@@ -45,6 +81,9 @@ pub mod font_sizes {
     /// Normal font size (11pt)
     pub const NORMAL: &str = "11pt";
 
+    /// Smallest body font size `--max-pages` may shrink to (9pt)
+    pub const MIN_NORMAL: &str = "9pt";
+
     /// Medium font size (12pt)
     pub const MEDIUM: &str = "12pt";
 
@@ -54,9 +93,6 @@ pub mod font_sizes {
     /// Section font size (16pt)
     pub const SECTION: &str = "16pt";
 
-    /// Title font size (36pt)
-    pub const TITLE: &str = "36pt";
-
     /// Name font size (28pt)
     pub const NAME: &str = "28pt";
 }
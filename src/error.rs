@@ -9,15 +9,32 @@ pub enum CvError {
     #[error("Unknown theme '{theme}'. Available themes: {available}")]
     UnknownTheme { theme: String, available: String },
 
+    #[error("Invalid theme {theme}: {reason}")]
+    InvalidTheme { theme: String, reason: String },
+
     #[error("Invalid markdown structure: {reason}")]
     InvalidMarkdown { reason: String },
 
     #[error("File not found: {0}")]
     FileNotFound(PathBuf),
 
-    #[error("Invalid output format: {format}. Supported formats: pdf, docx, html")]
+    #[error(
+        "Invalid output format: {format}. Supported formats: pdf, docx, html, typst, png, svg, txt"
+    )]
     InvalidFormat { format: String },
 
+    #[error("Could not fit the document on {max_pages} page(s); it still needs {pages}")]
+    PageLimit { max_pages: usize, pages: usize },
+
+    #[error("The build reported {count} warning(s) and --fail-on-warnings is set")]
+    RenderWarnings { count: usize },
+
+    #[error("Invalid manifest {path}: {reason}")]
+    InvalidManifest { path: PathBuf, reason: String },
+
+    #[error("Invalid anonymization map {path}: {reason}")]
+    InvalidAnonymizeMap { path: PathBuf, reason: String },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -1,8 +1,10 @@
 pub mod ai;
+pub mod build;
 pub mod cli;
 pub mod config;
 pub mod constants;
 pub mod error;
+pub mod manifest;
 pub mod parser;
 pub mod render;
 pub mod themes;
@@ -1,20 +1,33 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use colored::Colorize;
 use log::{error, info};
-use std::fmt::Display;
-use std::path::PathBuf;
+use std::fmt::{Display, Write};
+use std::path::{Path, PathBuf};
 
 mod ai;
+mod build;
 mod cli;
 mod config;
 mod constants;
 mod error;
+mod manifest;
 mod parser;
 mod render;
 mod themes;
 
-use crate::cli::{BuildOptions, CvGenerator, TailorOptions};
+use crate::build::{parse_formats, Anonymizer};
+use crate::cli::batch::expand_inputs;
+use crate::cli::bundle::default_output as default_bundle_output;
+use crate::cli::display::format_build_summary;
+use crate::cli::theme_list::ThemeSections;
+use crate::cli::{
+    BatchOptions, BuildOptions, BuildOutcome, BundleOptions, CacheMode, CvGenerator, TailorOptions,
+};
+use crate::error::CvError;
+use crate::manifest::{Manifest, MANIFEST_FILE};
+use crate::render::review::Review;
+use crate::render::RenderOptions;
 
 /// Modern CV and cover letter generator with themeable output
 #[derive(Parser)]
@@ -29,8 +42,21 @@ struct Cli {
 enum Commands {
     /// Generate PDF/DOCX from markdown
     Build {
-        /// Input markdown file
-        input: PathBuf,
+        /// Input markdown files or glob patterns
+        #[arg(required_unless_present = "all")]
+        input: Vec<PathBuf>,
+
+        /// Build every document declared in the project manifest (cv.toml)
+        #[arg(long, conflicts_with_all = ["input", "output", "font_theme", "color_theme", "layout", "format", "template", "dpi", "pdf_a", "print_safe", "max_pages", "anonymize", "anonymize_map", "draft", "notes"])]
+        all: bool,
+
+        /// Manifest to use with --all (default: nearest cv.toml)
+        #[arg(long, requires = "all")]
+        manifest: Option<PathBuf>,
+
+        /// Only build manifest documents with this tag (repeatable)
+        #[arg(long, requires = "all")]
+        tag: Vec<String>,
 
         /// Font theme (classic, modern, sharp)
         #[arg(short, long, default_value = crate::constants::DEFAULT_THEME)]
@@ -40,11 +66,15 @@ enum Commands {
         #[arg(short, long, default_value = crate::constants::DEFAULT_THEME)]
         color_theme: String,
 
-        /// Output file path
+        /// Layout theme (classic, modern, sharp, compact, banner; default: the color theme's)
+        #[arg(short = 'L', long)]
+        layout: Option<String>,
+
+        /// Output file path (a directory when building several outputs)
         #[arg(short, long)]
         output: Option<PathBuf>,
 
-        /// Output format (pdf, docx, html)
+        /// Output formats, comma separated (pdf, docx, html, typst, png, svg, txt)
         #[arg(short = 'F', long, default_value = "pdf")]
         format: String,
 
@@ -52,6 +82,46 @@ enum Commands {
         #[arg(short, long)]
         template: Option<PathBuf>,
 
+        /// Image resolution for PNG output; use {page} in the output path to name pages
+        #[arg(long, default_value_t = crate::render::DEFAULT_DPI)]
+        dpi: u32,
+
+        /// Produce archival PDF/A-2b output (PDF format only)
+        #[arg(long)]
+        pdf_a: bool,
+
+        /// Print-friendly build: greyscale, high-contrast colours of the colour theme
+        #[arg(long)]
+        print_safe: bool,
+
+        /// Review build: stamp TEXT (default DRAFT) across every page and the build in the footer
+        #[arg(long, value_name = "TEXT", num_args = 0..=1, default_missing_value = crate::render::review::DEFAULT_WATERMARK)]
+        draft: Option<String>,
+
+        /// Show <!-- note: ... --> comments as margin notes in a --draft build
+        #[arg(long, requires = "draft")]
+        notes: bool,
+
+        /// Tighten spacing and drop the oldest content until the CV fits on N pages
+        #[arg(long, value_name = "N")]
+        max_pages: Option<usize>,
+
+        /// Exit with an error if rendering reports any warnings (for CI)
+        #[arg(long)]
+        fail_on_warnings: bool,
+
+        /// Rebuild outputs even when nothing they depend on has changed
+        #[arg(long)]
+        force: bool,
+
+        /// Remove the name, contact details, location, social links and photo for blind hiring
+        #[arg(long)]
+        anonymize: bool,
+
+        /// YAML file with a replacement name and employer descriptors for --anonymize
+        #[arg(long, value_name = "FILE", requires = "anonymize")]
+        anonymize_map: Option<PathBuf>,
+
         /// Verbose output
         #[arg(short, long)]
         verbose: bool,
@@ -73,22 +143,30 @@ enum Commands {
     },
 
     /// List/preview themes
-    Themes {
-        /// Show font themes
-        #[arg(long)]
-        fonts: bool,
-
-        /// Show color themes
-        #[arg(long)]
-        colors: bool,
-    },
+    Themes(ThemesArgs),
 
     /// Validate markdown structure
     Check {
         /// Input markdown file
-        input: PathBuf,
+        #[arg(required_unless_present = "all")]
+        input: Option<PathBuf>,
+
+        /// Check every document declared in the project manifest (cv.toml)
+        #[arg(long, conflicts_with = "input")]
+        all: bool,
+
+        /// Manifest to use with --all (default: nearest cv.toml)
+        #[arg(long, requires = "all")]
+        manifest: Option<PathBuf>,
+
+        /// Only check manifest documents with this tag (repeatable)
+        #[arg(long, requires = "all")]
+        tag: Vec<String>,
     },
 
+    /// Combine a cover letter, CV and PDF attachments into one application pack
+    Bundle(BundleArgs),
+
     /// Start preview server
     Serve {
         /// Input markdown file
@@ -100,41 +178,143 @@ enum Commands {
     },
 
     /// Tailor CV for a specific job description using AI
-    Tailor {
-        /// Input CV markdown file (.md)
-        #[arg(short, long)]
-        cv: PathBuf,
+    Tailor(TailorArgs),
+}
 
-        /// Job description PDF file (.pdf)
-        #[arg(short, long)]
-        job_description: PathBuf,
+/// Arguments of the themes command
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+struct ThemesArgs {
+    #[command(subcommand)]
+    action: Option<ThemesAction>,
 
-        /// Output markdown file for tailored CV
-        #[arg(short, long)]
-        output: Option<PathBuf>,
+    #[command(flatten)]
+    sections: ThemeSectionArgs,
 
-        /// Font theme (classic, modern, sharp)
-        #[arg(short, long, default_value = crate::constants::DEFAULT_THEME)]
-        font_theme: String,
+    /// Print the themes as JSON, with each color palette and font status
+    #[arg(long, conflicts_with_all = ["preview", "gallery", "check"])]
+    json: bool,
 
-        /// Color theme (classic, modern, sharp)
-        #[arg(short = 'C', long, default_value = crate::constants::DEFAULT_THEME)]
-        color_theme: String,
+    /// Render PNG thumbnails of each theme into a directory
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "theme-previews")]
+    preview: Option<PathBuf>,
 
-        /// Output format (pdf, docx, html, md)
-        #[arg(short = 'F', long, default_value = "pdf")]
-        format: String,
+    /// Render every font and color theme pairing, plus an HTML index, into a directory
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "theme-gallery")]
+    gallery: Option<PathBuf>,
 
-        /// Verbose output
-        #[arg(short, long)]
-        verbose: bool,
+    /// Check the WCAG contrast of a color theme or theme file (default: all built-in themes)
+    #[arg(long, value_name = "THEME", num_args = 0..=1, default_missing_value = "all")]
+    check: Option<String>,
+}
 
-        /// Suppress output
+/// Kinds of theme the themes command lists (default: all)
+#[derive(Args)]
+struct ThemeSectionArgs {
+    /// Show font themes
+    #[arg(long)]
+    fonts: bool,
+
+    /// Show color themes
+    #[arg(long)]
+    colors: bool,
+
+    /// Show layout themes
+    #[arg(long)]
+    layouts: bool,
+}
+
+#[derive(Subcommand)]
+enum ThemesAction {
+    /// Derive a color theme from a brand color and save it as a theme file
+    Generate {
+        /// Brand color, used as the primary color (e.g. "#0066CC")
+        #[arg(long, value_name = "COLOR")]
+        from: String,
+
+        /// Theme name
+        #[arg(long)]
+        name: String,
+
+        /// Theme file to write (default: <name>.yaml)
         #[arg(short, long)]
-        quiet: bool,
+        output: Option<PathBuf>,
     },
 }
 
+/// Arguments of the tailor command
+#[derive(Args)]
+struct TailorArgs {
+    /// Input CV markdown file (.md)
+    #[arg(short, long)]
+    cv: PathBuf,
+
+    /// Job description PDF file (.pdf)
+    #[arg(short, long)]
+    job_description: PathBuf,
+
+    /// Output markdown file for tailored CV
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Font theme (classic, modern, sharp)
+    #[arg(short, long, default_value = crate::constants::DEFAULT_THEME)]
+    font_theme: String,
+
+    /// Color theme (classic, modern, sharp)
+    #[arg(short = 'C', long, default_value = crate::constants::DEFAULT_THEME)]
+    color_theme: String,
+
+    /// Output format (pdf, docx, html, md)
+    #[arg(short = 'F', long, default_value = "pdf")]
+    format: String,
+
+    /// AI profile from the global config (default: `AI_PROFILE`, then the
+    /// config's `default_profile`, then the `AI_*` environment variables)
+    #[arg(short, long)]
+    profile: Option<String>,
+
+    /// Verbose output
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Suppress output
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+/// Arguments of the bundle command
+#[derive(Args)]
+struct BundleArgs {
+    /// CV markdown file
+    #[arg(long)]
+    cv: PathBuf,
+
+    /// Cover letter markdown file, placed before the CV
+    #[arg(short, long)]
+    letter: Option<PathBuf>,
+
+    /// PDF to append after the CV, such as a certificate (repeatable)
+    #[arg(short, long = "attach", value_name = "PDF")]
+    attachments: Vec<PathBuf>,
+
+    /// Font theme (classic, modern, sharp)
+    #[arg(short, long, default_value = crate::constants::DEFAULT_THEME)]
+    font_theme: String,
+
+    /// Color theme (classic, modern, sharp)
+    #[arg(short, long, default_value = crate::constants::DEFAULT_THEME)]
+    color_theme: String,
+
+    /// Output file (default: application.pdf, or application.zip with --zip)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Write a zip holding each document as PDF and plain text instead of one PDF
+    #[arg(long)]
+    zip: bool,
+}
+
 #[derive(clap::ValueEnum, Clone)]
 enum NewDocType {
     Cv,
@@ -144,7 +324,8 @@ enum NewDocType {
 /// Initialize the logger based on the command's verbose flag
 fn init_logger(command: &Commands) {
     let default_filter = match command {
-        Commands::Build { verbose: true, .. } | Commands::Tailor { verbose: true, .. } => "info",
+        Commands::Build { verbose: true, .. }
+        | Commands::Tailor(TailorArgs { verbose: true, .. }) => "info",
         _ => "warn",
     };
 
@@ -160,6 +341,15 @@ fn output_user_message<T: Display>(message: T, quiet: bool) {
     }
 }
 
+/// `--force` renders every output instead of skipping unchanged ones
+const fn cache_mode(force: bool) -> CacheMode {
+    if force {
+        CacheMode::Refresh
+    } else {
+        CacheMode::Reuse
+    }
+}
+
 /// Handle the build command
 fn handle_build(generator: &CvGenerator, options: &BuildOptions<'_>) -> Result<()> {
     output_user_message(
@@ -190,22 +380,321 @@ fn handle_build(generator: &CvGenerator, options: &BuildOptions<'_>) -> Result<(
     Ok(())
 }
 
-/// Handle the tailor command
-async fn handle_tailor(generator: &CvGenerator, options: &TailorOptions<'_>) -> Result<()> {
+/// Check one document, or every manifest document with `--all`
+fn handle_check_command(
+    input: Option<&Path>,
+    manifest: Option<PathBuf>,
+    tags: &[String],
+) -> Result<()> {
+    match input {
+        Some(input) => handle_check(input),
+        None => handle_manifest_check(manifest, tags),
+    }
+}
+
+/// Start the preview server
+fn handle_serve(input: &Path, port: u16) {
+    // Show user message
+    println!("{} Starting preview server...", "→".blue());
+    println!("  Input: {}", input.display().to_string().dimmed());
+    println!("  Server: http://localhost:{}", port.to_string().dimmed());
+    // Log separately
+    info!("Preview server at http://localhost:{port}");
+    CvGenerator::serve(input, port);
+}
+
+/// Review settings for `--draft`, stamped from the first input's repository
+fn review(draft: Option<&str>, notes: bool, inputs: &[PathBuf]) -> Option<Review> {
+    let input = inputs.first()?;
+    draft.map(|watermark| Review::new(watermark, notes, input))
+}
+
+/// The anonymizer for `--anonymize`, read from `--anonymize-map` when given
+fn load_anonymizer(enabled: bool, map: Option<&Path>) -> Result<Option<Anonymizer>> {
+    if !enabled {
+        return Ok(None);
+    }
+    map.map_or_else(|| Ok(Anonymizer::default()), Anonymizer::load)
+        .map(Some)
+}
+
+/// Dispatch a build to the single-output or batch path
+fn handle_build_command(generator: &CvGenerator, options: &BatchOptions<'_>) -> Result<()> {
+    if let ([input], [format]) = (options.inputs, options.formats) {
+        return handle_build(
+            generator,
+            &BuildOptions {
+                input,
+                font_theme: options.font_theme,
+                color_theme: options.color_theme,
+                layout: options.layout,
+                output: options.output,
+                format,
+                template: options.template,
+                render: options.render.clone(),
+                max_pages: options.max_pages,
+                fail_on_warnings: options.fail_on_warnings,
+                cache: options.cache,
+                anonymize: options.anonymize,
+                verbose: options.verbose,
+                quiet: options.quiet,
+            },
+        );
+    }
+
+    handle_batch_build(options)
+}
+
+/// Handle a build with several inputs and/or formats
+fn handle_batch_build(options: &BatchOptions<'_>) -> Result<()> {
     output_user_message(
-        format!("{} Tailoring CV to job description using AI...", "→".blue()),
+        format!(
+            "{} Building {} document(s) as {}...",
+            "→".blue(),
+            options.inputs.len(),
+            options.formats.join(", ")
+        ),
         options.quiet,
     );
 
-    info!(
-        "Using AI endpoint: {}",
-        std::env::var("AI_ENDPOINT").unwrap_or_else(|_| "not set".to_string())
+    let outcomes = CvGenerator::build_batch(options)?;
+    report_outcomes(&outcomes, options.quiet, options.fail_on_warnings)
+}
+
+/// Print the build summary and fail if any output failed, or warned with
+/// `--fail-on-warnings`
+fn report_outcomes(outcomes: &[BuildOutcome], quiet: bool, fail_on_warnings: bool) -> Result<()> {
+    let failed = outcomes.iter().filter(|o| !o.succeeded()).count();
+    let warnings: usize = outcomes.iter().map(|o| o.warnings.len()).sum();
+    let warnings_fail = fail_on_warnings && warnings > 0;
+
+    if !quiet || failed > 0 || warnings_fail {
+        println!("\n{}", format_build_summary(outcomes));
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} outputs failed", outcomes.len());
+    }
+    if warnings_fail {
+        return Err(CvError::RenderWarnings { count: warnings }.into());
+    }
+
+    let unchanged = outcomes.iter().filter(|o| o.cached).count();
+    let mut message = format!(
+        "{} {} outputs generated successfully!",
+        "✓".green(),
+        outcomes.len()
     );
-    info!(
-        "Using AI model: {}",
-        std::env::var("AI_MODEL").unwrap_or_else(|_| "not set".to_string())
+    if unchanged > 0 {
+        let _ = write!(message, " ({unchanged} unchanged)");
+    }
+    output_user_message(message, quiet);
+
+    Ok(())
+}
+
+/// Handle the new command
+fn handle_new(doc_type: &NewDocType, output: &Path) -> Result<()> {
+    match doc_type {
+        NewDocType::Cv => CvGenerator::new_cv(output)?,
+        NewDocType::Letter => CvGenerator::new_letter(output)?,
+    }
+    // Show user message
+    println!("{} Created {} template", "✓".green(), output.display());
+    // Log separately
+    info!("Created {} template", output.display());
+    Ok(())
+}
+
+/// Load the manifest given with --manifest, or the nearest cv.toml
+fn load_manifest(path: Option<PathBuf>) -> Result<Manifest> {
+    let path = match path {
+        Some(path) => path,
+        None => Manifest::find(&std::env::current_dir()?).ok_or_else(|| {
+            anyhow::anyhow!("No {MANIFEST_FILE} found in the current directory or its parents")
+        })?,
+    };
+    info!("Using manifest {}", path.display());
+    Manifest::load(&path)
+}
+
+/// Handle `build --all`
+fn handle_manifest_build(
+    manifest: Option<PathBuf>,
+    tags: &[String],
+    quiet: bool,
+    fail_on_warnings: bool,
+    cache: CacheMode,
+) -> Result<()> {
+    let manifest = load_manifest(manifest)?;
+    let jobs = manifest.jobs(tags)?;
+    if jobs.is_empty() {
+        anyhow::bail!("The manifest selects no documents");
+    }
+
+    output_user_message(
+        format!(
+            "{} Building {} output(s) from the manifest...",
+            "→".blue(),
+            jobs.len()
+        ),
+        quiet,
+    );
+
+    let outcomes = CvGenerator::run_jobs(&jobs, cache)?;
+    report_outcomes(&outcomes, quiet, fail_on_warnings)
+}
+
+/// Handle the themes command
+fn handle_themes(generator: &CvGenerator, args: &ThemesArgs) -> Result<()> {
+    if let Some(ThemesAction::Generate { from, name, output }) = &args.action {
+        let output = output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("{name}.yaml")));
+        CvGenerator::generate_theme(name, from, &output)?;
+        println!(
+            "{} Theme {} written to {}",
+            "✓".green(),
+            name.cyan(),
+            output.display()
+        );
+        println!("  Use it with: cv build cv.md -c {}", output.display());
+        return Ok(());
+    }
+
+    if let Some(theme) = &args.check {
+        return CvGenerator::check_contrast(Some(theme.as_str()).filter(|theme| *theme != "all"));
+    }
+
+    // Show all if none specified
+    let sections = ThemeSections {
+        fonts: args.sections.fonts,
+        colors: args.sections.colors,
+        layouts: args.sections.layouts,
+    }
+    .or_all();
+    if args.json {
+        println!("{}", CvGenerator::themes_json(sections)?);
+        return Ok(());
+    }
+    CvGenerator::list_themes(sections)?;
+
+    if let Some(dir) = &args.preview {
+        println!();
+        println!("{} Rendering theme previews...", "→".blue());
+        for thumbnail in CvGenerator::preview_themes(dir)? {
+            println!("  {}", thumbnail.display().to_string().dimmed());
+        }
+        println!("{} Previews written to {}", "✓".green(), dir.display());
+    }
+
+    if let Some(dir) = &args.gallery {
+        println!();
+        println!("{} Rendering theme gallery...", "→".blue());
+        let index = generator.theme_gallery(dir)?;
+        println!("{} Gallery written to {}", "✓".green(), index.display());
+    }
+
+    Ok(())
+}
+
+/// Handle the check command for a single document
+fn handle_check(input: &Path) -> Result<()> {
+    println!("{} Checking document structure...", "→".blue());
+    println!("  Input: {}", input.display().to_string().dimmed());
+
+    for warning in CvGenerator::check(input)? {
+        println!("{} {warning}", "⚠".yellow());
+    }
+
+    // Show user message
+    println!("{} {} is valid!", "✓".green(), input.display());
+    // Log separately
+    info!("{} is valid!", input.display());
+    Ok(())
+}
+
+/// Handle `check --all`
+fn handle_manifest_check(manifest: Option<PathBuf>, tags: &[String]) -> Result<()> {
+    let manifest = load_manifest(manifest)?;
+    let results = CvGenerator::check_manifest(&manifest, tags)?;
+    println!(
+        "{} Checking {} document(s) from the manifest...",
+        "→".blue(),
+        results.len()
+    );
+
+    let mut failed = 0;
+    for (input, error) in &results {
+        match error {
+            None => println!("  {} {}", "✓".green(), input.display()),
+            Some(error) => {
+                failed += 1;
+                println!("  {} {}: {}", "✗".red(), input.display(), error);
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} documents are invalid", results.len());
+    }
+
+    println!("{} All documents are valid!", "✓".green());
+    Ok(())
+}
+
+/// Handle the bundle command
+fn handle_bundle(args: &BundleArgs) -> Result<()> {
+    println!("{} Bundling application...", "→".blue());
+
+    let output = args
+        .output
+        .clone()
+        .unwrap_or_else(|| default_bundle_output(args.zip));
+    let report = CvGenerator::bundle(&BundleOptions {
+        cv: &args.cv,
+        letter: args.letter.as_deref(),
+        attachments: &args.attachments,
+        font_theme: &args.font_theme,
+        color_theme: &args.color_theme,
+        output: &output,
+        zip: args.zip,
+    })?;
+
+    println!("{} Output: {}", "→".blue(), report.output.display());
+    if let Some(pages) = report.pages {
+        println!("  Pages: {pages}");
+    }
+    for item in &report.contents {
+        println!("  • {item}");
+    }
+    println!("{} Application bundled successfully!", "✓".green());
+    info!("Bundled {}", report.output.display());
+    Ok(())
+}
+
+/// Handle the tailor command
+async fn handle_tailor(generator: &CvGenerator, args: &TailorArgs) -> Result<()> {
+    let options = &TailorOptions {
+        cv_path: &args.cv,
+        job_description_path: &args.job_description,
+        output: args.output.as_deref(),
+        font_theme: &args.font_theme,
+        color_theme: &args.color_theme,
+        format: &args.format,
+        profile: args.profile.as_deref(),
+        verbose: args.verbose,
+        quiet: args.quiet,
+    };
+    output_user_message(
+        format!("{} Tailoring CV to job description using AI...", "→".blue()),
+        options.quiet,
     );
 
+    if let Some(profile) = options.profile {
+        info!("Using AI profile: {profile}");
+    }
+
     if let Err(e) = generator.tailor(options).await {
         error!("Error during CV tailoring: {e}");
 
@@ -238,96 +727,84 @@ async fn main() -> Result<()> {
     let generator = CvGenerator::new()?;
 
     match cli.command {
+        Commands::Build {
+            all: true,
+            manifest,
+            tag,
+            fail_on_warnings,
+            force,
+            quiet,
+            ..
+        } => handle_manifest_build(manifest, &tag, quiet, fail_on_warnings, cache_mode(force))?,
+
         Commands::Build {
             input,
             font_theme,
             color_theme,
+            layout,
             output,
             format,
             template,
+            dpi,
+            pdf_a,
+            print_safe,
+            draft,
+            notes,
+            max_pages,
+            fail_on_warnings,
+            force,
+            anonymize,
+            anonymize_map,
             verbose,
             quiet,
-        } => handle_build(
-            &generator,
-            &BuildOptions {
-                input: &input,
-                font_theme: &font_theme,
-                color_theme: &color_theme,
-                output: output.as_deref(),
-                format: &format,
-                template: template.as_deref(),
-                verbose,
-                quiet,
-            },
-        )?,
-
-        Commands::New { doc_type, output } => {
-            match doc_type {
-                NewDocType::Cv => CvGenerator::new_cv(&output)?,
-                NewDocType::Letter => CvGenerator::new_letter(&output)?,
-            }
-            // Show user message
-            println!("{} Created {} template", "✓".green(), output.display());
-            // Log separately
-            info!("Created {} template", output.display());
-        }
-
-        Commands::Themes { fonts, colors } => {
-            if !fonts && !colors {
-                // Show both if neither specified
-                CvGenerator::list_themes(true, true);
-            } else {
-                CvGenerator::list_themes(fonts, colors);
-            }
-        }
-
-        Commands::Check { input } => {
-            println!("{} Checking document structure...", "→".blue());
-            println!("  Input: {}", input.display().to_string().dimmed());
-
-            CvGenerator::check(&input)?;
-
-            // Show user message
-            println!("{} {} is valid!", "✓".green(), input.display());
-            // Log separately
-            info!("{} is valid!", input.display());
-        }
-
-        Commands::Serve { input, port } => {
-            // Show user message
-            println!("{} Starting preview server...", "→".blue());
-            println!("  Input: {}", input.display().to_string().dimmed());
-            println!("  Server: http://localhost:{}", port.to_string().dimmed());
-            // Log separately
-            info!("Preview server at http://localhost:{port}");
-            CvGenerator::serve(&input, port);
-        }
-
-        Commands::Tailor {
-            cv,
-            job_description,
-            output,
-            font_theme,
-            color_theme,
-            format,
-            verbose,
-            quiet,
+            ..
         } => {
-            handle_tailor(
+            let inputs = expand_inputs(&input)?;
+            let formats = parse_formats(&format)?;
+            let anonymizer = load_anonymizer(anonymize, anonymize_map.as_deref())?;
+            handle_build_command(
                 &generator,
-                &TailorOptions {
-                    cv_path: &cv,
-                    job_description_path: &job_description,
-                    output: output.as_deref(),
+                &BatchOptions {
+                    inputs: &inputs,
+                    formats: &formats,
                     font_theme: &font_theme,
                     color_theme: &color_theme,
-                    format: &format,
+                    layout: layout.as_deref(),
+                    output: output.as_deref(),
+                    template: template.as_deref(),
+                    render: RenderOptions {
+                        dpi,
+                        pdf_a,
+                        review: review(draft.as_deref(), notes, &inputs),
+                        print_safe,
+                        ..RenderOptions::default()
+                    },
+                    max_pages,
+                    fail_on_warnings,
+                    cache: cache_mode(force),
+                    anonymize: anonymizer.as_ref(),
                     verbose,
                     quiet,
                 },
-            )
-            .await?;
+            )?;
         }
+
+        Commands::New { doc_type, output } => handle_new(&doc_type, &output)?,
+
+        Commands::Themes(args) => handle_themes(&generator, &args)?,
+
+        Commands::Check {
+            input,
+            manifest,
+            tag,
+            ..
+        } => handle_check_command(input.as_deref(), manifest, &tag)?,
+
+        Commands::Bundle(args) => handle_bundle(&args)?,
+
+        Commands::Serve { input, port } => handle_serve(&input, port),
+
+        Commands::Tailor(args) => handle_tailor(&generator, &args).await?,
     }
 
     Ok(())
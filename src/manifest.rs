@@ -0,0 +1,316 @@
+//! Project manifest (`cv.toml`) listing the documents in a repository
+//!
+//! A manifest declares shared defaults and one `[[documents]]` entry per CV or
+//! letter, each of which may override the themes, formats, outputs and template.
+//! Relative paths are resolved against the directory holding the manifest.
+//!
+//! ```toml
+//! [defaults]
+//! font_theme = "modern"
+//! color_theme = "classic"
+//! formats = ["pdf"]
+//! output_dir = "out"
+//!
+//! [[documents]]
+//! input = "people/alice.md"
+//! formats = ["pdf", "html"]
+//! tags = ["engineering"]
+//!
+//! [[documents]]
+//! input = "letters/acme.md"
+//! color_theme = "sharp"
+//! layout = "compact"
+//! output = "out/acme-letter.pdf"
+//! ```
+
+use crate::build::{output_path, parse_formats, BuildJob};
+use crate::constants::DEFAULT_THEME;
+use crate::error::CvError;
+use crate::render::DEFAULT_DPI;
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// File name looked up when no manifest path is given
+pub const MANIFEST_FILE: &str = "cv.toml";
+
+/// Settings shared by every document unless overridden
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDefaults {
+    pub font_theme: Option<String>,
+    pub color_theme: Option<String>,
+    pub layout: Option<String>,
+    pub formats: Option<Vec<String>>,
+    pub output_dir: Option<PathBuf>,
+    pub template: Option<PathBuf>,
+    pub dpi: Option<u32>,
+    pub pdf_a: Option<bool>,
+    pub print_safe: Option<bool>,
+    pub max_pages: Option<usize>,
+}
+
+/// One document declared in the manifest
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestDocument {
+    pub input: PathBuf,
+    pub font_theme: Option<String>,
+    pub color_theme: Option<String>,
+    pub layout: Option<String>,
+    pub formats: Option<Vec<String>>,
+    /// Output file; only allowed when the document has a single format
+    pub output: Option<PathBuf>,
+    pub output_dir: Option<PathBuf>,
+    pub template: Option<PathBuf>,
+    pub dpi: Option<u32>,
+    pub pdf_a: Option<bool>,
+    pub print_safe: Option<bool>,
+    pub max_pages: Option<usize>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default)]
+    pub defaults: ManifestDefaults,
+    #[serde(default)]
+    pub documents: Vec<ManifestDocument>,
+
+    /// Path the manifest was loaded from
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl Manifest {
+    /// Loads a manifest from `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid manifest.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(CvError::FileNotFound(path.to_path_buf()).into());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        let mut manifest: Self =
+            toml::from_str(&content).map_err(|e| CvError::InvalidManifest {
+                path: path.to_path_buf(),
+                reason: e.message().to_string(),
+            })?;
+
+        manifest.path = path.to_path_buf();
+        Ok(manifest)
+    }
+
+    /// Directory that relative paths in the manifest are resolved against.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        self.path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Finds `cv.toml` in `start` or the nearest parent directory.
+    #[must_use]
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(MANIFEST_FILE))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Returns the documents carrying at least one of `tags`, or all documents when
+    /// no tags are given.
+    #[must_use]
+    pub fn select(&self, tags: &[String]) -> Vec<&ManifestDocument> {
+        self.documents
+            .iter()
+            .filter(|doc| tags.is_empty() || doc.tags.iter().any(|t| tags.contains(t)))
+            .collect()
+    }
+
+    /// Resolves a path from the manifest against the manifest's directory.
+    #[must_use]
+    pub fn resolve_path(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            self.root().join(path)
+        }
+    }
+
+    /// Turns the selected documents into build jobs, applying the shared defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a document lists an invalid format or sets `output`
+    /// together with several formats.
+    pub fn jobs(&self, tags: &[String]) -> Result<Vec<BuildJob>> {
+        let defaults = &self.defaults;
+        let mut jobs = Vec::new();
+
+        for doc in self.select(tags) {
+            let formats = doc
+                .formats
+                .as_ref()
+                .or(defaults.formats.as_ref())
+                .map_or_else(
+                    || Ok(vec!["pdf".to_string()]),
+                    |f| parse_formats(&f.join(",")),
+                )?;
+
+            if doc.output.is_some() && formats.len() > 1 {
+                return Err(CvError::InvalidManifest {
+                    path: self.path.clone(),
+                    reason: format!(
+                        "{} sets 'output' but builds {} formats; use 'output_dir' instead",
+                        doc.input.display(),
+                        formats.len()
+                    ),
+                }
+                .into());
+            }
+
+            let input = self.resolve_path(&doc.input);
+            let output_dir = doc
+                .output_dir
+                .as_ref()
+                .or(defaults.output_dir.as_ref())
+                .map_or_else(|| self.root().to_path_buf(), |dir| self.resolve_path(dir));
+
+            for format in formats {
+                let output = doc.output.as_ref().map_or_else(
+                    || output_path(&input, &format, Some(&output_dir), true),
+                    |file| self.resolve_path(file),
+                );
+
+                jobs.push(BuildJob {
+                    input: input.clone(),
+                    output,
+                    font_theme: doc
+                        .font_theme
+                        .clone()
+                        .or_else(|| defaults.font_theme.clone())
+                        .unwrap_or_else(|| DEFAULT_THEME.to_string()),
+                    color_theme: doc
+                        .color_theme
+                        .clone()
+                        .or_else(|| defaults.color_theme.clone())
+                        .unwrap_or_else(|| DEFAULT_THEME.to_string()),
+                    layout: doc.layout.clone().or_else(|| defaults.layout.clone()),
+                    template: doc
+                        .template
+                        .as_ref()
+                        .or(defaults.template.as_ref())
+                        .map(|t| self.resolve_path(t)),
+                    dpi: doc.dpi.or(defaults.dpi).unwrap_or(DEFAULT_DPI),
+                    pdf_a: doc.pdf_a.or(defaults.pdf_a).unwrap_or(false),
+                    print_safe: doc.print_safe.or(defaults.print_safe).unwrap_or(false),
+                    review: None,
+                    max_pages: doc.max_pages.or(defaults.max_pages),
+                    anonymize: None,
+                    format,
+                });
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /// Input paths of the selected documents, resolved against the manifest.
+    #[must_use]
+    pub fn inputs(&self, tags: &[String]) -> Vec<PathBuf> {
+        self.select(tags)
+            .into_iter()
+            .map(|doc| self.resolve_path(&doc.input))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Manifest {
+        let mut manifest: Manifest = toml::from_str(content).expect("Valid manifest");
+        manifest.path = PathBuf::from("repo/cv.toml");
+        manifest
+    }
+
+    #[test]
+    fn test_jobs_apply_defaults_and_overrides() {
+        let manifest = parse(
+            r#"
+[defaults]
+color_theme = "classic"
+formats = ["pdf"]
+output_dir = "out"
+
+[[documents]]
+input = "alice.md"
+formats = ["pdf", "html"]
+
+[[documents]]
+input = "letters/acme.md"
+font_theme = "sharp"
+output = "final/acme.pdf"
+"#,
+        );
+
+        let jobs = manifest.jobs(&[]).expect("Jobs resolve");
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].input, PathBuf::from("repo/alice.md"));
+        assert_eq!(jobs[0].output, PathBuf::from("repo/out/alice.pdf"));
+        assert_eq!(jobs[1].output, PathBuf::from("repo/out/alice.html"));
+        assert_eq!(jobs[0].font_theme, DEFAULT_THEME);
+        assert_eq!(jobs[0].color_theme, "classic");
+        assert_eq!(jobs[2].output, PathBuf::from("repo/final/acme.pdf"));
+        assert_eq!(jobs[2].font_theme, "sharp");
+    }
+
+    #[test]
+    fn test_select_by_tag() {
+        let manifest = parse(
+            r#"
+[[documents]]
+input = "a.md"
+tags = ["eng"]
+
+[[documents]]
+input = "b.md"
+tags = ["sales"]
+"#,
+        );
+
+        assert_eq!(manifest.select(&[]).len(), 2);
+        let selected = manifest.select(&["sales".to_string()]);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].input, PathBuf::from("b.md"));
+    }
+
+    #[test]
+    fn test_output_with_several_formats_is_rejected() {
+        let manifest = parse(
+            r#"
+[[documents]]
+input = "a.md"
+formats = ["pdf", "html"]
+output = "a.pdf"
+"#,
+        );
+
+        assert!(manifest.jobs(&[]).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        assert!(
+            toml::from_str::<Manifest>("[[documents]]\ninput = \"a.md\"\nfont = \"x\"\n").is_err()
+        );
+    }
+}
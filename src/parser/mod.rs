@@ -42,6 +42,16 @@ impl Document {
         })
     }
 
+    /// Returns a copy of the document with its markdown body replaced.
+    #[must_use]
+    pub fn with_content(&self, content: String) -> Self {
+        Self {
+            metadata: self.metadata.clone(),
+            markdown_ast: markdown::parse_markdown(&content),
+            content,
+        }
+    }
+
     /// Validates that the document contains required fields.
     ///
     /// # Errors
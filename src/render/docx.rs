@@ -1,5 +1,5 @@
 use crate::parser::Document;
-use crate::render::{load_template, RenderEngine};
+use crate::render::{load_template, RenderEngine, RenderReport};
 use crate::themes::Theme;
 use anyhow::Result;
 use std::path::Path;
@@ -23,10 +23,10 @@ impl DocxRenderer {
 }
 
 impl RenderEngine for DocxRenderer {
-    fn render(&self, _doc: &Document, _theme: &Theme, output: &Path) -> Result<()> {
+    fn render(&self, _doc: &Document, _theme: &Theme, output: &Path) -> Result<RenderReport> {
         // TODO: Implement DOCX generation
         // For now, create a placeholder file
         std::fs::write(output, b"Placeholder DOCX file")?;
-        Ok(())
+        Ok(RenderReport::default())
     }
 }
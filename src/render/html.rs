@@ -1,12 +1,22 @@
+use crate::constants::layout::layout_styles;
 use crate::parser::Document;
-use crate::render::{load_template, RenderEngine};
+use crate::render::icons::{self, Contact};
+use crate::render::page_text::{self, Part};
+use crate::render::review::{self, Review};
+use crate::render::{load_template, RenderEngine, RenderOptions, RenderReport};
+use crate::themes::layout::{EntryLayout, HeaderStyle, LayoutTheme, SeparatorStyle};
 use crate::themes::Theme;
 use anyhow::Result;
-use pulldown_cmark::html;
+use pulldown_cmark::{html, Event, HeadingLevel, Tag, TagEnd};
+use std::fmt::Write;
 use std::path::Path;
 
+/// Built-in page template, using the same slots that user templates can use
+pub const DEFAULT_TEMPLATE: &str = include_str!("../templates/page.html");
+
 pub struct HtmlRenderer {
-    _template: Option<String>,
+    template: Option<String>,
+    review: Option<Review>,
 }
 
 impl HtmlRenderer {
@@ -15,116 +25,558 @@ impl HtmlRenderer {
     /// # Errors
     ///
     /// Returns an error if the template file cannot be read.
+    #[allow(dead_code)] // `allow(dead_code)` exception
     pub fn new(template_path: Option<&Path>) -> Result<Self> {
+        Self::with_options(template_path, &RenderOptions::default())
+    }
+
+    /// Creates a new HTML renderer with optional custom template and render options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template file cannot be read.
+    pub fn with_options(template_path: Option<&Path>, options: &RenderOptions) -> Result<Self> {
         let template = load_template(template_path)?;
         Ok(Self {
-            _template: template,
+            template,
+            review: options.review.clone(),
         })
     }
 
-    fn generate_html(doc: &Document, theme: &Theme) -> String {
-        let mut html_output = String::new();
-        html::push_html(&mut html_output, doc.markdown_ast.iter().cloned());
+    fn generate_html(&self, doc: &Document, theme: &Theme) -> String {
+        let template = self.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+        let kind = if doc.metadata.recipient.is_some() {
+            "Cover Letter"
+        } else {
+            "CV"
+        };
+        let title = format!("{} - {kind}", escape_html(&doc.metadata.name));
+
+        let slots = [
+            ("lang", "en".to_string()),
+            ("title", title),
+            ("theme_css", Self::theme_css(theme)),
+            ("page_css", self.page_css(doc)),
+            ("json_ld", Self::json_ld(doc)),
+            ("header", Self::header_html(doc)),
+            ("contact", Self::contact_html(doc, theme)),
+            ("body", self.body_html(doc, theme)),
+        ];
+
+        fill_slots(template, &slots)
+    }
+
+    /// CSS custom properties carrying the theme colours, fonts and layout
+    fn theme_css(theme: &Theme) -> String {
+        let color = &theme.color;
+        let h1 = color.h1_color.as_deref().unwrap_or(&color.text);
+        let h2 = color.h2_color.as_deref().unwrap_or(&color.primary);
+        let h3 = color.h3_color.as_deref().unwrap_or(&color.text);
+
+        let mut css = String::from("        :root {\n");
+        for (name, value) in [
+            ("primary", color.primary.as_str()),
+            ("secondary", &color.secondary),
+            ("accent", &color.accent),
+            ("text", &color.text),
+            ("muted", &color.muted),
+            ("background", &color.background),
+            ("surface", &color.surface),
+            ("border", &color.border),
+            ("h1", h1),
+            ("h2", h2),
+            ("h3", h3),
+        ] {
+            let _ = writeln!(css, "            --{name}: {value};");
+        }
+        for (role, spec) in [("header", &theme.font.header), ("body", &theme.font.body)] {
+            let _ = writeln!(css, "            --font-{role}: \"{}\";", spec.family);
+            for (name, value) in [
+                ("weight", spec.weight_regular.to_string()),
+                ("weight-bold", spec.weight_bold.to_string()),
+                ("size-name", spec.size_name.clone()),
+                ("size-section", spec.size_section.clone()),
+                ("size-subsection", spec.size_subsection.clone()),
+                ("size-role", spec.size_role.clone()),
+                ("size-normal", spec.size_normal.clone()),
+                ("size-small", spec.size_small.clone()),
+                ("line-height", spec.line_height.to_string()),
+                (
+                    "letter-spacing",
+                    spec.letter_spacing
+                        .as_deref()
+                        .unwrap_or("normal")
+                        .to_string(),
+                ),
+            ] {
+                let _ = writeln!(css, "            --font-{role}-{name}: {value};");
+            }
+        }
+        Self::layout_css(&theme.layout, &mut css);
+        css.push_str("        }");
+        css
+    }
+
+    /// Custom properties for the layout theme's header, rules and spacing
+    fn layout_css(layout: &LayoutTheme, css: &mut String) {
+        let banner = layout.header_style == HeaderStyle::Banner;
+        let align = if layout.header_style == HeaderStyle::Left {
+            "left"
+        } else {
+            "center"
+        };
+        let (fill, text, muted, link, padding) = if banner {
+            (
+                "var(--primary)",
+                "var(--background)",
+                "var(--background)",
+                "inherit",
+                layout_styles::BANNER_INSET,
+            )
+        } else {
+            (
+                "transparent",
+                "var(--text)",
+                "var(--muted)",
+                "var(--accent)",
+                "0",
+            )
+        };
+        let (separator_width, separator_style) = match layout.separator_style {
+            SeparatorStyle::Line => (layout_styles::SEPARATOR_FULL, "solid"),
+            SeparatorStyle::Short => (layout_styles::SEPARATOR_SHORT, "solid"),
+            SeparatorStyle::None => ("0", "none"),
+        };
+
+        for (name, value) in [
+            ("header-align", align.to_string()),
+            ("header-fill", fill.to_string()),
+            ("header-text", text.to_string()),
+            ("header-muted", muted.to_string()),
+            ("header-link", link.to_string()),
+            ("header-padding", padding.to_string()),
+            ("separator-width", separator_width.to_string()),
+            ("separator-style", separator_style.to_string()),
+            (
+                "separator-thickness",
+                format!("{}pt", layout.get_separator_thickness()),
+            ),
+            (
+                "h1-spacing-above",
+                format!("{}rem", layout.get_h1_spacing_above()),
+            ),
+            (
+                "h1-spacing-below",
+                format!("{}rem", layout.get_h1_spacing_below()),
+            ),
+            (
+                "h2-spacing-above",
+                format!("{}rem", layout.get_h2_spacing_above()),
+            ),
+            (
+                "h2-spacing-below",
+                format!("{}rem", layout.get_h2_spacing_below()),
+            ),
+            (
+                "h3-spacing-above",
+                format!("{}rem", layout.get_h3_spacing_above()),
+            ),
+            (
+                "h3-spacing-below",
+                format!("{}rem", layout.get_h3_spacing_below()),
+            ),
+        ] {
+            let _ = writeln!(css, "            --{name}: {value};");
+        }
+    }
+
+    /// Print CSS placing the frontmatter header and footer in the page margin boxes,
+    /// plus the watermark and note styles of a review build
+    fn page_css(&self, doc: &Document) -> String {
+        let review = self
+            .review
+            .as_ref()
+            .map(Self::review_css)
+            .unwrap_or_default();
+        let meta = &doc.metadata;
+        let mut boxes = String::new();
+        let mut first_page = String::new();
+
+        for (edge, page_text) in [("top", &meta.header), ("bottom", &meta.footer)] {
+            let Some(page_text) = page_text else {
+                continue;
+            };
+            for (slot, text) in page_text::slots(page_text) {
+                let Some(text) = text else {
+                    continue;
+                };
+                let _ = writeln!(
+                    boxes,
+                    "            @{edge}-{slot} {{ content: {}; font-size: 9pt; color: var(--muted); }}",
+                    css_content(text, doc)
+                );
+                if !page_text.first_page {
+                    let _ = writeln!(
+                        first_page,
+                        "            @{edge}-{slot} {{ content: none; }}"
+                    );
+                }
+            }
+        }
+
+        if boxes.is_empty() {
+            return review;
+        }
+        let mut css = format!("        @page {{\n{boxes}        }}");
+        if !first_page.is_empty() {
+            let _ = write!(css, "\n        @page :first {{\n{first_page}        }}");
+        }
+        if !review.is_empty() {
+            let _ = write!(css, "\n{review}");
+        }
+        css
+    }
+
+    /// Watermark, footer stamp and margin note styles of a review build
+    fn review_css(review: &Review) -> String {
+        let watermark = css_string(&review.watermark);
+        let stamp = css_string(&review.footer());
+        format!(
+            "        body::before {{ content: {watermark}; position: fixed; top: 50%; left: 50%; transform: translate(-50%, -50%) rotate(-45deg); font-size: 8rem; font-weight: bold; color: var(--muted); opacity: 0.2; pointer-events: none; z-index: 1; }}
+        body::after {{ content: {stamp}; position: fixed; bottom: 0.5rem; left: 0; right: 0; text-align: center; font-size: 8pt; color: var(--muted); }}
+        .review-note {{ float: right; clear: right; width: 12rem; margin: 0 -14rem 0.5rem 1rem; font-size: 0.8rem; color: var(--accent); }}
+        main {{ margin-right: 14rem; }}"
+        )
+    }
+
+    /// schema.org `Person` description of the candidate
+    fn json_ld(doc: &Document) -> String {
+        let meta = &doc.metadata;
+        let mut person = serde_json::json!({
+            "@context": "https://schema.org",
+            "@type": "Person",
+            "name": meta.name,
+        });
+
+        // Anonymized documents have no email
+        if !meta.email.is_empty() {
+            person["email"] = format!("mailto:{}", meta.email).into();
+        }
+
+        if let Some(phone) = &meta.phone {
+            person["telephone"] = phone.clone().into();
+        }
+        if let Some(location) = &meta.location {
+            person["address"] = serde_json::json!({
+                "@type": "PostalAddress",
+                "addressLocality": location,
+            });
+        }
+        if let Some(website) = &meta.website {
+            person["url"] = website.clone().into();
+        }
+
+        let mut same_as = vec![];
+        if let Some(linkedin) = &meta.linkedin {
+            same_as.push(format!("https://linkedin.com/in/{linkedin}"));
+        }
+        if let Some(github) = &meta.github {
+            same_as.push(format!("https://github.com/{github}"));
+        }
+        if !same_as.is_empty() {
+            person["sameAs"] = same_as.into();
+        }
+
+        // "</" would end the surrounding <script> element early
+        serde_json::to_string_pretty(&person)
+            .unwrap_or_default()
+            .replace("</", "<\\/")
+    }
+
+    fn header_html(doc: &Document) -> String {
+        let mut header = format!("        <h1>{}</h1>", escape_html(&doc.metadata.name));
+        if let Some(location) = &doc.metadata.location {
+            let _ = write!(
+                header,
+                "\n        <p class=\"location\">{}</p>",
+                escape_html(location)
+            );
+        }
+        header
+    }
+
+    fn contact_html(doc: &Document, theme: &Theme) -> String {
+        let meta = &doc.metadata;
+        let icon = |contact| icons::html_icon(theme.layout.icons, contact);
+        let mut parts = vec![];
+
+        if let Some(phone) = &meta.phone {
+            parts.push(format!(
+                "<span class=\"phone\">{}<a href=\"tel:{}\">{}</a></span>",
+                icon(Contact::Phone),
+                escape_html(&phone.replace(' ', "")),
+                escape_html(phone)
+            ));
+        }
+        if !meta.email.is_empty() {
+            let email = escape_html(&meta.email);
+            parts.push(format!(
+                "<span class=\"email\">{}<a href=\"mailto:{email}\">{email}</a></span>",
+                icon(Contact::Email)
+            ));
+        }
+        if let Some(website) = &meta.website {
+            let website = escape_html(website);
+            parts.push(format!(
+                "<span class=\"website\">{}<a href=\"{website}\">{website}</a></span>",
+                icon(Contact::Website)
+            ));
+        }
+        if let Some(github) = &meta.github {
+            let github = escape_html(github);
+            parts.push(format!(
+                "<span class=\"github\">{}<a href=\"https://github.com/{github}\">github.com/{github}</a></span>",
+                icon(Contact::Github)
+            ));
+        }
+        if let Some(linkedin) = &meta.linkedin {
+            let linkedin = escape_html(linkedin);
+            parts.push(format!(
+                "<span class=\"linkedin\">{}<a href=\"https://linkedin.com/in/{linkedin}\">LinkedIn</a></span>",
+                icon(Contact::Linkedin)
+            ));
+        }
 
-        // Build complete HTML document
         format!(
-            r#"<!DOCTYPE html>
-<html lang="en">
-<head>
-    <meta charset="UTF-8">
-    <meta name="viewport" content="width=device-width, initial-scale=1.0">
-    <title>{} - CV</title>
-    <style>
-        :root {{
-            --primary: {};
-            --secondary: {};
-            --accent: {};
-            --text: {};
-            --muted: {};
-            --background: {};
-        }}
-
-        body {{
-            font-family: {}, sans-serif;
-            color: var(--text);
-            background: var(--background);
-            line-height: 1.6;
-            max-width: 800px;
-            margin: 0 auto;
-            padding: 2rem;
-        }}
-
-        h1 {{
-            font-family: {}, sans-serif;
-            color: var(--primary);
-            border-bottom: 2px solid var(--primary);
-            padding-bottom: 0.5rem;
-        }}
-
-        h2 {{
-            color: var(--secondary);
-            margin-top: 2rem;
-        }}
-
-        a {{
-            color: var(--accent);
-            text-decoration: none;
-        }}
-
-        a:hover {{
-            text-decoration: underline;
-        }}
-
-        .header {{
-            text-align: center;
-            margin-bottom: 2rem;
-        }}
-
-        .contact {{
-            color: var(--muted);
-            font-size: 0.9rem;
-        }}
-    </style>
-</head>
-<body>
-    <div class="header">
-        <h1>{}</h1>
-        <div class="contact">
-            {} | {}
-            {} {}
-        </div>
-    </div>
-
-    {}
-</body>
-</html>"#,
-            doc.metadata.name,
-            theme.color.primary,
-            theme.color.secondary,
-            theme.color.accent,
-            theme.color.text,
-            theme.color.muted,
-            theme.color.background,
-            theme.font.body.family,
-            theme.font.header.family,
-            doc.metadata.name,
-            doc.metadata.email,
-            doc.metadata.phone.as_deref().unwrap_or(""),
-            doc.metadata.location.as_deref().unwrap_or(""),
-            if let Some(linkedin) = &doc.metadata.linkedin {
-                format!(r#"| <a href="https://linkedin.com/in/{linkedin}">LinkedIn</a>"#)
-            } else {
-                String::new()
-            },
-            html_output
+            "        <address class=\"contact\">\n            {}\n        </address>",
+            parts.join("\n            ")
         )
     }
+
+    /// Renders the markdown body, wrapping each H1 in a `<section>` and each H2 entry
+    /// in an `<article>` so the structure survives outside the visual layout. With the
+    /// `dates_right` entry layout, the dates ending a role heading are wrapped in a
+    /// `<span class="dates">`.
+    fn body_html(&self, doc: &Document, theme: &Theme) -> String {
+        let notes = self.review.as_ref().is_some_and(|review| review.notes);
+        let dates_right = theme.layout.entry_layout == EntryLayout::DatesRight;
+        let mut output = String::new();
+        let mut chunk: Vec<Event<'static>> = Vec::new();
+        let mut section_open = false;
+        let mut entry_open = false;
+        let mut in_role = false;
+
+        let flush = |chunk: &mut Vec<Event<'static>>, output: &mut String| {
+            html::push_html(output, chunk.drain(..));
+        };
+
+        let events = review::join_html_blocks(doc.markdown_ast.iter().cloned());
+        for event in &events {
+            match event {
+                Event::Start(Tag::Heading {
+                    level: HeadingLevel::H1,
+                    ..
+                }) => {
+                    flush(&mut chunk, &mut output);
+                    if entry_open {
+                        output.push_str("</article>\n");
+                        entry_open = false;
+                    }
+                    if section_open {
+                        output.push_str("</section>\n");
+                    }
+                    output.push_str("<section class=\"cv-section\">\n");
+                    section_open = true;
+                }
+                Event::Start(Tag::Heading {
+                    level: HeadingLevel::H2,
+                    ..
+                }) => {
+                    flush(&mut chunk, &mut output);
+                    if entry_open {
+                        output.push_str("</article>\n");
+                    }
+                    output.push_str("<article class=\"cv-entry\">\n");
+                    entry_open = true;
+                }
+                Event::Start(Tag::Heading {
+                    level: HeadingLevel::H3,
+                    ..
+                }) => in_role = dates_right,
+                Event::End(TagEnd::Heading(HeadingLevel::H3)) => in_role = false,
+                Event::Text(text) if in_role => {
+                    if let Some((role, dates)) = text.rsplit_once(", ") {
+                        chunk.extend([
+                            Event::Text(role.to_string().into()),
+                            Event::InlineHtml("<span class=\"dates\">".into()),
+                            Event::Text(dates.to_string().into()),
+                            Event::InlineHtml("</span>".into()),
+                        ]);
+                        continue;
+                    }
+                }
+                Event::Html(html) if html.trim() == "<!-- pagebreak -->" => {
+                    flush(&mut chunk, &mut output);
+                    output.push_str("<div class=\"page-break\"></div>\n");
+                    continue;
+                }
+                // Review notes are shown in review builds and left out of everything else
+                Event::Html(html) | Event::InlineHtml(html) => {
+                    if let Some(text) = review::note_text(html) {
+                        if notes {
+                            chunk.push(Event::InlineHtml(
+                                format!(
+                                    "<aside class=\"review-note\">{}</aside>",
+                                    escape_html(text)
+                                )
+                                .into(),
+                            ));
+                        }
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            chunk.push(event.clone());
+        }
+
+        flush(&mut chunk, &mut output);
+        if entry_open {
+            output.push_str("</article>\n");
+        }
+        if section_open {
+            output.push_str("</section>\n");
+        }
+
+        output
+    }
+}
+
+/// Escapes text for use in HTML content and attribute values.
+#[must_use]
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Replaces `{{slot}}` placeholders in a single pass, so slot-like text inside the
+/// substituted values is never expanded. Unknown slots are left untouched.
+fn fill_slots(template: &str, slots: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+
+        let name = after[..end].trim();
+        if let Some((_, value)) = slots.iter().find(|(slot, _)| *slot == name) {
+            output.push_str(value);
+        } else {
+            output.push_str(&rest[start..start + 2 + end + 2]);
+        }
+        rest = &after[end + 2..];
+    }
+
+    output.push_str(rest);
+    output
 }
 
 impl RenderEngine for HtmlRenderer {
-    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<()> {
-        let html = Self::generate_html(doc, theme);
+    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<RenderReport> {
+        let html = self.generate_html(doc, theme);
         std::fs::write(output, html)?;
-        Ok(())
+        Ok(RenderReport::default())
+    }
+}
+
+/// Quotes `text` as a CSS string.
+fn css_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\A ")
+        .replace('<', "\\3C ");
+    format!("\"{escaped}\"")
+}
+
+/// CSS `content` value for a header or footer slot, using the page counters.
+fn css_content(template: &str, doc: &Document) -> String {
+    let parts: Vec<String> = page_text::parts(template, doc)
+        .into_iter()
+        .map(|part| match part {
+            Part::Text(text) => css_string(&text),
+            Part::Page => "counter(page)".to_string(),
+            Part::Pages => "counter(pages)".to_string(),
+        })
+        .collect();
+
+    if parts.is_empty() {
+        "\"\"".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_slots_single_pass() {
+        let slots = [
+            ("title", "Jane".to_string()),
+            ("body", "{{title}}".to_string()),
+        ];
+        assert_eq!(
+            fill_slots("<h1>{{title}}</h1>{{ body }}{{unknown}}", &slots),
+            "<h1>Jane</h1>{{title}}{{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_fill_slots_unclosed() {
+        assert_eq!(fill_slots("a {{title", &[]), "a {{title");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">O'Neil & Co</a>"),
+            "&lt;a href=&quot;x&quot;&gt;O&#39;Neil &amp; Co&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_default_template_has_all_slots() {
+        for slot in [
+            "lang",
+            "title",
+            "theme_css",
+            "page_css",
+            "json_ld",
+            "header",
+            "contact",
+            "body",
+        ] {
+            assert!(
+                DEFAULT_TEMPLATE.contains(&format!("{{{{{slot}}}}}")),
+                "Default template is missing slot '{slot}'"
+            );
+        }
     }
 }
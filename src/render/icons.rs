@@ -0,0 +1,192 @@
+//! Icons beside the contact details, drawn from the theme's [`IconSet`]
+//!
+//! The `FontAwesome` sets need their fonts installed where Typst runs. [`available`]
+//! swaps in text labels when they are missing, rather than printing empty boxes.
+
+use crate::constants::icons;
+use crate::render::pdf::typst_fonts;
+use crate::render::template_data::typst_str;
+use crate::themes::layout::IconSet;
+use crate::themes::Theme;
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+/// A contact detail shown with an icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contact {
+    Phone,
+    Email,
+    Website,
+    Github,
+    Linkedin,
+}
+
+impl Contact {
+    /// Label shown instead of an icon by the `none` icon set
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Phone => "Phone",
+            Self::Email => "Email",
+            Self::Website => "Web",
+            Self::Github => "GitHub",
+            Self::Linkedin => "LinkedIn",
+        }
+    }
+
+    fn fa4(self) -> &'static str {
+        match self {
+            Self::Phone => icons::PHONE,
+            Self::Email => icons::EMAIL,
+            Self::Website => icons::WEBSITE,
+            Self::Github => icons::GITHUB,
+            Self::Linkedin => icons::LINKEDIN,
+        }
+    }
+
+    fn fa6(self) -> &'static str {
+        match self {
+            Self::Phone => icons::fa6::PHONE,
+            Self::Email => icons::fa6::EMAIL,
+            Self::Website => icons::fa6::WEBSITE,
+            Self::Github => icons::fa6::GITHUB,
+            Self::Linkedin => icons::fa6::LINKEDIN,
+        }
+    }
+
+    fn svg_body(self) -> &'static str {
+        match self {
+            Self::Phone => icons::svg::PHONE,
+            Self::Email => icons::svg::EMAIL,
+            Self::Website => icons::svg::WEBSITE,
+            Self::Github => icons::svg::GITHUB,
+            Self::Linkedin => icons::svg::LINKEDIN,
+        }
+    }
+}
+
+/// Standalone SVG of a bundled icon, stroked in `stroke` (a hex colour or
+/// `currentColor`).
+#[must_use]
+pub fn svg(contact: Contact, stroke: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 24 24\" fill=\"none\" \
+         stroke=\"{stroke}\" stroke-width=\"2\" stroke-linecap=\"round\" \
+         stroke-linejoin=\"round\" aria-hidden=\"true\">{}</svg>",
+        contact.svg_body()
+    )
+}
+
+/// Typst markup placed before a contact detail: a glyph, a bundled SVG stroked in
+/// `stroke`, or a text label.
+#[must_use]
+pub fn typst_icon(set: IconSet, contact: Contact, stroke: &str) -> String {
+    match set {
+        IconSet::FontAwesome4 => {
+            format!("#text(font: \"{}\")[{}]", icons::FONT_NAME, contact.fa4())
+        }
+        IconSet::FontAwesome6 => match contact {
+            Contact::Github | Contact::Linkedin => {
+                format!(
+                    "#text(font: \"{}\")[{}]",
+                    icons::fa6::BRANDS_FONT,
+                    contact.fa6()
+                )
+            }
+            Contact::Phone | Contact::Email | Contact::Website => format!(
+                "#text(font: \"{}\", weight: {})[{}]",
+                icons::fa6::SOLID_FONT,
+                icons::fa6::SOLID_WEIGHT,
+                contact.fa6()
+            ),
+        },
+        IconSet::Svg => format!(
+            "#box(baseline: 0.15em, image(bytes({}), format: \"svg\", height: 0.9em))",
+            typst_str(&svg(contact, stroke))
+        ),
+        IconSet::None => format!("{}:", contact.label()),
+    }
+}
+
+/// HTML placed before a contact detail, followed by a space, or nothing for the
+/// `FontAwesome` sets, whose fonts the reader may not have.
+#[must_use]
+pub fn html_icon(set: IconSet, contact: Contact) -> String {
+    match set {
+        IconSet::Svg => format!("{} ", svg(contact, "currentColor")),
+        IconSet::None => format!("{}: ", contact.label()),
+        IconSet::FontAwesome4 | IconSet::FontAwesome6 => String::new(),
+    }
+}
+
+/// The icon set to use given the font families installed: `set` itself, or text
+/// labels when one of its fonts is missing.
+#[must_use]
+pub fn fallback(set: IconSet, families: &[String]) -> IconSet {
+    let installed = |font: &&str| families.iter().any(|f| f.eq_ignore_ascii_case(font));
+    if set.font_families().iter().all(installed) {
+        set
+    } else {
+        IconSet::None
+    }
+}
+
+/// Font families Typst can use, or `None` when Typst is not installed. Listed once
+/// per run.
+fn typst_families() -> Option<&'static [String]> {
+    static FAMILIES: OnceLock<Option<Vec<String>>> = OnceLock::new();
+    FAMILIES.get_or_init(|| typst_fonts(false)).as_deref()
+}
+
+/// Returns the theme to compile with Typst: unchanged, or with text labels when the
+/// icon font is not installed, together with a warning saying so.
+pub(crate) fn available(theme: &Theme) -> (Cow<'_, Theme>, Option<String>) {
+    let set = theme.layout.icons;
+    let Some(families) = typst_families() else {
+        return (Cow::Borrowed(theme), None);
+    };
+    if fallback(set, families) == set {
+        return (Cow::Borrowed(theme), None);
+    }
+
+    let mut labelled = theme.clone();
+    labelled.layout.icons = IconSet::None;
+    let warning = format!(
+        "Icon font {} is not installed, so contact details use text labels; \
+         install it or set `icons: svg` for the bundled icons",
+        set.font_families().join(" / ")
+    );
+    (Cow::Owned(labelled), Some(warning))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_to_labels_when_font_missing() {
+        let families = vec!["Inter".to_string(), "fontawesome".to_string()];
+        assert_eq!(
+            fallback(IconSet::FontAwesome4, &families),
+            IconSet::FontAwesome4
+        );
+        assert_eq!(fallback(IconSet::FontAwesome6, &families), IconSet::None);
+        // Sets without a font never fall back
+        assert_eq!(fallback(IconSet::Svg, &[]), IconSet::Svg);
+    }
+
+    #[test]
+    fn test_typst_icon_per_set() {
+        let fa6 = typst_icon(IconSet::FontAwesome6, Contact::Github, "#000000");
+        assert!(fa6.contains(icons::fa6::BRANDS_FONT));
+
+        let svg = typst_icon(IconSet::Svg, Contact::Email, "#2C3E50");
+        assert!(svg.starts_with("#box(baseline: 0.15em, image(bytes(\"<svg"));
+        assert!(svg.contains("stroke=\\\"#2C3E50\\\""));
+
+        assert_eq!(
+            typst_icon(IconSet::None, Contact::Phone, "#000000"),
+            "Phone:"
+        );
+    }
+}
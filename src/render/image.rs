@@ -0,0 +1,181 @@
+use crate::parser::Document;
+use crate::render::icons;
+use crate::render::pdf::{compile_typst, layout_warnings, PdfRenderer};
+use crate::render::{RenderEngine, RenderOptions, RenderReport};
+use crate::themes::Theme;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+/// Placeholder for the page number in image output paths
+pub const PAGE_PLACEHOLDER: &str = "{page}";
+
+/// Image formats supported by Typst's page exporters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    /// Value passed to `typst compile --format`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// Renders every page of a document as a separate PNG or SVG image.
+pub struct ImageRenderer {
+    pdf: PdfRenderer,
+    format: ImageFormat,
+    dpi: u32,
+}
+
+impl ImageRenderer {
+    /// Creates a new image renderer with optional custom template.
+    ///
+    /// The resolution in `options` only applies to PNG output; SVG is resolution
+    /// independent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template file cannot be read.
+    pub fn new(
+        template_path: Option<&Path>,
+        format: ImageFormat,
+        options: &RenderOptions,
+    ) -> Result<Self> {
+        Ok(Self {
+            pdf: PdfRenderer::with_options(template_path, options)?,
+            format,
+            dpi: options.dpi,
+        })
+    }
+}
+
+/// Converts an output path into a per-page pattern understood by Typst.
+///
+/// `{page}` is replaced by Typst's `{p}`. Paths without the placeholder get `-{p}`
+/// appended to the file stem, so `cv.png` becomes `cv-1.png`, `cv-2.png`, ...
+#[must_use]
+pub fn page_pattern(output: &Path) -> PathBuf {
+    let path = output.to_string_lossy();
+    if path.contains(PAGE_PLACEHOLDER) {
+        return PathBuf::from(path.replace(PAGE_PLACEHOLDER, "{p}"));
+    }
+
+    let stem = output
+        .file_stem()
+        .map_or_else(|| "page".into(), |s| s.to_string_lossy());
+    let name = output.extension().map_or_else(
+        || format!("{stem}-{{p}}"),
+        |ext| format!("{stem}-{{p}}.{}", ext.to_string_lossy()),
+    );
+    output.with_file_name(name)
+}
+
+/// Returns the path that `page` (1-based) of an image render is written to.
+#[must_use]
+pub fn page_path(output: &Path, page: usize) -> PathBuf {
+    PathBuf::from(
+        page_pattern(output)
+            .to_string_lossy()
+            .replace("{p}", &page.to_string()),
+    )
+}
+
+/// Number of page images a render to `output` wrote.
+#[must_use]
+pub fn count_pages(output: &Path) -> Option<usize> {
+    let pages = (1..usize::MAX)
+        .take_while(|&page| page_path(output, page).exists())
+        .count();
+    (pages > 0).then_some(pages)
+}
+
+/// Deletes the page images an earlier render to `output` left, so a shorter
+/// document neither keeps its old last pages nor counts them.
+///
+/// # Errors
+///
+/// Returns an error if an old page image cannot be deleted.
+pub fn remove_pages(output: &Path) -> Result<()> {
+    for page in 1.. {
+        let path = page_path(output, page);
+        if !path.exists() {
+            break;
+        }
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+impl RenderEngine for ImageRenderer {
+    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<RenderReport> {
+        let (theme, icon_warning) = icons::available(theme);
+        let source = self.pdf.generate_typst_source(doc, &theme);
+        let dpi = self.dpi.to_string();
+
+        let mut args = vec!["--format", self.format.as_str()];
+        if self.format == ImageFormat::Png {
+            args.extend(["--ppi", dpi.as_str()]);
+        }
+
+        remove_pages(output)?;
+        let mut warnings = compile_typst(&source, &page_pattern(output), &args)?;
+        warnings.extend(icon_warning);
+        warnings.extend(layout_warnings(&source));
+        Ok(RenderReport {
+            pages: count_pages(output),
+            warnings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_pattern_appends_page_number() {
+        assert_eq!(
+            page_pattern(Path::new("out/cv.png")),
+            PathBuf::from("out/cv-{p}.png")
+        );
+        assert_eq!(page_pattern(Path::new("cv")), PathBuf::from("cv-{p}"));
+    }
+
+    #[test]
+    fn test_page_pattern_uses_placeholder() {
+        assert_eq!(
+            page_pattern(Path::new("previews/{page}/cv.svg")),
+            PathBuf::from("previews/{p}/cv.svg")
+        );
+    }
+
+    #[test]
+    fn test_page_path() {
+        assert_eq!(page_path(Path::new("cv.png"), 1), PathBuf::from("cv-1.png"));
+        assert_eq!(
+            page_path(Path::new("cv-page{page}.svg"), 2),
+            PathBuf::from("cv-page2.svg")
+        );
+    }
+
+    #[test]
+    fn test_remove_pages() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let output = dir.path().join("cv.png");
+        for page in 1..=3 {
+            std::fs::write(page_path(&output, page), "").expect("Failed to write page");
+        }
+        assert_eq!(count_pages(&output), Some(3));
+
+        remove_pages(&output).expect("Pages removed");
+        assert_eq!(count_pages(&output), None);
+        assert!(remove_pages(&output).is_ok());
+    }
+}
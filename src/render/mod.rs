@@ -1,12 +1,63 @@
 pub mod docx;
 pub mod html;
+pub mod icons;
+pub mod image;
+pub mod page_text;
 pub mod pdf;
+pub mod review;
+pub mod template_data;
+pub mod text;
+pub mod typst;
+pub mod typst_ir;
 
 use crate::error::CvError;
 use crate::parser::Document;
 use crate::themes::Theme;
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Output formats accepted by `Renderer::new`
+pub const SUPPORTED_FORMATS: &[&str] = &["pdf", "docx", "html", "typst", "png", "svg", "txt"];
+
+/// Default resolution for PNG output, in pixels per inch
+pub const DEFAULT_DPI: u32 = 144;
+
+/// Format-specific settings that don't fit the common renderer constructor
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    /// Resolution for raster image output
+    pub dpi: u32,
+    /// Produce archival PDF/A output
+    pub pdf_a: bool,
+    /// Watermark and review notes for a draft build (PDF, image, Typst and HTML)
+    pub review: Option<review::Review>,
+    /// Render with the greyscale, high-contrast variant of the theme (all formats)
+    pub print_safe: bool,
+    /// Check the layout with a second Typst pass, for warnings such as oversized
+    /// entries (PDF); renders that only count pages skip it
+    pub layout_warnings: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            dpi: DEFAULT_DPI,
+            pdf_a: false,
+            review: None,
+            print_safe: false,
+            layout_warnings: true,
+        }
+    }
+}
+
+/// What a render produced besides the output file
+#[derive(Debug, Clone, Default)]
+pub struct RenderReport {
+    /// Number of pages, for formats that have pages
+    pub pages: Option<usize>,
+    /// Non-fatal problems reported while rendering, such as missing fonts
+    pub warnings: Vec<String>,
+}
 
 /// Utility function to load an optional template file
 ///
@@ -21,17 +72,43 @@ pub fn load_template(template_path: Option<&Path>) -> Result<Option<String>> {
     }
 }
 
+/// Returns the file extension used for outputs of the given format.
+#[must_use]
+pub fn output_extension(format: &str) -> &'static str {
+    match format {
+        "docx" => "docx",
+        "html" => "html",
+        "typst" => "typ",
+        "png" => "png",
+        "svg" => "svg",
+        "txt" => "txt",
+        _ => "pdf",
+    }
+}
+
+/// Returns the file a render of `format` to `output` actually produces first.
+///
+/// Image formats write one file per page, so this is the first page.
+#[must_use]
+pub fn primary_output(format: &str, output: &Path) -> PathBuf {
+    match format {
+        "png" | "svg" => image::page_path(output, 1),
+        _ => output.to_path_buf(),
+    }
+}
+
 pub trait RenderEngine {
     /// Renders a document to the specified output path.
     ///
     /// # Errors
     ///
     /// Returns an error if rendering fails.
-    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<()>;
+    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<RenderReport>;
 }
 
 pub struct Renderer {
     engine: Box<dyn RenderEngine>,
+    print_safe: bool,
 }
 
 impl Renderer {
@@ -40,11 +117,37 @@ impl Renderer {
     /// # Errors
     ///
     /// Returns an error if the format is unsupported or template cannot be loaded.
+    #[allow(dead_code)] // `allow(dead_code)` exception
     pub fn new(format: &str, template: Option<&Path>) -> Result<Self> {
+        Self::with_options(format, template, &RenderOptions::default())
+    }
+
+    /// Creates a new renderer for the specified format with explicit render options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format is unsupported or template cannot be loaded.
+    pub fn with_options(
+        format: &str,
+        template: Option<&Path>,
+        options: &RenderOptions,
+    ) -> Result<Self> {
         let engine: Box<dyn RenderEngine> = match format {
-            "pdf" => Box::new(pdf::PdfRenderer::new(template)?),
+            "pdf" => Box::new(pdf::PdfRenderer::with_options(template, options)?),
             "docx" => Box::new(docx::DocxRenderer::new(template)?),
-            "html" => Box::new(html::HtmlRenderer::new(template)?),
+            "html" => Box::new(html::HtmlRenderer::with_options(template, options)?),
+            "typst" => Box::new(typst::TypstRenderer::with_options(template, options)?),
+            "txt" => Box::new(text::TextRenderer),
+            "png" => Box::new(image::ImageRenderer::new(
+                template,
+                image::ImageFormat::Png,
+                options,
+            )?),
+            "svg" => Box::new(image::ImageRenderer::new(
+                template,
+                image::ImageFormat::Svg,
+                options,
+            )?),
             _ => {
                 return Err(CvError::InvalidFormat {
                     format: format.to_string(),
@@ -53,15 +156,22 @@ impl Renderer {
             }
         };
 
-        Ok(Self { engine })
+        Ok(Self {
+            engine,
+            print_safe: options.print_safe,
+        })
     }
 
-    /// Renders a document using the configured render engine.
+    /// Renders a document using the configured render engine, reporting the page
+    /// count and any warnings.
     ///
     /// # Errors
     ///
     /// Returns an error if rendering fails.
-    pub fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<()> {
+    pub fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<RenderReport> {
+        if self.print_safe {
+            return self.engine.render(doc, &theme.print_safe(), output);
+        }
         self.engine.render(doc, theme, output)
     }
 }
@@ -0,0 +1,124 @@
+//! Running headers and footers from the frontmatter `header` and `footer` options
+//!
+//! Both PDF and HTML output split each slot into literal text and page-number
+//! parts; `{name}` and `{date}` are known up front, while `{page}` and `{pages}`
+//! are left to Typst counters or CSS `counter(page)` / `counter(pages)`.
+
+use crate::config::PageText;
+use crate::parser::Document;
+use crate::render::pdf::creation_date;
+
+/// Placeholders recognised in header and footer text
+pub const PLACEHOLDERS: &[&str] = &["{name}", "{page}", "{pages}", "{date}"];
+
+/// A piece of header or footer text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Part {
+    Text(String),
+    /// Current page number
+    Page,
+    /// Total number of pages
+    Pages,
+}
+
+/// Splits `template` into parts, filling in `{name}` and `{date}` from `doc`.
+///
+/// Unknown placeholders are kept as written.
+#[must_use]
+pub fn parts(template: &str, doc: &Document) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(placeholder) = PLACEHOLDERS.iter().copied().find(|p| rest.starts_with(p)) else {
+            text.push('{');
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[placeholder.len()..];
+
+        match placeholder {
+            "{name}" => text.push_str(&doc.metadata.name),
+            "{date}" => text.push_str(&date_text(doc)),
+            counter => {
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                parts.push(if counter == "{page}" {
+                    Part::Page
+                } else {
+                    Part::Pages
+                });
+            }
+        }
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    parts
+}
+
+/// The `{date}` value: the frontmatter `date`, or today.
+#[must_use]
+pub fn date_text(doc: &Document) -> String {
+    doc.metadata
+        .date
+        .clone()
+        .unwrap_or_else(|| creation_date().format("%-d %B %Y").to_string())
+}
+
+/// The left, center and right slots of `page_text`, in order.
+#[must_use]
+pub fn slots(page_text: &PageText) -> [(&'static str, Option<&str>); 3] {
+    [
+        ("left", page_text.left.as_deref()),
+        ("center", page_text.center.as_deref()),
+        ("right", page_text.right.as_deref()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn doc() -> Document {
+        Document::from_string(
+            "---\nname: Jane Doe\nemail: jane@example.com\ndate: 1 May 2025\n---\n",
+            Path::new("cv.md"),
+        )
+        .expect("Valid document")
+    }
+
+    #[test]
+    fn test_parts_fill_placeholders() {
+        assert_eq!(
+            parts("{name} - Page {page} of {pages}", &doc()),
+            vec![
+                Part::Text("Jane Doe - Page ".to_string()),
+                Part::Page,
+                Part::Text(" of ".to_string()),
+                Part::Pages,
+            ]
+        );
+        assert_eq!(
+            parts("Confidential {date}", &doc()),
+            vec![Part::Text("Confidential 1 May 2025".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_kept() {
+        assert_eq!(
+            parts("{company} {", &doc()),
+            vec![Part::Text("{company} {".to_string())]
+        );
+        assert!(parts("", &doc()).is_empty());
+    }
+}
@@ -1,23 +1,43 @@
 use crate::config::RecipientInfo;
-use crate::constants::{icons, layout};
+use crate::constants::layout;
+use crate::constants::layout::layout_styles;
 use crate::parser::Document;
-use crate::render::{load_template, RenderEngine};
+use crate::render::icons::{self, Contact};
+use crate::render::page_text::{self, Part};
+use crate::render::review::{self, Review};
+use crate::render::template_data::typst_str;
+use crate::render::typst_ir::{self, Builder, Node};
+use crate::render::{load_template, template_data, RenderEngine, RenderOptions, RenderReport};
+use crate::themes::font::typst_weight;
+use crate::themes::layout::{EntryLayout, HeaderStyle, SeparatorStyle};
 use crate::themes::Theme;
 use anyhow::Result;
-use chrono::Local;
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use pulldown_cmark::HeadingLevel;
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tempfile::NamedTempFile;
 
+/// PDF standard passed to `typst compile --pdf-standard` for archival output
+pub const PDF_A_STANDARD: &str = "a-2b";
+
+/// Label of the metadata the built-in layout places beside an entry taller than a page
+pub const OVERSIZED_LABEL: &str = "cv-oversized";
+
 pub struct PdfRenderer {
     template: Option<String>,
+    pdf_a: bool,
+    review: Option<Review>,
+    layout_warnings: bool,
 }
 
 struct RenderContext {
     list_depth: usize,
     in_heading: bool,
     heading_level: pulldown_cmark::HeadingLevel,
+    /// Plain text of the current heading, used for the PDF outline
+    heading_text: String,
 }
 
 impl RenderContext {
@@ -26,6 +46,7 @@ impl RenderContext {
             list_depth: 0,
             in_heading: false,
             heading_level: pulldown_cmark::HeadingLevel::H1,
+            heading_text: String::new(),
         }
     }
 }
@@ -36,9 +57,24 @@ impl PdfRenderer {
     /// # Errors
     ///
     /// Returns an error if the template file cannot be read.
+    #[allow(dead_code)] // `allow(dead_code)` exception
     pub fn new(template_path: Option<&Path>) -> Result<Self> {
+        Self::with_options(template_path, &RenderOptions::default())
+    }
+
+    /// Creates a new PDF renderer with optional custom template and render options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template file cannot be read.
+    pub fn with_options(template_path: Option<&Path>, options: &RenderOptions) -> Result<Self> {
         let template = load_template(template_path)?;
-        Ok(Self { template })
+        Ok(Self {
+            template,
+            pdf_a: options.pdf_a,
+            review: options.review.clone(),
+            layout_warnings: options.layout_warnings,
+        })
     }
 
     /// Exposed for testing purposes only
@@ -49,86 +85,213 @@ impl PdfRenderer {
         self.generate_typst_source(doc, theme)
     }
 
-    fn generate_typst_source(&self, doc: &Document, theme: &Theme) -> String {
+    pub(crate) fn generate_typst_source(&self, doc: &Document, theme: &Theme) -> String {
+        let notes = self.review.as_ref().is_some_and(|review| review.notes);
+
         if let Some(template) = &self.template {
-            // Custom template - just use it as-is
-            return template.clone();
+            // Custom template - prepend the document data it renders from
+            let mut source = String::new();
+            template_data::write_template_data(&mut source, doc, theme, |markdown| {
+                Self::render_content(markdown, theme, notes)
+            });
+            self.add_review(&mut source, theme);
+            source.push_str(template);
+            return source;
         }
 
         // Generate a complete Typst document without complex template functions
         let mut source = String::new();
 
         // Add document setup
-        Self::add_document_setup(&mut source, doc, theme);
+        let right_margin = if notes {
+            layout::margins::NOTES_RIGHT
+        } else {
+            layout::margins::RIGHT
+        };
+        Self::add_document_setup(&mut source, doc, theme, right_margin);
+        self.add_review(&mut source, theme);
 
         // Check if this is a cover letter
         let is_cover_letter = doc.metadata.recipient.is_some();
 
         // Add header section
         if is_cover_letter {
-            Self::add_cover_letter_header(&mut source, doc);
+            Self::add_cover_letter_header(&mut source, doc, theme);
         } else {
-            Self::add_cv_header(&mut source, doc);
+            Self::add_cv_header(&mut source, doc, theme);
         }
 
         // Add recipient information for cover letters
         if let Some(recipient) = &doc.metadata.recipient {
-            Self::add_recipient_section(&mut source, recipient, doc.metadata.subject.as_ref());
+            Self::add_recipient_section(
+                &mut source,
+                recipient,
+                doc.metadata.subject.as_ref(),
+                theme,
+            );
         }
 
         // Body content - convert markdown to Typst
         let _ = writeln!(source, "// Content");
-        let mut typst_content = String::new();
-        Self::render_markdown_as_typst(&doc.content, &mut typst_content, theme);
-
-        // Post-process to wrap H2 sections in non-breakable blocks
-        let processed_content = Self::wrap_h2_sections(&typst_content);
-        source.push_str(&processed_content);
+        source.push_str(&Self::render_content(&doc.content, theme, notes));
 
         // For cover letters, add a signature section with contact info
         if is_cover_letter {
-            Self::add_letter_signature(&mut source, doc);
+            Self::add_letter_signature(&mut source, doc, theme);
         }
 
         source
     }
 
-    fn add_document_setup(source: &mut String, doc: &Document, theme: &Theme) {
-        // Document setup
+    /// Converts markdown body content to Typst markup, keeping job entries together.
+    /// Review notes become margin notes when `notes` is set and are dropped otherwise.
+    fn render_content(content: &str, theme: &Theme, notes: bool) -> String {
+        typst_ir::to_typst(&Self::render_markdown_as_typst(content, theme, notes))
+    }
+
+    fn add_document_setup(source: &mut String, doc: &Document, theme: &Theme, right_margin: &str) {
+        let meta = &doc.metadata;
+        let name = typst_str(&meta.name);
+
+        // PDF metadata - Typst writes these to the document info and XMP
+        let description = meta.subject.clone().unwrap_or_else(|| {
+            if meta.recipient.is_some() {
+                format!("Cover letter from {}", meta.name)
+            } else {
+                format!("Curriculum vitae of {}", meta.name)
+            }
+        });
+        // A trailing comma keeps a single keyword an array
+        let keywords: String = meta.keywords.iter().map(|k| typst_str(k) + ",").collect();
+        let date = creation_date();
         let _ = writeln!(
             source,
-            "#set document(title: \"{}\", author: \"{}\")",
-            doc.metadata.name, doc.metadata.name
+            "#set document(title: {name}, author: {name}, description: {}, keywords: ({}), date: datetime(year: {}, month: {}, day: {}))",
+            typst_str(&description),
+            keywords,
+            date.year(),
+            date.month(),
+            date.day()
         );
+
+        let (lang, region) = text_language(meta.lang.as_deref());
+        match region {
+            Some(region) => {
+                let _ = writeln!(source, "#set text(lang: \"{lang}\", region: \"{region}\")");
+            }
+            None => {
+                let _ = writeln!(source, "#set text(lang: \"{lang}\")");
+            }
+        }
+
         let _ = writeln!(
             source,
-            "#set page(paper: \"a4\", margin: (top: {}, bottom: {}, left: {}, right: {}))",
+            "#set page(paper: \"a4\", margin: (top: {}, bottom: {}, left: {}, right: {right_margin}))",
             layout::margins::TOP,
             layout::margins::BOTTOM,
             layout::margins::LEFT,
-            layout::margins::RIGHT
         );
+        Self::add_page_text(source, doc, theme);
 
-        // Font configuration - use header font from theme
-        let font_family = &theme.font.header.family;
-
-        // Set default text properties
+        // Body font, weight and line spacing; headings switch to the header font
+        let body = &theme.font.body;
         let _ = writeln!(
             source,
-            "#set text(font: \"{}\", size: {}, fill: {})",
-            font_family,
-            layout::font_sizes::NORMAL,
+            "#set text({}, size: {}, weight: {}, fill: {})",
+            body.typst_font_args(),
+            body.size_normal,
+            typst_weight(body.weight_regular),
             theme.color.to_typst_rgb("text")
         );
+        let _ = writeln!(source, "#set par(leading: {})", body.typst_leading());
+        let _ = writeln!(
+            source,
+            "#set strong(delta: {})",
+            body.weight_bold.saturating_sub(body.weight_regular)
+        );
+
+        // Mark entries kept together that can't fit on a page, for `layout_warnings`
+        let _ = writeln!(
+            source,
+            "#show block.where(breakable: false): it => context {{
+  let size = measure(block(width: page.width - {} - {}, it))
+  if size.height > page.height - {} - {} [#metadata(here().page()) <{OVERSIZED_LABEL}>]
+  it
+}}",
+            layout::margins::LEFT,
+            right_margin,
+            layout::margins::TOP,
+            layout::margins::BOTTOM
+        );
+    }
+
+    /// Diagonal watermark and footer stamp of a review build, drawn behind the page
+    /// content so they never move the layout.
+    fn add_review(&self, source: &mut String, theme: &Theme) {
+        let Some(review) = &self.review else {
+            return;
+        };
+        let muted = theme.color.to_typst_rgb("muted");
+        let _ = writeln!(
+            source,
+            "#set page(background: {{
+  place(center + horizon, rotate(-45deg, text(size: {}, weight: \"bold\", fill: {muted}.transparentize({}), {})))
+  place(bottom + center, dy: -{}, text(size: {}, fill: {muted}, {}))
+}})",
+            layout::review::WATERMARK_SIZE,
+            layout::review::WATERMARK_TRANSPARENCY,
+            typst_str(&review.watermark),
+            layout::review::STAMP_OFFSET,
+            layout::review::NOTE_SIZE,
+            typst_str(&review.footer())
+        );
+    }
+
+    /// Running header and footer from the frontmatter, as a `#set page(...)` rule.
+    fn add_page_text(source: &mut String, doc: &Document, theme: &Theme) {
+        let meta = &doc.metadata;
+        let mut fields = Vec::new();
+
+        for (field, page_text) in [("header", &meta.header), ("footer", &meta.footer)] {
+            let Some(page_text) = page_text else {
+                continue;
+            };
+            let cells: Vec<String> = page_text::slots(page_text)
+                .iter()
+                .map(|(_, text)| page_text_expr(text.unwrap_or_default(), doc))
+                .collect();
+            let grid = format!(
+                "grid(columns: (1fr, auto, 1fr), align: (left, center, right), {})",
+                cells.join(", ")
+            );
+            let body = if page_text.first_page {
+                grid
+            } else {
+                format!("if counter(page).get().first() > 1 {{ {grid} }}")
+            };
+            fields.push(format!(
+                "{field}: context {{\n  set text(size: {}, fill: {})\n  {body}\n}}",
+                theme.font.body.size_small,
+                theme.color.to_typst_rgb("muted")
+            ));
+        }
+
+        if !fields.is_empty() {
+            let _ = writeln!(source, "#set page({})", fields.join(", "));
+        }
     }
 
-    fn add_cover_letter_header(source: &mut String, doc: &Document) {
+    fn add_cover_letter_header(source: &mut String, doc: &Document, theme: &Theme) {
         // Cover letter header - simpler, more formal
+        let header = &theme.font.header;
+        let size_normal = &theme.font.body.size_normal;
         let _ = writeln!(source, "#align(right)[");
         let _ = writeln!(
             source,
-            "  #text(size: {}, weight: \"bold\")[{}]",
-            layout::font_sizes::SUBSECTION,
+            "  #text({}, size: {}, weight: {})[{}]",
+            header.typst_font_args(),
+            header.size_subsection,
+            typst_weight(header.weight_bold),
             doc.metadata.name
         );
 
@@ -137,52 +300,64 @@ impl PdfRenderer {
 
         // Location
         if let Some(location) = &doc.metadata.location {
-            let _ = writeln!(
-                source,
-                "  #text(size: {})[{location}]",
-                layout::font_sizes::NORMAL
-            );
+            let _ = writeln!(source, "  #text(size: {size_normal})[{location}]");
             let _ = writeln!(source, "  #v({})", layout::spacing::EXTRA_TINY);
         }
 
         // Phone
         if let Some(phone) = &doc.metadata.phone {
-            let _ = writeln!(
-                source,
-                "  #text(size: {})[{phone}]",
-                layout::font_sizes::NORMAL
-            );
+            let _ = writeln!(source, "  #text(size: {size_normal})[{phone}]");
             let _ = writeln!(source, "  #v({})", layout::spacing::EXTRA_TINY);
         }
 
-        // Email
-        let escaped_email = doc.metadata.email.replace('@', "\\@");
-        let _ = writeln!(
-            source,
-            "  #text(size: {})[{escaped_email}]",
-            layout::font_sizes::NORMAL
-        );
+        // Email, absent from anonymized documents
+        if !doc.metadata.email.is_empty() {
+            let escaped_email = doc.metadata.email.replace('@', "\\@");
+            let _ = writeln!(source, "  #text(size: {size_normal})[{escaped_email}]");
+        }
 
         // Website (optional for letters)
         if let Some(website) = &doc.metadata.website {
             let _ = writeln!(source, "  #v({})", layout::spacing::EXTRA_TINY);
             let _ = writeln!(
                 source,
-                "  #text(size: {})[#link(\"{website}\")[{website}]]",
-                layout::font_sizes::NORMAL
+                "  #text(size: {size_normal})[#link(\"{website}\")[{website}]]"
             );
         }
 
         let _ = writeln!(source, "]");
     }
 
-    fn add_cv_header(source: &mut String, doc: &Document) {
-        // CV header - original centered layout with icons
-        let _ = writeln!(source, "#align(center)[");
+    fn add_cv_header(source: &mut String, doc: &Document, theme: &Theme) {
+        // CV header - name, location and contact line with icons, arranged by the
+        // layout theme
+        let header = &theme.font.header;
+        let body = &theme.font.body;
+        let banner = theme.layout.header_style == HeaderStyle::Banner;
+        if banner {
+            let _ = writeln!(
+                source,
+                "#block(width: 100%, fill: {}, inset: {})[",
+                theme.color.to_typst_rgb("primary"),
+                layout_styles::BANNER_INSET
+            );
+            let _ = writeln!(
+                source,
+                "#set text(fill: {})",
+                theme.color.to_typst_rgb("background")
+            );
+        }
+        let align = match theme.layout.header_style {
+            HeaderStyle::Left => "left",
+            HeaderStyle::Centered | HeaderStyle::Banner => "center",
+        };
+        let _ = writeln!(source, "#align({align})[");
         let _ = writeln!(
             source,
-            "  #text(size: {}, weight: \"bold\")[{}]",
-            layout::font_sizes::TITLE,
+            "  #text({}, size: {}, weight: {})[{}]",
+            header.typst_font_args(),
+            header.size_name,
+            typst_weight(header.weight_bold),
             doc.metadata.name
         );
 
@@ -192,53 +367,49 @@ impl PdfRenderer {
             let _ = writeln!(
                 source,
                 "  #text(size: {}, style: \"italic\")[{location}]",
-                layout::font_sizes::NORMAL
+                body.size_normal
             );
         }
 
         let _ = writeln!(source, "  #v({})", layout::spacing::TINY);
 
-        // Contact info - all on one line with icons
-        let _ = writeln!(source, "  #text(size: {})[", layout::font_sizes::SMALL);
+        // Contact info - all on one line with the theme's icons
+        let _ = writeln!(source, "  #text(size: {})[", body.size_small);
+        let stroke = if banner {
+            &theme.color.background
+        } else {
+            &theme.color.text
+        };
+        let icon = |contact| icons::typst_icon(theme.layout.icons, contact, stroke);
         let mut contact_parts = vec![];
 
-        // Phone with FontAwesome icon
         if let Some(phone) = &doc.metadata.phone {
-            contact_parts.push(format!(
-                "#text(font: \"{}\")[{}] {phone}",
-                icons::FONT_NAME,
-                icons::PHONE
-            ));
+            contact_parts.push(format!("{} {phone}", icon(Contact::Phone)));
         }
 
-        // Email with FontAwesome icon
-        let escaped_email = doc.metadata.email.replace('@', "\\@");
-        contact_parts.push(format!(
-            "#text(font: \"{}\")[{}] {escaped_email}",
-            icons::FONT_NAME,
-            icons::EMAIL
-        ));
+        if !doc.metadata.email.is_empty() {
+            let escaped_email = doc.metadata.email.replace('@', "\\@");
+            contact_parts.push(format!("{} {escaped_email}", icon(Contact::Email)));
+        }
 
-        // Website with FontAwesome icon
         if let Some(website) = &doc.metadata.website {
             contact_parts.push(format!(
-                "#text(font: \"{}\")[{}] #link(\"{website}\")[{website}]",
-                icons::FONT_NAME,
-                icons::WEBSITE
+                "{} #link(\"{website}\")[{website}]",
+                icon(Contact::Website)
             ));
         }
 
-        // GitHub with FontAwesome icon
         if let Some(github) = &doc.metadata.github {
             contact_parts.push(format!(
-                "#text(font: \"{}\")[{}] #link(\"https://github.com/{github}\")[github.com/{github}]", icons::FONT_NAME, icons::GITHUB
+                "{} #link(\"https://github.com/{github}\")[github.com/{github}]",
+                icon(Contact::Github)
             ));
         }
 
-        // LinkedIn with FontAwesome icon
         if let Some(linkedin) = &doc.metadata.linkedin {
             contact_parts.push(format!(
-                "#text(font: \"{}\")[{}] #link(\"https://linkedin.com/in/{linkedin}\")[linkedin.com/in/{linkedin}]", icons::FONT_NAME, icons::LINKEDIN
+                "{} #link(\"https://linkedin.com/in/{linkedin}\")[linkedin.com/in/{linkedin}]",
+                icon(Contact::Linkedin)
             ));
         }
 
@@ -246,6 +417,9 @@ impl PdfRenderer {
         let _ = writeln!(source, "  ]");
 
         let _ = writeln!(source, "]");
+        if banner {
+            let _ = writeln!(source, "]");
+        }
         let _ = writeln!(source, "#v({})", layout::spacing::SMALL);
     }
 
@@ -253,30 +427,32 @@ impl PdfRenderer {
         source: &mut String,
         recipient: &RecipientInfo,
         subject: Option<&String>,
+        theme: &Theme,
     ) {
+        let size_normal = &theme.font.body.size_normal;
+        let bold = typst_weight(theme.font.body.weight_bold);
         // This is a cover letter - adjust formatting accordingly
         let _ = writeln!(source, "// Cover Letter Formatting");
 
         // Add extra space after header for letter format
         let _ = writeln!(source, "#v({})", layout::spacing::LARGE);
 
-        // Date aligned to the left (standard business letter format) - always use today's date
-        // Use locale-aware formatting
-        let today = Local::now();
+        // Date aligned to the left (standard business letter format) - today's date,
+        // or the fixed SOURCE_DATE_EPOCH date for reproducible builds
+        let today = creation_date();
         // Format: "15 December 2024" for international compatibility
         let formatted_date = today.format("%-d %B %Y").to_string();
         let _ = writeln!(source, "#align(left)[");
         let _ = writeln!(
             source,
-            "  #text(size: {}, weight: \"bold\")[{formatted_date}]",
-            layout::font_sizes::NORMAL
+            "  #text(size: {size_normal}, weight: {bold})[{formatted_date}]"
         );
         let _ = writeln!(source, "]");
         let _ = writeln!(source, "#v({})", layout::spacing::MEDIUM);
 
         // Recipient information on the left
         let _ = writeln!(source, "#align(left)[");
-        let _ = writeln!(source, "  #text(size: {})[", layout::font_sizes::NORMAL);
+        let _ = writeln!(source, "  #text(size: {size_normal})[");
 
         // Handle optional recipient name
         let mut has_content = false;
@@ -297,7 +473,7 @@ impl PdfRenderer {
             if has_content {
                 let _ = writeln!(source, "    #linebreak()");
             }
-            let _ = writeln!(source, "    #text(weight: \"bold\")[{company}]");
+            let _ = writeln!(source, "    #text(weight: {bold})[{company}]");
             has_content = true;
         }
 
@@ -325,8 +501,7 @@ impl PdfRenderer {
         if let Some(subject) = subject {
             let _ = writeln!(
                 source,
-                "#text(size: {}, weight: \"bold\")[Subject: {subject}]",
-                layout::font_sizes::NORMAL
+                "#text(size: {size_normal}, weight: {bold})[Subject: {subject}]"
             );
             let _ = writeln!(source, "#v({})", layout::spacing::MEDIUM);
         }
@@ -335,42 +510,52 @@ impl PdfRenderer {
         let _ = writeln!(source, "#v({})", layout::spacing::SMALL);
     }
 
-    fn add_letter_signature(source: &mut String, doc: &Document) {
+    fn add_letter_signature(source: &mut String, doc: &Document, theme: &Theme) {
         // Add signature section at the end of the letter
         let _ = writeln!(source, "\n#v({})", layout::spacing::MEDIUM);
 
         // Name in bold
-        let _ = writeln!(source, "#text(weight: \"bold\")[{}]", doc.metadata.name);
-        let _ = writeln!(source, "#v({})", layout::spacing::SMALL);
-
-        // Contact info on separate lines with FontAwesome icons
-        let escaped_email = doc.metadata.email.replace('@', "\\@");
         let _ = writeln!(
             source,
-            "#text(font: \"{}\")[{}] {escaped_email}",
-            icons::FONT_NAME,
-            icons::EMAIL
+            "#text(weight: {})[{}]",
+            typst_weight(theme.font.body.weight_bold),
+            doc.metadata.name
         );
+        let _ = writeln!(source, "#v({})", layout::spacing::SMALL);
+
+        // Contact info on separate lines with the theme's icons
+        let icon = |contact| icons::typst_icon(theme.layout.icons, contact, &theme.color.text);
+        if !doc.metadata.email.is_empty() {
+            let escaped_email = doc.metadata.email.replace('@', "\\@");
+            let _ = writeln!(source, "{} {escaped_email}", icon(Contact::Email));
+        }
 
         if let Some(linkedin) = &doc.metadata.linkedin {
-            let _ = writeln!(source, "#text(font: \"{}\")[{}] #link(\"https://linkedin.com/in/{linkedin}\")[linkedin.com/in/{linkedin}]", icons::FONT_NAME, icons::LINKEDIN);
+            let _ = writeln!(
+                source,
+                "{} #link(\"https://linkedin.com/in/{linkedin}\")[linkedin.com/in/{linkedin}]",
+                icon(Contact::Linkedin)
+            );
         }
 
         if let Some(github) = &doc.metadata.github {
-            let _ = writeln!(source, "#text(font: \"{}\")[{}] #link(\"https://github.com/{github}\")[github.com/{github}]", icons::FONT_NAME, icons::GITHUB);
+            let _ = writeln!(
+                source,
+                "{} #link(\"https://github.com/{github}\")[github.com/{github}]",
+                icon(Contact::Github)
+            );
         }
 
         if let Some(website) = &doc.metadata.website {
             let _ = writeln!(
                 source,
-                "#text(font: \"{}\")[{}] #link(\"{website}\")[{website}]",
-                icons::FONT_NAME,
-                icons::WEBSITE
+                "{} #link(\"{website}\")[{website}]",
+                icon(Contact::Website)
             );
         }
     }
 
-    fn render_markdown_as_typst(content: &str, output: &mut String, theme: &Theme) {
+    fn render_markdown_as_typst(content: &str, theme: &Theme, notes: bool) -> Vec<Node> {
         use crate::constants::markdown_options;
         use pulldown_cmark::{Event, Parser};
 
@@ -381,30 +566,33 @@ impl PdfRenderer {
         let options = markdown_options();
         let parser = Parser::new_ext(&content_with_pagebreaks, options);
         let mut render_ctx = RenderContext::new();
+        let mut builder = Builder::new();
 
-        for event in parser {
+        for event in review::join_html_blocks(parser) {
             match event {
-                Event::Start(tag) => Self::handle_start_tag(tag, output, &mut render_ctx, theme),
-                Event::End(tag) => Self::handle_end_tag(tag, output, theme, &mut render_ctx),
-                Event::Text(text) => Self::handle_text(&text, output, &render_ctx, theme),
-                Event::Code(code) => {
-                    let _ = write!(output, "`{code}`");
+                Event::Start(tag) => {
+                    Self::handle_start_tag(tag, &mut builder, &mut render_ctx, theme);
                 }
-                Event::SoftBreak => {
-                    let _ = write!(output, " ");
-                }
-                Event::HardBreak => {
-                    let _ = writeln!(output);
-                }
-                Event::Html(html) => {
-                    // Handle HTML comments that might contain pagebreak markers
-                    if html.trim() == "<!-- pagebreak -->" {
-                        let _ = writeln!(output, "\n#pagebreak()\n");
+                Event::End(tag) => Self::handle_end_tag(tag, &mut builder, theme, &mut render_ctx),
+                Event::Text(text) => Self::handle_text(&text, &mut builder, &mut render_ctx, theme),
+                Event::Code(code) => builder.markup(&format!("`{code}`")),
+                Event::SoftBreak => builder.markup(" "),
+                Event::HardBreak => builder.markup("\n"),
+                // Handle HTML comments that might contain pagebreak markers
+                Event::Html(html) if html.trim() == "<!-- pagebreak -->" => builder.page_break(),
+                Event::Html(html) | Event::InlineHtml(html) if notes => {
+                    if let Some(text) = review::note_text(&html) {
+                        builder.push(Node::MarginNote {
+                            fill: theme.color.to_typst_rgb("accent"),
+                            text: text.to_string(),
+                        });
                     }
                 }
                 _ => {}
             }
         }
+
+        builder.finish()
     }
 
     fn process_pagebreak_markers(content: &str) -> String {
@@ -412,109 +600,6 @@ impl PdfRenderer {
         content.replace("\\pagebreak", "TYPST_PAGEBREAK_MARKER")
     }
 
-    fn wrap_h2_sections(content: &str) -> String {
-        // This method wraps content between H2 headings in non-breakable blocks
-        // to prevent job entries from splitting across pages
-
-        let mut result = String::new();
-        let mut in_h2_section = false;
-        let lines: Vec<&str> = content.lines().collect();
-        let mut i = 0;
-
-        while i < lines.len() {
-            let line = lines[i];
-
-            // If we encounter a pagebreak inside an H2 section, close the block first
-            if in_h2_section && line.contains("#pagebreak()") {
-                result.push_str("]  // End of job entry block before pagebreak\n\n");
-                result.push_str(line);
-                result.push('\n');
-                // Re-open the block after the pagebreak
-                result.push_str(
-                    "\n#block(breakable: false)[\n  // Continue job entry after pagebreak\n",
-                );
-                i += 1;
-                continue;
-            }
-
-            // Check if this is the start of an H2 section
-            // Look for pattern: #v(XXem) followed by a single-line #block with H2 text
-            if line.contains("#v(")
-                && line.contains("em)")
-                && i + 1 < lines.len()
-                && lines[i + 1].contains("#block(above: 0em, below:")
-                && lines[i + 1].contains("em)[")
-            // Single-line block pattern for H2
-            {
-                // Look ahead to confirm this is an H2
-                let mut is_h2 = false;
-                for check_line in lines.iter().skip(i + 2).take(3) {
-                    if check_line.contains(&format!(
-                        "text(size: {}, weight: \"bold\"",
-                        layout::font_sizes::SUBSECTION
-                    )) {
-                        is_h2 = true;
-                        break;
-                    }
-                }
-
-                if is_h2 {
-                    // Wrap all H2 sections (job entries, education entries, etc.)
-                    // If we were already in an H2 section, close it
-                    if in_h2_section {
-                        result.push_str("]  // End of job entry block\n\n");
-                    }
-
-                    // Start a new non-breakable block with height limit
-                    // This allows Typst to break to a new page if the block is too tall
-                    result.push_str(
-                        "#block(breakable: false, height: auto)[\n  // Start of job entry\n",
-                    );
-                    in_h2_section = true;
-                }
-            }
-
-            // Check if this is an H1 heading
-            if line.contains("#v(") && line.contains("em)") {
-                // Look ahead to see if this is followed by an H1 block
-                let mut is_h1 = false;
-
-                // Check the next several lines for H1 characteristics
-                for j in 1..=10 {
-                    if i + j >= lines.len() {
-                        break;
-                    }
-                    let next_line = lines[i + j];
-
-                    // Check for H1 text
-                    if next_line.contains("text(size: 16pt, weight: \"bold\"") {
-                        is_h1 = true;
-                        break;
-                    }
-                }
-
-                if is_h1 {
-                    // If we were in an H2 section, close it before the H1
-                    if in_h2_section {
-                        result.push_str("]  // End of job entry block\n\n");
-                        in_h2_section = false;
-                    }
-                }
-            }
-
-            result.push_str(line);
-            result.push('\n');
-            i += 1;
-        }
-
-        // Close any remaining open H2 section
-        if in_h2_section {
-            result.push_str("]  // End of job entry block\n");
-        }
-
-        result
-    }
-
     fn enhance_company_names(content: &str) -> String {
         // Replace pattern **Company** (Location) with H2 heading
         let company_re = regex::Regex::new(r"(?m)^\*\*([^*]+)\*\*\s*\(([^)]+)\)\s*$")
@@ -544,288 +629,468 @@ impl PdfRenderer {
             .to_string()
     }
 
+    /// Opens the block and text run for a heading. Every H2 starts a new entry,
+    /// kept together on one page, and an H1 ends the current one.
+    fn open_heading(level: HeadingLevel, builder: &mut Builder, theme: &Theme) {
+        let colors = &theme.color;
+        let spacing = &theme.layout;
+        let header = &theme.font.header;
+        let block = |below: String, breakable: bool| Node::Block {
+            above: "0em".to_string(),
+            below,
+            breakable,
+            body: Vec::new(),
+        };
+        let text = |size: &str, weight, fill| Node::Text {
+            size: size.to_string(),
+            weight,
+            fill,
+            body: Vec::new(),
+        };
+        let header_font = || Node::TextStyle(header.typst_font_args());
+
+        match level {
+            HeadingLevel::H1 => {
+                // Top-level sections (Experience, Education, Skills); the
+                // unbreakable block keeps the heading with its rule
+                builder.end_entry();
+                builder.push(Node::Space(format!("{}em", spacing.get_h1_spacing_above())));
+                builder.open(block(
+                    format!("{}em", spacing.get_h1_spacing_below()),
+                    false,
+                ));
+                builder.push(header_font());
+                builder.open(text(
+                    &header.size_section,
+                    header.weight_bold,
+                    Some(colors.get_h1_color()),
+                ));
+            }
+            HeadingLevel::H2 => {
+                // Company/Organization names - the text runs are added per text
+                // event, to allow for mixed bold/non-bold content
+                builder.start_entry();
+                builder.push(Node::Space(format!("{}em", spacing.get_h2_spacing_above())));
+                builder.open(block(format!("{}em", spacing.get_h2_spacing_below()), true));
+                builder.push(header_font());
+            }
+            HeadingLevel::H3 => {
+                // Job titles/roles - less prominent than company
+                builder.push(Node::Space(format!("{}em", spacing.get_h3_spacing_above())));
+                builder.open(block(format!("{}em", spacing.get_h3_spacing_below()), true));
+                builder.push(header_font());
+                builder.open(text(
+                    &header.size_role,
+                    header.weight_semibold(),
+                    Some(colors.get_h3_color()),
+                ));
+            }
+            _ => {
+                // H4, H5, H6 - rarely used
+                builder.push(Node::Space(layout::spacing::SMALL.to_string()));
+                builder.open(block(layout::spacing::VERY_TINY.to_string(), true));
+                builder.push(header_font());
+                builder.open(text(&header.size_normal, header.weight_medium(), None));
+            }
+        }
+    }
+
     fn handle_start_tag(
         tag: pulldown_cmark::Tag,
-        output: &mut String,
+        builder: &mut Builder,
         context: &mut RenderContext,
         theme: &Theme,
     ) {
-        use pulldown_cmark::{HeadingLevel, Tag};
+        use pulldown_cmark::Tag;
 
         match tag {
             Tag::Heading { level, .. } => {
                 context.in_heading = true;
                 context.heading_level = level;
-                match level {
-                    HeadingLevel::H1 => {
-                        // Top-level sections (Experience, Education, Skills)
-                        // Use Typst's keep-with-next feature to prevent orphaned headings
-                        let _ = writeln!(output, "\n#v({}em)", theme.color.get_h1_spacing_above());
-                        let _ = writeln!(output, "#block(");
-                        let _ = writeln!(output, "  above: 0em,");
-                        let _ =
-                            writeln!(output, "  below: {}em,", theme.color.get_h1_spacing_below());
-                        let _ = writeln!(output, "  breakable: false,");
-                        let _ = writeln!(output, "  height: auto");
-                        let _ = writeln!(output, ")[");
-                        let _ = write!(
-                            output,
-                            "  #text(size: {}, weight: \"bold\", fill: {})[",
-                            layout::font_sizes::SECTION,
-                            theme.color.get_h1_color()
-                        );
-                    }
-                    HeadingLevel::H2 => {
-                        // Company/Organization names - make prominent
-                        let _ = writeln!(output, "\n#v({}em)", theme.color.get_h2_spacing_above());
-                        let _ = writeln!(
-                            output,
-                            "#block(above: 0em, below: {}em)[",
-                            theme.color.get_h2_spacing_below()
-                        );
-                        // Don't start the text formatting here - we'll handle it when processing the text
-                        // to allow for mixed bold/non-bold content
-                        context.in_heading = true;
-                    }
-                    HeadingLevel::H3 => {
-                        // Job titles/roles - less prominent than company
-                        let _ = writeln!(output, "\n#v({}em)", theme.color.get_h3_spacing_above());
-                        let _ = writeln!(
-                            output,
-                            "#block(above: 0em, below: {}em)[",
-                            theme.color.get_h3_spacing_below()
-                        );
-                        let _ = write!(
-                            output,
-                            "  #text(size: {}, weight: \"semibold\", fill: {})[",
-                            layout::font_sizes::MEDIUM,
-                            theme.color.get_h3_color()
-                        );
-                    }
-                    _ => {
-                        // H4, H5, H6 - rarely used
-                        let _ = writeln!(output, "\n#v({})", layout::spacing::SMALL);
-                        let _ = writeln!(
-                            output,
-                            "#block(above: 0em, below: {})[",
-                            layout::spacing::VERY_TINY
-                        );
-                        let _ = write!(
-                            output,
-                            "  #text(size: {}, weight: \"medium\")[",
-                            layout::font_sizes::NORMAL
-                        );
-                    }
-                }
-            }
-            Tag::Paragraph => {
-                if context.list_depth == 0 {
-                    let _ = writeln!(output);
-                }
+                context.heading_text.clear();
+                Self::open_heading(level, builder, theme);
             }
+            Tag::Paragraph if context.list_depth == 0 => builder.markup("\n"),
             Tag::List(_) => {
                 context.list_depth += 1;
                 if context.list_depth == 1 {
-                    let _ = writeln!(output);
+                    builder.markup("\n");
                 }
             }
             Tag::Item => {
-                let _ = write!(output, "\n{}• ", "  ".repeat(context.list_depth - 1));
-            }
-            Tag::Strong | Tag::Emphasis => {
-                let _ = write!(output, "*");
-            }
-            Tag::Strikethrough => {
-                let _ = write!(output, "#strike[");
-            }
-            Tag::Link { dest_url, .. } => {
-                let _ = write!(output, "#link(\"{dest_url}\")[");
-            }
-            Tag::CodeBlock(_) => {
-                let _ = writeln!(output, "\n```");
-            }
-            Tag::BlockQuote(_) => {
-                let _ = write!(output, "\n#quote[");
+                builder.markup(&format!("\n{}• ", "  ".repeat(context.list_depth - 1)));
             }
+            Tag::Strong | Tag::Emphasis => builder.markup("*"),
+            Tag::Strikethrough => builder.markup("#strike["),
+            Tag::Link { dest_url, .. } => builder.markup(&format!("#link(\"{dest_url}\")[")),
+            Tag::CodeBlock(_) => builder.markup("\n```\n"),
+            Tag::BlockQuote(_) => builder.markup("\n#quote["),
             _ => {}
         }
     }
 
     fn handle_end_tag(
         tag: pulldown_cmark::TagEnd,
-        output: &mut String,
+        builder: &mut Builder,
         theme: &Theme,
         context: &mut RenderContext,
     ) {
-        use pulldown_cmark::{HeadingLevel, TagEnd};
+        use pulldown_cmark::TagEnd;
 
         match tag {
-            TagEnd::Heading(_) => {
-                if context.in_heading {
-                    // For H2, we handle the text formatting in handle_text, so no closing bracket needed here
-                    if !matches!(context.heading_level, HeadingLevel::H2) {
-                        let _ = write!(output, "]");
-                    }
-                    if matches!(context.heading_level, HeadingLevel::H1) {
-                        let _ = writeln!(
-                            output,
-                            "\n  #line(length: 100%, stroke: {}pt + {})",
-                            theme.color.get_separator_thickness(),
+            TagEnd::Heading(_) if context.in_heading => {
+                let level = context.heading_level;
+                // H2 text runs are closed as they are written
+                if level != HeadingLevel::H2 {
+                    builder.close();
+                }
+                let length = match theme.layout.separator_style {
+                    SeparatorStyle::Line => Some(layout_styles::SEPARATOR_FULL),
+                    SeparatorStyle::Short => Some(layout_styles::SEPARATOR_SHORT),
+                    SeparatorStyle::None => None,
+                };
+                if let (HeadingLevel::H1, Some(length)) = (level, length) {
+                    builder.push(Node::Line {
+                        length: length.to_string(),
+                        stroke: format!(
+                            "{}pt + {}",
+                            theme.layout.get_separator_thickness(),
                             theme.color.to_typst_rgb("accent")
-                        );
-                    }
-                    let _ = writeln!(output, "]");
-                    // Add extra space after H1 with line
-                    if matches!(context.heading_level, HeadingLevel::H1) {
-                        let _ = writeln!(output, "#v({})", layout::spacing::VERY_TINY);
-                    }
-                    context.in_heading = false;
+                        ),
+                    });
                 }
-            }
-            TagEnd::Paragraph => {
-                if context.list_depth == 0 {
-                    let _ = writeln!(output);
+                builder.close();
+
+                // The visible heading is styled text, so add an invisible heading
+                // element to give the PDF a bookmark outline of sections and entries
+                let outline_level = match level {
+                    HeadingLevel::H1 => Some(1),
+                    HeadingLevel::H2 => Some(2),
+                    _ => None,
+                };
+                if let Some(level) = outline_level {
+                    builder.push(Node::Bookmark {
+                        level,
+                        title: context.heading_text.trim().to_string(),
+                    });
+                }
+                // Add extra space after H1 with line
+                if level == HeadingLevel::H1 {
+                    builder.push(Node::Space(layout::spacing::VERY_TINY.to_string()));
                 }
+                context.in_heading = false;
             }
+            TagEnd::Paragraph if context.list_depth == 0 => builder.markup("\n"),
             TagEnd::List(_) => {
                 context.list_depth -= 1;
                 if context.list_depth == 0 {
-                    let _ = writeln!(output);
+                    builder.markup("\n");
                 }
             }
-            TagEnd::Strong | TagEnd::Emphasis => {
-                let _ = write!(output, "*");
-            }
-            TagEnd::Strikethrough | TagEnd::Link | TagEnd::BlockQuote(_) => {
-                let _ = write!(output, "]");
-            }
-            TagEnd::CodeBlock => {
-                let _ = writeln!(output, "```");
-            }
-            TagEnd::Item => {
-                // Add line break after list item to ensure next item starts on new line
-                let _ = writeln!(output);
-            }
+            TagEnd::Strong | TagEnd::Emphasis => builder.markup("*"),
+            TagEnd::Strikethrough | TagEnd::Link | TagEnd::BlockQuote(_) => builder.markup("]"),
+            TagEnd::CodeBlock => builder.markup("```\n"),
+            // Line break after a list item so the next item starts on a new line
+            TagEnd::Item => builder.markup("\n"),
             _ => {}
         }
     }
 
     fn handle_text(
         text: &pulldown_cmark::CowStr,
-        output: &mut String,
-        context: &RenderContext,
+        builder: &mut Builder,
+        context: &mut RenderContext,
         theme: &Theme,
     ) {
         // Check for pagebreak marker
         if text.trim() == "TYPST_PAGEBREAK_MARKER" {
-            let _ = writeln!(output, "\n#pagebreak()\n");
+            builder.page_break();
             return;
         }
 
+        if context.in_heading {
+            context.heading_text.push_str(text);
+        }
+
         let escaped = text
             .replace('@', "\\@")
             .replace('#', "\\#")
             .replace('$', "\\$");
 
-        // Special handling for H2 headings with parentheses (Company names with locations)
-        if context.in_heading && matches!(context.heading_level, pulldown_cmark::HeadingLevel::H2) {
-            // Check if this text contains parentheses
-            if let Some(paren_start) = escaped.find('(') {
-                // Split into company name and location parts
-                let company = &escaped[..paren_start].trim();
-                let location = &escaped[paren_start..];
-
-                // Company name in bold, location in regular weight
-                let _ = write!(
-                    output,
-                    "  #text(size: {}, weight: \"bold\", fill: {})[{}] #text(size: {}, weight: \"regular\", fill: {})[{}]",
-                    layout::font_sizes::SUBSECTION,
-                    theme.color.get_h2_color(),
-                    company,
-                    layout::font_sizes::SUBSECTION,
-                    theme.color.get_h2_color(),
-                    location
-                );
-            } else {
-                // No parentheses, render normally with bold
-                let _ = write!(
-                    output,
-                    "  #text(size: {}, weight: \"bold\", fill: {})[{}]",
-                    layout::font_sizes::SUBSECTION,
-                    theme.color.get_h2_color(),
-                    escaped
-                );
+        if context.in_heading
+            && context.heading_level == HeadingLevel::H3
+            && theme.layout.entry_layout == EntryLayout::DatesRight
+        {
+            // Role headings end in their dates, which go to the right margin
+            if let Some((role, dates)) = escaped.rsplit_once(", ") {
+                builder.markup(&format!("{role}#h(1fr){dates}"));
+                return;
             }
+        }
+
+        if !(context.in_heading && context.heading_level == HeadingLevel::H2) {
+            builder.markup(&escaped);
+            return;
+        }
+
+        // H2 headings: company name in bold, a parenthesised location in regular weight
+        let header = &theme.font.header;
+        let run = |weight, body: &str| Node::Text {
+            size: header.size_subsection.clone(),
+            weight,
+            fill: Some(theme.color.get_h2_color()),
+            body: vec![Node::Markup(body.to_string())],
+        };
+        if let Some(paren_start) = escaped.find('(') {
+            builder.push(run(header.weight_bold, escaped[..paren_start].trim()));
+            builder.markup(" ");
+            builder.push(run(header.weight_regular, &escaped[paren_start..]));
         } else {
-            // Normal text handling
-            let _ = write!(output, "{escaped}");
+            builder.push(run(header.weight_bold, &escaped));
         }
     }
 }
 
-impl RenderEngine for PdfRenderer {
-    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<()> {
-        // Check if Typst is available
-        if Command::new("typst").arg("--version").output().is_err() {
-            anyhow::bail!(
-                "Typst is required for PDF generation but is not installed.\n\
-                Please install Typst:\n\
-                  - macOS: brew install typst\n\
-                  - Linux: Download from https://github.com/typst/typst/releases\n\
-                  - Cross-platform: cargo install typst-cli"
-            );
-        }
+/// Typst expression for one header or footer slot, with live page counters.
+fn page_text_expr(template: &str, doc: &Document) -> String {
+    let parts: Vec<String> = page_text::parts(template, doc)
+        .into_iter()
+        .map(|part| match part {
+            Part::Text(text) => typst_str(&text),
+            Part::Page => "str(counter(page).get().first())".to_string(),
+            Part::Pages => "str(counter(page).final().first())".to_string(),
+        })
+        .collect();
+
+    if parts.is_empty() {
+        "[]".to_string()
+    } else {
+        parts.join(" + ")
+    }
+}
 
-        // Generate Typst source
-        let typst_source = self.generate_typst_source(doc, theme);
+/// Date recorded as the PDF creation date: the `SOURCE_DATE_EPOCH` timestamp when
+/// set, so that builds are reproducible, otherwise today.
+pub(crate) fn creation_date() -> NaiveDate {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| {
+            let date = epoch_date(&epoch);
+            if date.is_none() {
+                log::warn!("Ignoring invalid SOURCE_DATE_EPOCH '{epoch}'");
+            }
+            date
+        })
+        .unwrap_or_else(|| Local::now().date_naive())
+}
 
-        // Write to temporary file
-        let mut temp_file = NamedTempFile::new()?;
-        std::io::Write::write_all(&mut temp_file, typst_source.as_bytes())?;
+/// UTC date of a Unix timestamp given in seconds.
+fn epoch_date(epoch: &str) -> Option<NaiveDate> {
+    let seconds = epoch.trim().parse::<i64>().ok()?;
+    DateTime::from_timestamp(seconds, 0).map(|time| time.date_naive())
+}
 
-        // Debug: save a copy for inspection
-        #[cfg(debug_assertions)]
-        {
-            let debug_path = std::path::Path::new("/tmp/cv_debug/generated.typ");
-            if let Some(parent) = debug_path.parent() {
-                let _ = std::fs::create_dir_all(parent);
-            }
-            let _ = std::fs::write(debug_path, &typst_source);
-        }
+/// Splits a frontmatter language tag such as `en` or `en-GB` into the Typst `lang`
+/// and `region` values, falling back to English for anything Typst would reject.
+fn text_language(tag: Option<&str>) -> (String, Option<String>) {
+    let Some(tag) = tag else {
+        return ("en".to_string(), None);
+    };
 
-        // Get fonts directory path
-        let fonts_dir = std::env::current_dir()
-            .map_or_else(|_| std::path::PathBuf::from("fonts"), |p| p.join("fonts"));
+    let mut parts = tag.split(['-', '_']);
+    let lang = parts.next().unwrap_or_default();
+    let region = parts.next();
 
-        // Run Typst with font path
-        let mut cmd = Command::new("typst");
-        cmd.arg("compile");
+    let is_code = |code: &str, lengths: &[usize]| {
+        lengths.contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphabetic())
+    };
 
-        // Add font path if it exists
-        if fonts_dir.exists() {
-            cmd.arg("--font-path").arg(&fonts_dir);
-        }
+    if !is_code(lang, &[2, 3]) {
+        log::warn!("Unsupported language '{tag}', using 'en'");
+        return ("en".to_string(), None);
+    }
 
-        cmd.arg(
-            temp_file
-                .path()
-                .to_str()
-                .ok_or_else(|| anyhow::anyhow!("Invalid temp file path"))?,
-        );
-        cmd.arg(
-            output
-                .to_str()
-                .ok_or_else(|| anyhow::anyhow!("Invalid output path"))?,
+    (
+        lang.to_ascii_lowercase(),
+        region
+            .filter(|r| is_code(r, &[2]))
+            .map(str::to_ascii_uppercase),
+    )
+}
+
+/// Directory of project fonts passed to Typst via `--font-path` (`./fonts`)
+pub(crate) fn fonts_dir() -> PathBuf {
+    std::env::current_dir().map_or_else(|_| PathBuf::from("fonts"), |p| p.join("fonts"))
+}
+
+/// Font families Typst can use, including the project `./fonts` directory, or `None`
+/// when Typst is not installed. With `ignore_system`, only the fonts Typst embeds and
+/// those in `./fonts`.
+pub(crate) fn typst_fonts(ignore_system: bool) -> Option<Vec<String>> {
+    let mut cmd = Command::new("typst");
+    cmd.arg("fonts");
+    if ignore_system {
+        cmd.arg("--ignore-system-fonts");
+    }
+    let fonts_dir = fonts_dir();
+    if fonts_dir.exists() {
+        cmd.arg("--font-path").arg(&fonts_dir);
+    }
+
+    let output = cmd.output().ok().filter(|o| o.status.success())?;
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect(),
+    )
+}
+
+/// Returns an error unless the `typst` CLI is installed.
+fn require_typst() -> Result<()> {
+    if Command::new("typst").arg("--version").output().is_err() {
+        anyhow::bail!(
+            "Typst is required for PDF generation but is not installed.\n\
+            Please install Typst:\n\
+              - macOS: brew install typst\n\
+              - Linux: Download from https://github.com/typst/typst/releases\n\
+              - Cross-platform: cargo install typst-cli"
         );
+    }
+    Ok(())
+}
+
+/// Runs a `typst` subcommand on `source`, passing `extra_args` before the source path
+/// and `trailing` after it. Returns the command's stdout and stderr.
+fn run_typst(
+    subcommand: &str,
+    source: &str,
+    extra_args: &[&str],
+    trailing: &[&str],
+) -> Result<std::process::Output> {
+    // Write to temporary file
+    let mut temp_file = NamedTempFile::new()?;
+    std::io::Write::write_all(&mut temp_file, source.as_bytes())?;
+
+    // Get fonts directory path
+    let fonts_dir = fonts_dir();
+
+    // Run Typst with font path
+    let mut cmd = Command::new("typst");
+    cmd.arg(subcommand);
+
+    // Add font path if it exists
+    if fonts_dir.exists() {
+        cmd.arg("--font-path").arg(&fonts_dir);
+    }
+
+    cmd.args(extra_args);
+    cmd.arg(
+        temp_file
+            .path()
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid temp file path"))?,
+    );
+    cmd.args(trailing);
 
-        let output_status = cmd.output()?;
+    Ok(cmd.output()?)
+}
 
-        if !output_status.status.success() {
-            let stderr = String::from_utf8_lossy(&output_status.stderr);
-            anyhow::bail!("Typst compilation failed: {}", stderr);
+/// Compiles Typst source with the `typst` CLI, passing `extra_args` before the paths.
+///
+/// Returns the warnings Typst printed.
+///
+/// # Errors
+///
+/// Returns an error if Typst is not installed or compilation fails.
+pub(crate) fn compile_typst(
+    source: &str,
+    output: &Path,
+    extra_args: &[&str],
+) -> Result<Vec<String>> {
+    require_typst()?;
+
+    let output = output
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("Invalid output path"))?;
+    let output_status = run_typst("compile", source, extra_args, &[output])?;
+
+    let stderr = String::from_utf8_lossy(&output_status.stderr);
+    if !output_status.status.success() {
+        anyhow::bail!("Typst compilation failed: {}", stderr);
+    }
+
+    Ok(typst_warnings(&stderr))
+}
+
+/// Extracts the `warning: ...` messages from Typst's diagnostics, without duplicates.
+pub(crate) fn typst_warnings(stderr: &str) -> Vec<String> {
+    let mut warnings: Vec<String> = Vec::new();
+    for line in stderr.lines() {
+        if let Some(message) = line.trim_start().strip_prefix("warning: ") {
+            let message = message.trim().to_string();
+            if !warnings.contains(&message) {
+                warnings.push(message);
+            }
         }
+    }
+    warnings
+}
 
-        Ok(())
+/// Warnings about the layout of the built-in template, found with `typst query`.
+///
+/// Custom templates don't carry the markers, so they get none. A failed query only
+/// loses these warnings; the render itself already succeeded.
+pub(crate) fn layout_warnings(source: &str) -> Vec<String> {
+    let label = format!("<{OVERSIZED_LABEL}>");
+    if !source.contains(&label) {
+        return Vec::new();
+    }
+
+    let pages: Vec<usize> = run_typst("query", source, &[], &[&label, "--field", "value"])
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice(&output.stdout).ok())
+        .unwrap_or_default();
+
+    pages
+        .into_iter()
+        .map(|page| {
+            format!(
+                "An entry on page {page} is taller than a page and can't be kept together; \
+                 split it with <!-- pagebreak --> or shorten it"
+            )
+        })
+        .collect()
+}
+
+/// Number of pages in a PDF file.
+pub(crate) fn page_count(path: &Path) -> Option<usize> {
+    lopdf::Document::load(path)
+        .ok()
+        .map(|doc| doc.get_pages().len())
+}
+
+impl RenderEngine for PdfRenderer {
+    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<RenderReport> {
+        let (theme, icon_warning) = icons::available(theme);
+        let typst_source = self.generate_typst_source(doc, &theme);
+        let args: &[&str] = if self.pdf_a {
+            &["--pdf-standard", PDF_A_STANDARD]
+        } else {
+            &[]
+        };
+
+        let mut warnings = compile_typst(&typst_source, output, args)?;
+        warnings.extend(icon_warning);
+        if self.layout_warnings {
+            warnings.extend(layout_warnings(&typst_source));
+        }
+        Ok(RenderReport {
+            pages: page_count(output),
+            warnings,
+        })
     }
 }
 
@@ -833,8 +1098,9 @@ impl RenderEngine for PdfRenderer {
 mod tests {
     use super::*;
     use crate::config::{DocumentMetadata, LayoutOptions};
+    use crate::constants::icons;
     use crate::parser::Document;
-    use crate::themes::{color::ColorTheme, font::FontTheme, Theme};
+    use crate::themes::{color::ColorTheme, font::FontTheme, layout::LayoutTheme, Theme};
     use std::collections::HashMap;
 
     fn create_test_document() -> Document {
@@ -849,10 +1115,16 @@ mod tests {
                 website: Some("https://example.com".to_string()),
                 font_theme: "modern".to_string(),
                 color_theme: "modern".to_string(),
+                theme_overrides: None,
+                icons: None,
                 recipient: None,
                 date: None,
                 subject: None,
                 layout: LayoutOptions::default(),
+                keywords: Vec::new(),
+                lang: None,
+                header: None,
+                footer: None,
                 custom: HashMap::new(),
             },
             content: "# Test Section\n\nThis is a test document.".to_string(),
@@ -864,6 +1136,7 @@ mod tests {
         Theme {
             color: ColorTheme::load("modern").expect("Failed to load modern color theme"),
             font: FontTheme::load("modern").expect("Failed to load modern font theme"),
+            layout: LayoutTheme::load("modern").expect("Failed to load modern layout theme"),
         }
     }
 
@@ -873,6 +1146,58 @@ mod tests {
         assert!(renderer.is_ok());
     }
 
+    #[test]
+    fn test_typst_warnings() {
+        let stderr = "warning: unknown font family: inter\n  ┌─ /tmp/x.typ:5:17\n  │\n5 │ #set text(font: \"Inter\")\n  │                 ^^^^^^^\n\nwarning: unknown font family: inter\n\nwarning: block may not occur inside of a paragraph\n  = hint: try wrapping it in a block\n";
+        assert_eq!(
+            typst_warnings(stderr),
+            vec![
+                "unknown font family: inter",
+                "block may not occur inside of a paragraph"
+            ]
+        );
+        assert!(typst_warnings("").is_empty());
+    }
+
+    #[test]
+    fn test_oversized_entries_are_marked() {
+        let renderer = PdfRenderer::new(None).expect("Failed to create renderer");
+        let doc = Document::from_string(
+            "---\nname: Jane\nemail: jane@example.com\n---\n# Experience\n",
+            Path::new("cv.md"),
+        )
+        .expect("Failed to parse");
+        let theme = Theme::new("modern", "modern").expect("Failed to load theme");
+        let source = renderer.generate_typst_source(&doc, &theme);
+
+        assert!(source.contains("#show block.where(breakable: false): it => context {"));
+        assert!(source.contains("[#metadata(here().page()) <cv-oversized>]"));
+
+        // Custom templates carry no markers, so no query is run for them
+        assert!(layout_warnings("#let cv = ()").is_empty());
+    }
+
+    #[test]
+    fn test_epoch_date() {
+        assert_eq!(
+            epoch_date("1700000000"),
+            NaiveDate::from_ymd_opt(2023, 11, 14)
+        );
+        assert_eq!(epoch_date(" 0\n"), NaiveDate::from_ymd_opt(1970, 1, 1));
+        assert_eq!(epoch_date("yesterday"), None);
+    }
+
+    #[test]
+    fn test_text_language() {
+        assert_eq!(text_language(None), ("en".to_string(), None));
+        assert_eq!(text_language(Some("DE")), ("de".to_string(), None));
+        assert_eq!(
+            text_language(Some("pt_br")),
+            ("pt".to_string(), Some("BR".to_string()))
+        );
+        assert_eq!(text_language(Some("english")), ("en".to_string(), None));
+    }
+
     #[test]
     fn test_typst_source_generation() {
         let renderer = PdfRenderer::new(None).expect("Failed to create PDF renderer");
@@ -882,11 +1207,11 @@ mod tests {
         let source = renderer.generate_typst_source(&doc, &theme);
 
         // Check document setup
-        assert!(source.contains("#set document(title: \"Test User\", author: \"Test User\")"));
+        assert!(source.contains("#set document(title: \"Test User\", author: \"Test User\","));
         assert!(source.contains("#set page(paper: \"a4\""));
 
         // Check font configuration
-        assert!(source.contains("#set text(font: \"Inter\""));
+        assert!(source.contains("#set text(font: \"Open Sans\""));
 
         // Check header section
         assert!(source.contains("Test User"));
@@ -989,7 +1314,6 @@ mod tests {
 
     #[test]
     fn test_markdown_to_typst_conversion() {
-        let mut output = String::new();
         let theme = create_test_theme();
         let content = r"
 # Main Section
@@ -1003,7 +1327,7 @@ This is a paragraph.
 **Bold text** and *italic text*.
 ";
 
-        PdfRenderer::render_markdown_as_typst(content, &mut output, &theme);
+        let output = PdfRenderer::render_content(content, &theme, false);
 
         // Check heading formatting (now includes fill color)
         assert!(output.contains(&format!(
@@ -1044,21 +1368,35 @@ This is a paragraph.
         let classic_theme = Theme {
             color: ColorTheme::load("modern").expect("Failed to load color theme"),
             font: FontTheme::load("classic").expect("Failed to load classic font theme"),
+            ..create_test_theme()
         };
         let source = renderer.generate_typst_source(&doc, &classic_theme);
-        assert!(source.contains("#set text(font: \"Georgia\""));
+        assert!(source.contains("#set text(font: \"Times New Roman\", size: 11pt"));
+        assert!(source.contains("#text(font: \"Georgia\", size: 28pt, weight: \"bold\")"));
 
-        // Test modern theme
+        // Test modern theme: headings switch to the header font and tracking, and
+        // bold body text uses the body's semibold weight
         let modern_theme = create_test_theme();
         let source = renderer.generate_typst_source(&doc, &modern_theme);
-        assert!(source.contains("#set text(font: \"Inter\""));
+        assert!(source.contains("#set text(font: \"Open Sans\", size: 11pt, weight: \"regular\""));
+        assert!(source.contains("#set par(leading: 0.50em)"));
+        assert!(source.contains("#set strong(delta: 200)"));
+        assert!(source.contains("#set text(font: \"Inter\", tracking: -0.02em);#text(size: 16pt"));
+
+        // Job titles take their size from the header font
+        let mut role_theme = create_test_theme();
+        role_theme.font.header.size_role = "13pt".to_string();
+        let output = PdfRenderer::render_content("### Engineer\n", &role_theme, false);
+        assert!(output.contains("#text(size: 13pt, weight: \"semibold\""));
 
         // Test sharp theme
         let sharp_theme = Theme {
             color: ColorTheme::load("modern").expect("Failed to load color theme"),
             font: FontTheme::load("sharp").expect("Failed to load sharp font theme"),
+            ..create_test_theme()
         };
         let source = renderer.generate_typst_source(&doc, &sharp_theme);
-        assert!(source.contains("#set text(font: \"Montserrat\""));
+        assert!(source.contains("#set text(font: \"Roboto\""));
+        assert!(source.contains("#set text(font: \"Montserrat\", tracking: -0.03em);"));
     }
 }
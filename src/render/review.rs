@@ -0,0 +1,159 @@
+//! Review builds for feedback rounds (`cv build --draft`)
+//!
+//! A review build stamps every page with a diagonal watermark and names the build in
+//! the footer, by git short hash or, outside a repository, by build time. With notes
+//! enabled, `<!-- note: ... -->` comments in the markdown appear as margin notes; every
+//! other build drops them.
+
+use chrono::{DateTime, Local};
+use pulldown_cmark::{Event, Tag, TagEnd};
+use serde::Serialize;
+use std::path::Path;
+
+/// Watermark used when `--draft` is given without text
+pub const DEFAULT_WATERMARK: &str = "DRAFT";
+
+/// Prefix that marks an HTML comment as a review note
+pub const NOTE_PREFIX: &str = "note:";
+
+/// Settings for a review build
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Review {
+    /// Text stamped diagonally on every page
+    pub watermark: String,
+    /// Identifies the build in the footer: a git short hash or the build time
+    pub stamp: String,
+    /// Show `<!-- note: ... -->` comments as margin notes
+    pub notes: bool,
+}
+
+impl Review {
+    /// Review settings for a build of `input`, stamped from its git repository.
+    #[must_use]
+    pub fn new(watermark: &str, notes: bool, input: &Path) -> Self {
+        Self {
+            watermark: watermark.to_string(),
+            stamp: build_stamp(input),
+            notes,
+        }
+    }
+
+    /// Footer text naming the build, e.g. "DRAFT · 1a2b3c4".
+    #[must_use]
+    pub fn footer(&self) -> String {
+        format!("{} · {}", self.watermark, self.stamp)
+    }
+}
+
+/// Short hash of the git commit `input` belongs to, or the build time when it is not
+/// in a repository.
+#[must_use]
+pub fn build_stamp(input: &Path) -> String {
+    let dir = input
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(build_time)
+}
+
+/// Build time to the minute, fixed by `SOURCE_DATE_EPOCH` when set.
+fn build_time() -> String {
+    std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .map_or_else(|| Local::now().naive_local(), |time| time.naive_utc())
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Text of a `<!-- note: ... -->` comment, or `None` for any other HTML.
+#[must_use]
+pub fn note_text(html: &str) -> Option<&str> {
+    let inner = html
+        .trim()
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim_start();
+    let (prefix, text) = inner.split_at_checked(NOTE_PREFIX.len())?;
+    prefix
+        .eq_ignore_ascii_case(NOTE_PREFIX)
+        .then(|| text.trim())
+}
+
+/// Joins the lines of each HTML block into one `Html` event. pulldown-cmark emits a
+/// block line by line, so a note spanning lines would otherwise not be recognised.
+pub fn join_html_blocks<'a>(events: impl IntoIterator<Item = Event<'a>>) -> Vec<Event<'a>> {
+    let mut joined = Vec::new();
+    let mut block: Option<String> = None;
+    for event in events {
+        match event {
+            Event::Start(Tag::HtmlBlock) => {
+                block = Some(String::new());
+                joined.push(event);
+            }
+            Event::Html(html) if block.is_some() => {
+                if let Some(text) = block.as_mut() {
+                    text.push_str(&html);
+                }
+            }
+            Event::End(TagEnd::HtmlBlock) => {
+                if let Some(text) = block.take().filter(|text| !text.is_empty()) {
+                    joined.push(Event::Html(text.into()));
+                }
+                joined.push(event);
+            }
+            event => joined.push(event),
+        }
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_text() {
+        assert_eq!(
+            note_text("<!-- note: Quantify this -->\n"),
+            Some("Quantify this")
+        );
+        assert_eq!(note_text("<!--NOTE:shorter-->"), Some("shorter"));
+        assert_eq!(note_text("<!-- pagebreak -->"), None);
+        assert_eq!(note_text("<!-- notes -->"), None);
+        assert_eq!(note_text("<b>note: bold</b>"), None);
+    }
+
+    #[test]
+    fn test_join_html_blocks() {
+        let events = pulldown_cmark::Parser::new("<!-- note: tighten\nthis bullet -->\n\nText\n");
+        let html: Vec<String> = join_html_blocks(events)
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Html(html) => Some(html.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(html, ["<!-- note: tighten\nthis bullet -->\n"]);
+        assert_eq!(note_text(&html[0]), Some("tighten\nthis bullet"));
+    }
+
+    #[test]
+    fn test_build_stamp_outside_repository() {
+        let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        let stamp = build_stamp(&dir.path().join("cv.md"));
+        assert!(!stamp.is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+//! Structured document data handed to custom Typst templates
+//!
+//! When a custom template is supplied with `--template`, the generated source starts
+//! with a `#let cv = (...)` dictionary describing the document and the selected theme,
+//! followed by the template text. See `docs/typst-templates.md` for the contract.
+
+use crate::config::RecipientInfo;
+use crate::parser::Document;
+use crate::themes::layout::style_name;
+use crate::themes::Theme;
+use std::fmt::Write;
+
+/// Version of the data contract exposed to templates as `cv.contract`
+pub const TEMPLATE_CONTRACT_VERSION: u32 = 1;
+
+/// Quotes a string as a Typst string literal.
+#[must_use]
+pub fn typst_str(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quotes an optional string, using Typst's `none` when absent.
+fn typst_opt(value: Option<&String>) -> String {
+    value.map_or_else(|| "none".to_string(), |v| typst_str(v))
+}
+
+/// Converts a YAML value from the frontmatter into a Typst value.
+fn yaml_to_typst(value: &serde_yaml::Value) -> String {
+    use serde_yaml::Value;
+
+    match value {
+        Value::Null => "none".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => typst_str(s),
+        Value::Sequence(items) => {
+            if items.is_empty() {
+                return "()".to_string();
+            }
+            let items: Vec<String> = items.iter().map(yaml_to_typst).collect();
+            // A trailing comma keeps single-element arrays from becoming parenthesised values
+            format!("({},)", items.join(", "))
+        }
+        Value::Mapping(map) => {
+            if map.is_empty() {
+                return "(:)".to_string();
+            }
+            let entries: Vec<String> = map
+                .iter()
+                .filter_map(|(k, v)| {
+                    k.as_str()
+                        .map(|key| format!("{}: {}", typst_str(key), yaml_to_typst(v)))
+                })
+                .collect();
+            format!("({})", entries.join(", "))
+        }
+        Value::Tagged(tagged) => yaml_to_typst(&tagged.value),
+    }
+}
+
+/// A top-level (H1) section of the markdown body
+struct Section<'a> {
+    title: &'a str,
+    body: String,
+}
+
+/// Splits markdown content into the text before the first H1 and one entry per H1 section.
+fn split_sections(content: &str) -> (String, Vec<Section<'_>>) {
+    let mut intro = String::new();
+    let mut sections: Vec<Section> = Vec::new();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+
+        if !in_code_block {
+            if let Some(title) = line.strip_prefix("# ") {
+                sections.push(Section {
+                    title: title.trim(),
+                    body: String::new(),
+                });
+                continue;
+            }
+        }
+
+        let target = sections.last_mut().map_or(&mut intro, |s| &mut s.body);
+        target.push_str(line);
+        target.push('\n');
+    }
+
+    (intro, sections)
+}
+
+fn write_recipient(source: &mut String, recipient: Option<&RecipientInfo>) {
+    if let Some(recipient) = recipient {
+        let _ = writeln!(
+            source,
+            "  recipient: (name: {}, title: {}, company: {}, address: {}),",
+            typst_opt(recipient.name.as_ref()),
+            typst_opt(recipient.title.as_ref()),
+            typst_opt(recipient.company.as_ref()),
+            typst_opt(recipient.address.as_ref())
+        );
+    } else {
+        let _ = writeln!(source, "  recipient: none,");
+    }
+}
+
+fn write_theme(source: &mut String, theme: &Theme) {
+    let color = &theme.color;
+    let _ = writeln!(source, "  theme: (");
+    let _ = writeln!(source, "    colors: (");
+    for field in [
+        "primary",
+        "secondary",
+        "accent",
+        "text",
+        "muted",
+        "background",
+        "surface",
+        "border",
+    ] {
+        let _ = writeln!(source, "      {field}: {},", color.to_typst_rgb(field));
+    }
+    let _ = writeln!(source, "      h1: {},", color.get_h1_color());
+    let _ = writeln!(source, "      h2: {},", color.get_h2_color());
+    let _ = writeln!(source, "      h3: {},", color.get_h3_color());
+    let _ = writeln!(source, "    ),");
+
+    let _ = writeln!(source, "    fonts: (");
+    for (role, spec) in [("header", &theme.font.header), ("body", &theme.font.body)] {
+        let _ = writeln!(
+            source,
+            "      {role}: (family: {}, weight-regular: {}, weight-bold: {}, size-name: {}, size-section: {}, size-subsection: {}, size-role: {}, size-normal: {}, size-small: {}, line-height: {}, letter-spacing: {}),",
+            typst_str(&spec.family),
+            spec.weight_regular,
+            spec.weight_bold,
+            spec.size_name,
+            spec.size_section,
+            spec.size_subsection,
+            spec.size_role,
+            spec.size_normal,
+            spec.size_small,
+            spec.line_height,
+            spec.letter_spacing.as_deref().unwrap_or("0em")
+        );
+    }
+    let _ = writeln!(source, "    ),");
+
+    let layout = &theme.layout;
+    let _ = writeln!(
+        source,
+        "    layout: (header: \"{}\", separator: \"{}\", entry: \"{}\", density: \"{}\", icons: \"{}\"),",
+        style_name(&layout.header_style),
+        style_name(&layout.separator_style),
+        style_name(&layout.entry_layout),
+        style_name(&layout.density),
+        style_name(&layout.icons)
+    );
+
+    let _ = writeln!(source, "    spacing: (");
+    let _ = writeln!(
+        source,
+        "      separator-thickness: {}pt,",
+        layout.get_separator_thickness()
+    );
+    let _ = writeln!(
+        source,
+        "      h1-above: {}em,",
+        layout.get_h1_spacing_above()
+    );
+    let _ = writeln!(
+        source,
+        "      h1-below: {}em,",
+        layout.get_h1_spacing_below()
+    );
+    let _ = writeln!(
+        source,
+        "      h2-above: {}em,",
+        layout.get_h2_spacing_above()
+    );
+    let _ = writeln!(
+        source,
+        "      h2-below: {}em,",
+        layout.get_h2_spacing_below()
+    );
+    let _ = writeln!(
+        source,
+        "      h3-above: {}em,",
+        layout.get_h3_spacing_above()
+    );
+    let _ = writeln!(
+        source,
+        "      h3-below: {}em,",
+        layout.get_h3_spacing_below()
+    );
+    let _ = writeln!(source, "    ),");
+    let _ = writeln!(source, "  ),");
+}
+
+/// Writes the `#let cv = (...)` preamble describing `doc` and `theme`.
+///
+/// `render_body` converts a markdown fragment into Typst markup using the built-in
+/// renderer, so templates can place pre-rendered sections wherever they like.
+pub fn write_template_data(
+    source: &mut String,
+    doc: &Document,
+    theme: &Theme,
+    render_body: impl Fn(&str) -> String,
+) {
+    let meta = &doc.metadata;
+    let kind = if meta.recipient.is_some() {
+        "letter"
+    } else {
+        "cv"
+    };
+
+    let _ = writeln!(
+        source,
+        "// Document data provided by cv_check (template contract v{TEMPLATE_CONTRACT_VERSION})"
+    );
+    let _ = writeln!(source, "#let cv = (");
+    let _ = writeln!(source, "  contract: {TEMPLATE_CONTRACT_VERSION},");
+    let _ = writeln!(source, "  kind: \"{kind}\",");
+    let _ = writeln!(source, "  name: {},", typst_str(&meta.name));
+    let _ = writeln!(source, "  email: {},", typst_str(&meta.email));
+    let _ = writeln!(source, "  phone: {},", typst_opt(meta.phone.as_ref()));
+    let _ = writeln!(source, "  location: {},", typst_opt(meta.location.as_ref()));
+    let _ = writeln!(source, "  linkedin: {},", typst_opt(meta.linkedin.as_ref()));
+    let _ = writeln!(source, "  github: {},", typst_opt(meta.github.as_ref()));
+    let _ = writeln!(source, "  website: {},", typst_opt(meta.website.as_ref()));
+    let _ = writeln!(source, "  date: {},", typst_opt(meta.date.as_ref()));
+    let _ = writeln!(source, "  subject: {},", typst_opt(meta.subject.as_ref()));
+    write_recipient(source, meta.recipient.as_ref());
+
+    // Custom frontmatter fields, sorted for stable output
+    let mut custom: Vec<_> = meta.custom.iter().collect();
+    custom.sort_by(|a, b| a.0.cmp(b.0));
+    let entries: Vec<String> = custom
+        .into_iter()
+        .map(|(k, v)| format!("{}: {}", typst_str(k), yaml_to_typst(v)))
+        .collect();
+    if entries.is_empty() {
+        let _ = writeln!(source, "  custom: (:),");
+    } else {
+        let _ = writeln!(source, "  custom: ({}),", entries.join(", "));
+    }
+
+    write_theme(source, theme);
+
+    let (intro, sections) = split_sections(&doc.content);
+    let _ = writeln!(source, "  intro: [{}],", render_body(&intro));
+    let _ = writeln!(source, "  sections: (");
+    for section in &sections {
+        let _ = writeln!(
+            source,
+            "    (title: {}, body: [{}]),",
+            typst_str(section.title),
+            render_body(&section.body)
+        );
+    }
+    let _ = writeln!(source, "  ),");
+    let _ = writeln!(source, "  body: [{}],", render_body(&doc.content));
+    let _ = writeln!(source, ")");
+    let _ = writeln!(source);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typst_str_escaping() {
+        assert_eq!(typst_str("plain"), "\"plain\"");
+        assert_eq!(typst_str("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(typst_str("a\\b"), "\"a\\\\b\"");
+        assert_eq!(typst_str("line1\nline2"), "\"line1\\nline2\"");
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let content = "Intro text\n# Experience\n## Job\n# Education\n```\n# not a heading\n```\n";
+        let (intro, sections) = split_sections(content);
+
+        assert_eq!(intro, "Intro text\n");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].title, "Experience");
+        assert_eq!(sections[0].body, "## Job\n");
+        assert_eq!(sections[1].title, "Education");
+        assert!(sections[1].body.contains("# not a heading"));
+    }
+
+    #[test]
+    fn test_yaml_to_typst() {
+        let value: serde_yaml::Value =
+            serde_yaml::from_str("{a: 1, b: [x], c: null, d: true}").expect("valid yaml");
+        assert_eq!(
+            yaml_to_typst(&value),
+            "(\"a\": 1, \"b\": (\"x\",), \"c\": none, \"d\": true)"
+        );
+    }
+
+    #[test]
+    fn test_yaml_to_typst_empty_list() {
+        let value: serde_yaml::Value = serde_yaml::from_str("{tags: []}").expect("valid yaml");
+        assert_eq!(yaml_to_typst(&value), "(\"tags\": ())");
+    }
+}
@@ -0,0 +1,166 @@
+use crate::parser::Document;
+use crate::render::{RenderEngine, RenderReport};
+use crate::themes::Theme;
+use anyhow::Result;
+use pulldown_cmark::{Event, HeadingLevel, Tag, TagEnd};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Plain text output, for pasting into application forms and ATS text boxes.
+///
+/// Templates don't apply to plain text, so none is taken.
+pub struct TextRenderer;
+
+impl TextRenderer {
+    fn header(doc: &Document) -> String {
+        let meta = &doc.metadata;
+        let mut header = format!("{}\n", meta.name);
+
+        let mut contact = vec![];
+        if let Some(location) = &meta.location {
+            contact.push(location.clone());
+        }
+        if let Some(phone) = &meta.phone {
+            contact.push(phone.clone());
+        }
+        if !meta.email.is_empty() {
+            contact.push(meta.email.clone());
+        }
+        if let Some(website) = &meta.website {
+            contact.push(website.clone());
+        }
+        if let Some(github) = &meta.github {
+            contact.push(format!("github.com/{github}"));
+        }
+        if let Some(linkedin) = &meta.linkedin {
+            contact.push(format!("linkedin.com/in/{linkedin}"));
+        }
+
+        header.push_str(&contact.join(" | "));
+        header.push('\n');
+        header
+    }
+
+    /// Converts the markdown body into plain text.
+    pub(crate) fn body_text(doc: &Document) -> String {
+        let mut output = String::new();
+        let mut list_depth = 0usize;
+        let mut heading: Option<HeadingLevel> = None;
+        let mut heading_text = String::new();
+        let mut link_url: Option<String> = None;
+
+        for event in &doc.markdown_ast {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some(*level);
+                    heading_text.clear();
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    let text = heading_text.trim();
+                    output.push('\n');
+                    if heading == Some(HeadingLevel::H1) {
+                        output.push_str(&text.to_uppercase());
+                        output.push('\n');
+                        output.push_str(&"=".repeat(text.chars().count()));
+                    } else {
+                        output.push_str(text);
+                    }
+                    output.push('\n');
+                    heading = None;
+                }
+                Event::Start(Tag::List(_)) => list_depth += 1,
+                Event::End(TagEnd::List(_)) => {
+                    list_depth = list_depth.saturating_sub(1);
+                    if list_depth == 0 {
+                        output.push('\n');
+                    }
+                }
+                Event::Start(Tag::Item) => {
+                    output.push_str(&"  ".repeat(list_depth.saturating_sub(1)));
+                    output.push_str("- ");
+                }
+                Event::End(TagEnd::Item) if !output.ends_with('\n') => output.push('\n'),
+                Event::End(TagEnd::Paragraph) => {
+                    output.push('\n');
+                    if list_depth == 0 {
+                        output.push('\n');
+                    }
+                }
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    link_url = Some(dest_url.to_string());
+                }
+                Event::End(TagEnd::Link) => {
+                    if let Some(url) = link_url.take() {
+                        let _ = write!(output, " ({url})");
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    if heading.is_some() {
+                        heading_text.push_str(text);
+                    } else {
+                        output.push_str(text);
+                    }
+                }
+                Event::SoftBreak | Event::HardBreak => {
+                    if heading.is_some() {
+                        heading_text.push(' ');
+                    } else {
+                        output.push('\n');
+                    }
+                }
+                Event::Rule => output.push_str("\n----------\n\n"),
+                _ => {}
+            }
+        }
+
+        output
+    }
+}
+
+impl RenderEngine for TextRenderer {
+    fn render(&self, doc: &Document, _theme: &Theme, output: &Path) -> Result<RenderReport> {
+        let mut text = Self::header(doc);
+        text.push_str(&Self::body_text(doc));
+
+        // Collapse the blank-line runs left by nested blocks
+        let mut cleaned = String::with_capacity(text.len());
+        let mut blank_lines = 0;
+        for line in text.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                blank_lines += 1;
+                if blank_lines > 1 {
+                    continue;
+                }
+            } else {
+                blank_lines = 0;
+            }
+            cleaned.push_str(line);
+            cleaned.push('\n');
+        }
+
+        std::fs::write(output, cleaned.trim_end().to_string() + "\n")?;
+        Ok(RenderReport::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_body_text() {
+        let doc = Document::from_string(
+            "---\nname: Test User\nemail: test@example.com\n---\n# Experience\n\n## Engineer\n\n- Built [tools](https://example.com)\n- Shipped **features**\n",
+            &PathBuf::from("test.md"),
+        )
+        .expect("Failed to parse document");
+
+        let text = TextRenderer::body_text(&doc);
+        assert!(text.contains("EXPERIENCE\n=========="));
+        assert!(text.contains("\nEngineer\n"));
+        assert!(text.contains("- Built tools (https://example.com)\n"));
+        assert!(text.contains("- Shipped features\n"));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::parser::Document;
+use crate::render::pdf::{fonts_dir, PdfRenderer};
+use crate::render::{RenderEngine, RenderOptions, RenderReport};
+use crate::themes::Theme;
+use anyhow::Result;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Name of the directory that font assets are copied into, next to the emitted source
+pub const FONTS_DIR_NAME: &str = "fonts";
+
+/// Writes the generated Typst source instead of compiling it, so it can be hand-tuned.
+pub struct TypstRenderer {
+    pdf: PdfRenderer,
+}
+
+impl TypstRenderer {
+    /// Creates a new Typst source renderer with optional custom template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template file cannot be read.
+    #[allow(dead_code)] // `allow(dead_code)` exception
+    pub fn new(template_path: Option<&Path>) -> Result<Self> {
+        Self::with_options(template_path, &RenderOptions::default())
+    }
+
+    /// Creates a new Typst source renderer with optional custom template and render
+    /// options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the template file cannot be read.
+    pub fn with_options(template_path: Option<&Path>, options: &RenderOptions) -> Result<Self> {
+        Ok(Self {
+            pdf: PdfRenderer::with_options(template_path, options)?,
+        })
+    }
+
+    /// Copies the font files used for PDF compilation next to the emitted source.
+    ///
+    /// Returns the number of files copied.
+    fn copy_font_assets(source_dir: &Path, output: &Path) -> Result<usize> {
+        if !source_dir.is_dir() {
+            return Ok(0);
+        }
+
+        let target_dir = output
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(FONTS_DIR_NAME);
+
+        // Emitting next to the fonts directory itself - nothing to copy
+        if target_dir.canonicalize().ok() == source_dir.canonicalize().ok() {
+            return Ok(0);
+        }
+
+        std::fs::create_dir_all(&target_dir)?;
+        let mut copied = 0;
+        for entry in std::fs::read_dir(source_dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                if let Some(name) = path.file_name() {
+                    std::fs::copy(&path, target_dir.join(name))?;
+                    copied += 1;
+                }
+            }
+        }
+
+        Ok(copied)
+    }
+}
+
+impl RenderEngine for TypstRenderer {
+    fn render(&self, doc: &Document, theme: &Theme, output: &Path) -> Result<RenderReport> {
+        let mut source = String::new();
+        let copied = Self::copy_font_assets(&fonts_dir(), output)?;
+
+        // Tell the reader how to turn the file back into a PDF
+        let file_name = output
+            .file_name()
+            .map_or_else(|| "cv.typ".into(), |n| n.to_string_lossy());
+        let _ = writeln!(
+            source,
+            "// Generated by cv_check - edit freely and compile with:"
+        );
+        if copied > 0 {
+            let _ = writeln!(
+                source,
+                "//   typst compile --font-path {FONTS_DIR_NAME} {file_name}\n"
+            );
+        } else {
+            let _ = writeln!(source, "//   typst compile {file_name}\n");
+        }
+
+        source.push_str(&self.pdf.generate_typst_source(doc, theme));
+        std::fs::write(output, source)?;
+        Ok(RenderReport::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_typst_renderer_writes_source() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let output = temp_dir.path().join("cv.typ");
+        let renderer = TypstRenderer::new(None).expect("Failed to create Typst renderer");
+
+        let doc = Document::from_string(
+            "---\nname: Test User\nemail: test@example.com\n---\n# Experience\n",
+            &PathBuf::from("test.md"),
+        )
+        .expect("Failed to parse document");
+        let theme = Theme::new("modern", "modern").expect("Failed to load theme");
+
+        renderer
+            .render(&doc, &theme, &output)
+            .expect("Failed to emit Typst source");
+
+        let source = std::fs::read_to_string(&output).expect("Failed to read emitted source");
+        assert!(source.starts_with("// Generated by cv_check"));
+        assert!(source.contains("#set document(title: \"Test User\""));
+    }
+
+    #[test]
+    fn test_copy_font_assets() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let fonts = temp_dir.path().join("src-fonts");
+        std::fs::create_dir_all(&fonts).expect("Failed to create fonts dir");
+        std::fs::write(fonts.join("Inter.ttf"), b"font").expect("Failed to write font");
+
+        let out_dir = temp_dir.path().join("out");
+        std::fs::create_dir_all(&out_dir).expect("Failed to create output dir");
+        let copied = TypstRenderer::copy_font_assets(&fonts, &out_dir.join("cv.typ"))
+            .expect("Failed to copy fonts");
+
+        assert_eq!(copied, 1);
+        assert!(out_dir.join(FONTS_DIR_NAME).join("Inter.ttf").exists());
+    }
+
+    #[test]
+    fn test_copy_font_assets_missing_dir() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let copied = TypstRenderer::copy_font_assets(
+            &temp_dir.path().join("missing"),
+            &temp_dir.path().join("cv.typ"),
+        )
+        .expect("Missing fonts dir should not fail");
+        assert_eq!(copied, 0);
+    }
+}
@@ -0,0 +1,354 @@
+//! A small typed representation of the Typst markup emitted for document bodies
+//!
+//! `PdfRenderer` walks the markdown once and builds a tree of [`Node`]s through a
+//! [`Builder`], which is then serialized in a single pass. Keeping job entries on
+//! one page and splitting them around forced page breaks is decided while the tree
+//! is built, rather than by re-scanning the generated text.
+
+use crate::constants::layout::review;
+use crate::render::template_data::typst_str;
+use crate::themes::font::typst_weight;
+use std::fmt::{self, Write};
+
+/// One piece of generated Typst markup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// Already-escaped markup, written as is
+    Markup(String),
+    /// Vertical space, `#v(amount)`
+    Space(String),
+    /// A styled text run, `#text(size: .., weight: .., fill: ..)[..]`
+    Text {
+        size: String,
+        weight: u16,
+        fill: Option<String>,
+        body: Vec<Node>,
+    },
+    /// A `#set text(..)` rule styling the rest of the enclosing container
+    TextStyle(String),
+    /// A block with its own spacing; unbreakable blocks stay on one page
+    Block {
+        above: String,
+        below: String,
+        breakable: bool,
+        body: Vec<Node>,
+    },
+    /// Rule under a section heading, `length` long
+    Line { length: String, stroke: String },
+    /// Invisible heading that only adds a PDF bookmark titled `title`
+    Bookmark { level: u8, title: String },
+    /// A job or education entry kept together on one page; `continued` marks the
+    /// part of an entry that follows a forced page break
+    Entry { continued: bool, body: Vec<Node> },
+    /// Forced page break, `#pagebreak()`
+    PageBreak,
+    /// Review note placed in the right margin, level with where it appears
+    MarginNote { fill: String, text: String },
+}
+
+impl Node {
+    /// Returns the children of a container node.
+    fn body_mut(&mut self) -> Option<&mut Vec<Self>> {
+        match self {
+            Self::Text { body, .. } | Self::Block { body, .. } | Self::Entry { body, .. } => {
+                Some(body)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn write_all(f: &mut fmt::Formatter<'_>, nodes: &[Node]) -> fmt::Result {
+    nodes.iter().try_for_each(|node| write!(f, "{node}"))
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Markup(markup) => f.write_str(markup),
+            Self::Space(amount) => writeln!(f, "\n#v({amount})"),
+            Self::Text {
+                size,
+                weight,
+                fill,
+                body,
+            } => {
+                write!(f, "#text(size: {size}, weight: {}", typst_weight(*weight))?;
+                if let Some(fill) = fill {
+                    write!(f, ", fill: {fill}")?;
+                }
+                f.write_str(")[")?;
+                write_all(f, body)?;
+                f.write_str("]")
+            }
+            // The semicolon lets the styled content follow on the same line
+            Self::TextStyle(args) => write!(f, "#set text({args});"),
+            Self::Block {
+                above,
+                below,
+                breakable,
+                body,
+            } => {
+                if *breakable {
+                    writeln!(f, "#block(above: {above}, below: {below})[")?;
+                } else {
+                    writeln!(
+                        f,
+                        "#block(\n  above: {above},\n  below: {below},\n  breakable: false,\n  height: auto\n)["
+                    )?;
+                }
+                f.write_str("  ")?;
+                write_all(f, body)?;
+                f.write_str("\n]\n")
+            }
+            Self::Line { length, stroke } => {
+                write!(f, "\n  #line(length: {length}, stroke: {stroke})")
+            }
+            Self::Bookmark { level, title } => writeln!(
+                f,
+                "#place(hide(heading(level: {level}, outlined: false, bookmarked: true, {})))",
+                typst_str(title)
+            ),
+            Self::Entry { continued, body } => {
+                let comment = if *continued {
+                    "Continue job entry after pagebreak"
+                } else {
+                    "Start of job entry"
+                };
+                writeln!(
+                    f,
+                    "\n#block(breakable: false, height: auto)[\n  // {comment}"
+                )?;
+                write_all(f, body)?;
+                f.write_str("]  // End of job entry block\n")
+            }
+            Self::PageBreak => f.write_str("\n#pagebreak()\n"),
+            // The semicolon ends the call, should text follow on the same line
+            Self::MarginNote { fill, text } => write!(
+                f,
+                "#place(right, dx: {gap} + {width}, block(width: {width}, text(size: {size}, fill: {fill}, {})));",
+                typst_str(text),
+                gap = review::NOTE_GAP,
+                width = review::NOTE_WIDTH,
+                size = review::NOTE_SIZE,
+            ),
+        }
+    }
+}
+
+/// Builds a node tree from a stream of markdown events.
+///
+/// Container nodes (text runs, blocks and entries) are opened, filled and closed;
+/// everything pushed in between becomes their children.
+#[derive(Debug, Default)]
+pub struct Builder {
+    root: Vec<Node>,
+    open: Vec<Node>,
+}
+
+impl Builder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Children of the innermost open container.
+    fn body(&mut self) -> &mut Vec<Node> {
+        match self.open.last_mut().and_then(Node::body_mut) {
+            Some(body) => body,
+            None => &mut self.root,
+        }
+    }
+
+    /// Appends a node to the innermost open container.
+    pub fn push(&mut self, node: Node) {
+        self.body().push(node);
+    }
+
+    /// Appends escaped markup, merging it with preceding markup.
+    pub fn markup(&mut self, markup: &str) {
+        let body = self.body();
+        if let Some(Node::Markup(last)) = body.last_mut() {
+            last.push_str(markup);
+        } else {
+            body.push(Node::Markup(markup.to_string()));
+        }
+    }
+
+    /// Opens a container node; later nodes go inside it until [`Builder::close`].
+    pub fn open(&mut self, node: Node) {
+        self.open.push(node);
+    }
+
+    /// Closes the innermost open container.
+    pub fn close(&mut self) {
+        if let Some(node) = self.open.pop() {
+            self.push(node);
+        }
+    }
+
+    /// Starts a new entry, ending the previous one.
+    pub fn start_entry(&mut self) {
+        self.end_entry();
+        self.open(Node::Entry {
+            continued: false,
+            body: Vec::new(),
+        });
+    }
+
+    /// Ends the open entry along with anything still open inside it. An entry
+    /// with nothing in it, such as the rest of one ending at a page break, is dropped.
+    ///
+    /// Returns whether an entry was open.
+    pub fn end_entry(&mut self) -> bool {
+        if !self.open.iter().any(|n| matches!(n, Node::Entry { .. })) {
+            return false;
+        }
+        while let Some(node) = self.open.pop() {
+            if let Node::Entry { body, .. } = &node {
+                if !body.is_empty() {
+                    self.push(node);
+                }
+                break;
+            }
+            self.push(node);
+        }
+        true
+    }
+
+    /// Forces a page break. An unbreakable entry cannot contain one, so an open
+    /// entry is ended before the break and continued after it.
+    pub fn page_break(&mut self) {
+        let in_entry = self.end_entry();
+        self.push(Node::PageBreak);
+        if in_entry {
+            self.open(Node::Entry {
+                continued: true,
+                body: Vec::new(),
+            });
+        }
+    }
+
+    /// Closes everything still open and returns the top-level nodes.
+    #[must_use]
+    pub fn finish(mut self) -> Vec<Node> {
+        self.end_entry();
+        while !self.open.is_empty() {
+            self.close();
+        }
+        self.root
+    }
+}
+
+/// Serializes `nodes` to Typst markup.
+#[must_use]
+pub fn to_typst(nodes: &[Node]) -> String {
+    let mut source = String::new();
+    for node in nodes {
+        let _ = write!(source, "{node}");
+    }
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heading(builder: &mut Builder, text: &str) {
+        builder.push(Node::Space("1em".to_string()));
+        builder.open(Node::Block {
+            above: "0em".to_string(),
+            below: "0.5em".to_string(),
+            breakable: true,
+            body: Vec::new(),
+        });
+        builder.push(Node::Text {
+            size: "14pt".to_string(),
+            weight: 700,
+            fill: None,
+            body: vec![Node::Markup(text.to_string())],
+        });
+        builder.close();
+    }
+
+    #[test]
+    fn test_entries_close_each_other() {
+        let mut builder = Builder::new();
+        builder.start_entry();
+        heading(&mut builder, "First");
+        builder.markup("one ");
+        builder.markup("two");
+        builder.start_entry();
+        heading(&mut builder, "Second");
+        let nodes = builder.finish();
+
+        assert_eq!(nodes.len(), 2);
+        let Node::Entry { body, .. } = &nodes[0] else {
+            panic!("Expected an entry, got {:?}", nodes[0]);
+        };
+        assert_eq!(body.last(), Some(&Node::Markup("one two".to_string())));
+
+        let source = to_typst(&nodes);
+        assert_eq!(source.matches("// Start of job entry").count(), 2);
+        assert_eq!(source.matches("// End of job entry block").count(), 2);
+        assert!(source.contains(
+            "#v(1em)\n#block(above: 0em, below: 0.5em)[\n  #text(size: 14pt, weight: \"bold\")[First]\n]"
+        ));
+    }
+
+    #[test]
+    fn test_page_break_splits_entry() {
+        let mut builder = Builder::new();
+        builder.markup("intro");
+        builder.page_break();
+        builder.start_entry();
+        builder.markup("before");
+        builder.page_break();
+        builder.markup("after");
+        let nodes = builder.finish();
+
+        assert!(matches!(
+            nodes.as_slice(),
+            [
+                Node::Markup(_),
+                Node::PageBreak,
+                Node::Entry {
+                    continued: false,
+                    ..
+                },
+                Node::PageBreak,
+                Node::Entry {
+                    continued: true,
+                    ..
+                },
+            ]
+        ));
+        let source = to_typst(&nodes);
+        assert!(source.contains("// Continue job entry after pagebreak\nafter]"));
+
+        // Nothing left of the entry after the break
+        let mut builder = Builder::new();
+        builder.start_entry();
+        builder.markup("before");
+        builder.page_break();
+        let nodes = builder.finish();
+        assert!(matches!(nodes.last(), Some(Node::PageBreak)));
+    }
+
+    #[test]
+    fn test_unbreakable_block() {
+        let block = Node::Block {
+            above: "0em".to_string(),
+            below: "1em".to_string(),
+            breakable: false,
+            body: vec![Node::Line {
+                length: "100%".to_string(),
+                stroke: "1pt + accent".to_string(),
+            }],
+        };
+        assert_eq!(
+            block.to_string(),
+            "#block(\n  above: 0em,\n  below: 1em,\n  breakable: false,\n  height: auto\n)[\n  \n  #line(length: 100%, stroke: 1pt + accent)\n]\n"
+        );
+    }
+}
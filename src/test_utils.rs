@@ -5,7 +5,7 @@
 
 use crate::config::{DocumentMetadata, LayoutOptions};
 use crate::parser::{markdown::parse_markdown, Document};
-use crate::themes::{color::ColorTheme, font::FontTheme, Theme};
+use crate::themes::{color::ColorTheme, font::FontTheme, layout::LayoutTheme, Theme};
 use std::collections::HashMap;
 
 /// Creates a standard test document with all fields populated
@@ -22,10 +22,16 @@ pub fn create_test_document() -> Document {
             website: Some("https://example.com".to_string()),
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: None,
             date: None,
             subject: None,
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: "# Test Section\n\nThis is a test document.".to_string(),
@@ -47,10 +53,16 @@ pub fn create_minimal_document(name: &str, email: &str) -> Document {
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: None,
             date: None,
             subject: None,
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: String::new(),
@@ -72,10 +84,16 @@ pub fn create_document_with_content(content: &str) -> Document {
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: None,
             date: None,
             subject: None,
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: content.to_string(),
@@ -89,6 +107,7 @@ pub fn create_test_theme() -> Theme {
     Theme {
         color: ColorTheme::load("modern").expect("Failed to load modern color theme"),
         font: FontTheme::load("modern").expect("Failed to load modern font theme"),
+        layout: LayoutTheme::load("modern").expect("Failed to load modern layout theme"),
     }
 }
 
@@ -100,6 +119,8 @@ pub fn create_theme_by_name(font_theme: &str, color_theme: &str) -> Theme {
             .unwrap_or_else(|_| panic!("Failed to load color theme: {color_theme}")),
         font: FontTheme::load(font_theme)
             .unwrap_or_else(|_| panic!("Failed to load font theme: {font_theme}")),
+        layout: LayoutTheme::load(color_theme)
+            .unwrap_or_else(|_| panic!("Failed to load layout theme: {color_theme}")),
     }
 }
 
@@ -140,10 +161,16 @@ Experienced software engineer with 10+ years building scalable applications.
             website: Some("https://johndoe.com".to_string()),
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             layout: LayoutOptions::default(),
             recipient: None,
             date: None,
             subject: None,
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: content.to_string(),
@@ -178,6 +205,8 @@ John Doe";
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: Some(RecipientInfo {
                 name: Some("Jane Smith".to_string()),
                 title: Some("Hiring Manager".to_string()),
@@ -187,6 +216,10 @@ John Doe";
             date: None,
             subject: Some("Software Engineer Position".to_string()),
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: content.to_string(),
@@ -17,14 +17,6 @@ pub struct ColorTheme {
     pub h1_color: Option<String>,
     pub h2_color: Option<String>,
     pub h3_color: Option<String>,
-    // Styling properties
-    pub separator_thickness: Option<f32>,
-    pub h1_spacing_above: Option<f32>,
-    pub h1_spacing_below: Option<f32>,
-    pub h2_spacing_above: Option<f32>,
-    pub h2_spacing_below: Option<f32>,
-    pub h3_spacing_above: Option<f32>,
-    pub h3_spacing_below: Option<f32>,
 }
 
 impl ColorTheme {
@@ -59,13 +51,6 @@ impl ColorTheme {
             h1_color: None,                    // Use default
             h2_color: None,                    // Use primary
             h3_color: None,                    // Use text
-            separator_thickness: None,         // Use default 2pt
-            h1_spacing_above: None,            // Use default
-            h1_spacing_below: None,            // Use default
-            h2_spacing_above: None,            // Use default
-            h2_spacing_below: None,            // Use default
-            h3_spacing_above: None,            // Use default
-            h3_spacing_below: None,            // Use default
         }
     }
 
@@ -82,13 +67,6 @@ impl ColorTheme {
             h1_color: None,                        // Use default (text color)
             h2_color: Some("#607D8B".to_string()), // Blue-grey
             h3_color: Some("#424242".to_string()), // Dark grey
-            separator_thickness: Some(1.0),        // Thinner line
-            h1_spacing_above: Some(2.5),           // Space above H1
-            h1_spacing_below: Some(0.5),           // Space below H1
-            h2_spacing_above: Some(1.2),           // Default H2 spacing
-            h2_spacing_below: Some(0.8),           // Default H2 spacing
-            h3_spacing_above: Some(1.0),           // Increased spacing
-            h3_spacing_below: Some(0.8),           // Increased spacing
         }
     }
 
@@ -105,13 +83,6 @@ impl ColorTheme {
             h1_color: None,                    // Use default
             h2_color: None,                    // Use primary
             h3_color: None,                    // Use text
-            separator_thickness: None,         // Use default
-            h1_spacing_above: None,            // Use default
-            h1_spacing_below: None,            // Use default
-            h2_spacing_above: None,            // Use default
-            h2_spacing_below: None,            // Use default
-            h3_spacing_above: None,            // Use default
-            h3_spacing_below: None,            // Use default
         }
     }
 
@@ -133,6 +104,27 @@ impl ColorTheme {
         format!("rgb(\"{hex}\")")
     }
 
+    /// Hex value of a colour field, including the `h1`-`h3` heading colours with
+    /// their fallbacks. Returns `None` for an unknown field.
+    #[must_use]
+    pub fn hex(&self, color_field: &str) -> Option<&str> {
+        let hex = match color_field {
+            "primary" => &self.primary,
+            "secondary" => &self.secondary,
+            "accent" => &self.accent,
+            "text" => &self.text,
+            "muted" => &self.muted,
+            "background" => &self.background,
+            "surface" => &self.surface,
+            "border" => &self.border,
+            "h1" => self.h1_color.as_ref().unwrap_or(&self.text),
+            "h2" => self.h2_color.as_ref().unwrap_or(&self.primary),
+            "h3" => self.h3_color.as_ref().unwrap_or(&self.text),
+            _ => return None,
+        };
+        Some(hex)
+    }
+
     /// Get H1 color with fallback to text color
     #[must_use]
     pub fn get_h1_color(&self) -> String {
@@ -156,48 +148,6 @@ impl ColorTheme {
             .as_ref()
             .map_or_else(|| self.to_typst_rgb("text"), |c| format!("rgb(\"{c}\")"))
     }
-
-    /// Get separator thickness with fallback to 2pt
-    #[must_use]
-    pub fn get_separator_thickness(&self) -> f32 {
-        self.separator_thickness.unwrap_or(2.0)
-    }
-
-    /// Get H1 spacing above with fallback to 1.5em
-    #[must_use]
-    pub fn get_h1_spacing_above(&self) -> f32 {
-        self.h1_spacing_above.unwrap_or(1.5)
-    }
-
-    /// Get H1 spacing below with fallback to 0.8em
-    #[must_use]
-    pub fn get_h1_spacing_below(&self) -> f32 {
-        self.h1_spacing_below.unwrap_or(0.8)
-    }
-
-    /// Get H2 spacing above with fallback to 1.2em
-    #[must_use]
-    pub fn get_h2_spacing_above(&self) -> f32 {
-        self.h2_spacing_above.unwrap_or(1.2)
-    }
-
-    /// Get H2 spacing below with fallback to 0.8em
-    #[must_use]
-    pub fn get_h2_spacing_below(&self) -> f32 {
-        self.h2_spacing_below.unwrap_or(0.8)
-    }
-
-    /// Get H3 spacing above with fallback to 0.8em
-    #[must_use]
-    pub fn get_h3_spacing_above(&self) -> f32 {
-        self.h3_spacing_above.unwrap_or(0.8)
-    }
-
-    /// Get H3 spacing below with fallback to 0.6em
-    #[must_use]
-    pub fn get_h3_spacing_below(&self) -> f32 {
-        self.h3_spacing_below.unwrap_or(0.6)
-    }
 }
 
 #[cfg(test)]
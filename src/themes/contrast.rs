@@ -0,0 +1,195 @@
+//! WCAG contrast checks for colour themes
+//!
+//! Each pair of colours a document renders together (body text on the background,
+//! headings, links) is measured with the WCAG 2 contrast ratio and held to the AA
+//! minimum for its text size: 4.5:1 for normal text, 3:1 for large bold headings.
+
+use crate::themes::color::ColorTheme;
+use std::fmt;
+
+/// WCAG AA minimum contrast for normal text
+pub const MIN_CONTRAST_NORMAL: f64 = 4.5;
+
+/// WCAG AA minimum contrast for large text (14pt bold and up)
+pub const MIN_CONTRAST_LARGE: f64 = 3.0;
+
+/// Colour pairs rendered together: what they are used for, foreground and background
+/// field, and the minimum ratio for their text size
+const PAIRS: &[(&str, &str, &str, f64)] = &[
+    ("body text", "text", "background", MIN_CONTRAST_NORMAL),
+    (
+        "body text on surfaces",
+        "text",
+        "surface",
+        MIN_CONTRAST_NORMAL,
+    ),
+    ("secondary text", "muted", "background", MIN_CONTRAST_NORMAL),
+    (
+        "secondary text on surfaces",
+        "muted",
+        "surface",
+        MIN_CONTRAST_NORMAL,
+    ),
+    ("section headings", "h1", "background", MIN_CONTRAST_LARGE),
+    ("entry headings", "h2", "background", MIN_CONTRAST_LARGE),
+    ("job titles", "h3", "background", MIN_CONTRAST_NORMAL),
+    ("links", "accent", "background", MIN_CONTRAST_NORMAL),
+];
+
+/// Contrast of one colour pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastCheck {
+    /// What the pair is used for, e.g. "secondary text"
+    pub usage: &'static str,
+    /// Foreground colour field, e.g. `muted`
+    pub foreground: &'static str,
+    /// Background colour field, e.g. `surface`
+    pub background: &'static str,
+    /// Contrast ratio, from 1 to 21
+    pub ratio: f64,
+    /// WCAG AA minimum for the pair's text size
+    pub minimum: f64,
+}
+
+impl ContrastCheck {
+    /// Whether the pair meets its WCAG AA minimum.
+    #[must_use]
+    pub fn passes(&self) -> bool {
+        self.ratio >= self.minimum
+    }
+}
+
+impl fmt::Display for ContrastCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {} ({}): {:.2}:1",
+            self.foreground, self.background, self.usage, self.ratio
+        )?;
+        if !self.passes() {
+            write!(f, ", needs {}:1", self.minimum)?;
+        }
+        Ok(())
+    }
+}
+
+/// Measures every colour pair of a theme. Pairs with a colour that is not hex are
+/// left out.
+#[must_use]
+pub fn check(colors: &ColorTheme) -> Vec<ContrastCheck> {
+    PAIRS
+        .iter()
+        .filter_map(|&(usage, foreground, background, minimum)| {
+            let ratio = contrast_ratio(colors.hex(foreground)?, colors.hex(background)?)?;
+            Some(ContrastCheck {
+                usage,
+                foreground,
+                background,
+                ratio,
+                minimum,
+            })
+        })
+        .collect()
+}
+
+/// Pairs that fail in `themed` but pass in `base`, such as those broken by a
+/// document's `theme_overrides`.
+#[must_use]
+pub fn regressions(base: &ColorTheme, themed: &ColorTheme) -> Vec<ContrastCheck> {
+    let base = check(base);
+    check(themed)
+        .into_iter()
+        .filter(|pair| !pair.passes())
+        .filter(|pair| {
+            base.iter()
+                .find(|b| b.usage == pair.usage)
+                .is_none_or(ContrastCheck::passes)
+        })
+        .collect()
+}
+
+/// WCAG contrast ratio of two hex colours (`#RGB` or `#RRGGBB`).
+#[must_use]
+pub fn contrast_ratio(foreground: &str, background: &str) -> Option<f64> {
+    let a = relative_luminance(foreground)?;
+    let b = relative_luminance(background)?;
+    let (light, dark) = if a > b { (a, b) } else { (b, a) };
+    Some((light + 0.05) / (dark + 0.05))
+}
+
+/// Red, green and blue channels of a hex colour (`#RGB` or `#RRGGBB`), from 0 to 255.
+pub(crate) fn rgb8(hex: &str) -> Option<[u8; 3]> {
+    let digits = hex.strip_prefix('#')?;
+    let expanded: String = match digits.len() {
+        3 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 => digits.to_string(),
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(expanded.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Red, green and blue channels of a hex colour (`#RGB` or `#RRGGBB`), from 0 to 1.
+pub(crate) fn rgb(hex: &str) -> Option<[f64; 3]> {
+    rgb8(hex).map(|channels| channels.map(|c| f64::from(c) / 255.0))
+}
+
+/// WCAG relative luminance of a hex colour.
+pub(crate) fn relative_luminance(hex: &str) -> Option<f64> {
+    let linear = |value: f64| {
+        if value <= 0.039_28 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let [r, g, b] = rgb(hex)?;
+    Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let ratio = contrast_ratio("#000", "#FFFFFF").expect("Valid colours");
+        assert!((ratio - 21.0).abs() < 0.01);
+        let ratio = contrast_ratio("#767676", "#FFFFFF").expect("Valid colours");
+        assert!((ratio - 4.54).abs() < 0.01);
+        assert_eq!(contrast_ratio("#FFF", "#FFF"), Some(1.0));
+        assert_eq!(contrast_ratio("red", "#FFF"), None);
+    }
+
+    #[test]
+    fn test_sharp_lime_links_fail() {
+        let sharp = ColorTheme::load("sharp").expect("Theme loads");
+        let checks = check(&sharp);
+        assert_eq!(checks.len(), PAIRS.len());
+
+        let links = checks
+            .iter()
+            .find(|pair| pair.usage == "links")
+            .expect("Links are checked");
+        assert!(!links.passes());
+        assert!(links.to_string().contains("accent on background (links)"));
+        assert!(links.to_string().ends_with("needs 4.5:1"));
+
+        let body = &checks[0];
+        assert!(body.passes());
+    }
+
+    #[test]
+    fn test_regressions_only_report_new_failures() {
+        let sharp = ColorTheme::load("sharp").expect("Theme loads");
+        assert!(regressions(&sharp, &sharp).is_empty());
+
+        let mut themed = sharp.clone();
+        themed.muted = "#E0E0E0".to_string();
+        // Muted text on surfaces already fails in sharp, so only the background
+        // pair is new
+        let failures = regressions(&sharp, &themed);
+        let usages: Vec<_> = failures.iter().map(|pair| pair.usage).collect();
+        assert_eq!(usages, ["secondary text"]);
+    }
+}
@@ -17,13 +17,76 @@ pub struct FontSpec {
     pub size_name: String,
     pub size_section: String,
     pub size_subsection: String,
+    /// Job titles (`###`), below the entry heading
+    #[serde(default = "default_size_role")]
+    pub size_role: String,
     pub size_normal: String,
     pub size_small: String,
+    /// Smallest `size_normal` that `--max-pages` may shrink to
+    pub min_size_normal: String,
     pub line_height: f32,
     pub letter_spacing: Option<String>,
 }
 
+fn default_size_role() -> String {
+    font_sizes::MEDIUM.to_string()
+}
+
+/// Typst names of the standard font weights, by weight
+const WEIGHT_NAMES: [(u16, &str); 9] = [
+    (100, "thin"),
+    (200, "extralight"),
+    (300, "light"),
+    (400, "regular"),
+    (500, "medium"),
+    (600, "semibold"),
+    (700, "bold"),
+    (800, "extrabold"),
+    (900, "black"),
+];
+
+/// Typst value for a numeric weight: its name for standard weights such as
+/// `"bold"`, the number otherwise.
+#[must_use]
+pub fn typst_weight(weight: u16) -> String {
+    WEIGHT_NAMES
+        .iter()
+        .find(|(value, _)| *value == weight)
+        .map_or_else(|| weight.to_string(), |(_, name)| format!("\"{name}\""))
+}
+
 impl FontSpec {
+    /// Weight between regular and bold, rounded to a standard weight; used for
+    /// job titles.
+    #[must_use]
+    pub fn weight_semibold(&self) -> u16 {
+        (self.weight_regular + self.weight_bold).div_ceil(200) * 100
+    }
+
+    /// One step above the regular weight, capped at bold; used for minor headings.
+    #[must_use]
+    pub fn weight_medium(&self) -> u16 {
+        (self.weight_regular + 100).min(self.weight_bold)
+    }
+
+    /// Typst `text` arguments selecting this font: the family, plus tracking when
+    /// the spec sets a letter spacing.
+    #[must_use]
+    pub fn typst_font_args(&self) -> String {
+        let family = format!("font: \"{}\"", self.family.replace('"', "\\\""));
+        match &self.letter_spacing {
+            Some(spacing) => format!("{family}, tracking: {spacing}"),
+            None => family,
+        }
+    }
+
+    /// Typst paragraph leading for `line_height`. Leading is the gap between
+    /// lines, so it is the part of the CSS-style line height above 1.
+    #[must_use]
+    pub fn typst_leading(&self) -> String {
+        format!("{:.2}em", (self.line_height - 1.0).max(0.0))
+    }
+
     fn new(
         family: &str,
         weight_regular: u16,
@@ -37,8 +100,10 @@ impl FontSpec {
             size_name: font_sizes::NAME.to_string(),
             size_section: font_sizes::SECTION.to_string(),
             size_subsection: font_sizes::SUBSECTION.to_string(),
+            size_role: default_size_role(),
             size_normal: font_sizes::NORMAL.to_string(),
             size_small: font_sizes::SMALL.to_string(),
+            min_size_normal: font_sizes::MIN_NORMAL.to_string(),
             line_height: 1.5,
             letter_spacing: letter_spacing.map(std::string::ToString::to_string),
         }
@@ -111,6 +176,21 @@ mod tests {
         assert_eq!(theme.body.family, "Roboto");
     }
 
+    #[test]
+    fn test_typst_font_settings() {
+        let theme = FontTheme::load("modern").expect("Failed to load modern theme");
+        assert_eq!(
+            theme.header.typst_font_args(),
+            "font: \"Inter\", tracking: -0.02em"
+        );
+        assert_eq!(theme.body.typst_font_args(), "font: \"Open Sans\"");
+        assert_eq!(theme.body.typst_leading(), "0.50em");
+        assert_eq!(theme.header.weight_semibold(), 600);
+        assert_eq!(theme.body.weight_medium(), 500);
+        assert_eq!(typst_weight(theme.body.weight_bold), "\"semibold\"");
+        assert_eq!(typst_weight(650), "650");
+    }
+
     #[test]
     fn test_load_unknown_theme() {
         let result = FontTheme::load("nonexistent");
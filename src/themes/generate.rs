@@ -0,0 +1,213 @@
+//! Colour themes derived from a single brand colour (`cv themes generate`)
+//!
+//! The brand colour becomes `primary`; every other colour keeps its hue family and
+//! is placed by HSL rules: near-white tinted backgrounds, a dark tinted text colour,
+//! a neighbouring hue for `secondary` and the complementary hue for `accent`.
+//! Foreground colours are then darkened until each pair checked by [`contrast`]
+//! meets its WCAG AA minimum, so a generated theme always passes `themes --check`.
+//!
+//! [`contrast`]: super::contrast
+
+use super::color::ColorTheme;
+use super::contrast::{contrast_ratio, rgb, MIN_CONTRAST_LARGE, MIN_CONTRAST_NORMAL};
+use super::overrides::{ThemeOverrides, EXTENDS_KEY};
+use crate::constants::DEFAULT_THEME;
+use crate::error::CvError;
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+
+/// Contrast body text is held to, above the AA minimum (WCAG AAA)
+const MIN_CONTRAST_BODY: f64 = 7.0;
+
+/// Lightness removed per step while darkening a colour to reach a contrast
+const DARKEN_STEP: f64 = 0.01;
+
+/// A colour as hue (degrees), saturation and lightness (0 to 1)
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Hsl {
+    h: f64,
+    s: f64,
+    l: f64,
+}
+
+impl Hsl {
+    pub(super) fn from_hex(hex: &str) -> Option<Self> {
+        let [red, green, blue] = rgb(hex)?;
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+        let lightness = f64::midpoint(max, min);
+        if delta == 0.0 {
+            return Some(Self {
+                h: 0.0,
+                s: 0.0,
+                l: lightness,
+            });
+        }
+
+        let hue = if red >= green && red >= blue {
+            60.0 * ((green - blue) / delta).rem_euclid(6.0)
+        } else if green >= blue {
+            60.0 * ((blue - red) / delta + 2.0)
+        } else {
+            60.0 * ((red - green) / delta + 4.0)
+        };
+        Some(Self {
+            h: hue,
+            s: delta / (1.0 - (2.0 * lightness - 1.0).abs()),
+            l: lightness,
+        })
+    }
+
+    /// A grey of the given lightness.
+    pub(super) fn grey(lightness: f64) -> Self {
+        Self {
+            h: 0.0,
+            s: 0.0,
+            l: lightness.clamp(0.0, 1.0),
+        }
+    }
+
+    /// The same colour with a different hue, saturation and lightness.
+    fn with(self, rotate: f64, s: f64, l: f64) -> Self {
+        Self {
+            h: (self.h + rotate).rem_euclid(360.0),
+            s: s.clamp(0.0, 1.0),
+            l: l.clamp(0.0, 1.0),
+        }
+    }
+
+    pub(super) fn to_hex(self) -> String {
+        let chroma = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let second = chroma * (1.0 - ((self.h / 60.0).rem_euclid(2.0) - 1.0).abs());
+        let offset = self.l - chroma / 2.0;
+        let (red, green, blue) = match self.h {
+            hue if hue < 60.0 => (chroma, second, 0.0),
+            hue if hue < 120.0 => (second, chroma, 0.0),
+            hue if hue < 180.0 => (0.0, chroma, second),
+            hue if hue < 240.0 => (0.0, second, chroma),
+            hue if hue < 300.0 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let byte = |value: f64| -> u8 {
+            format!("{:.0}", ((value + offset) * 255.0).clamp(0.0, 255.0))
+                .parse()
+                .unwrap_or_default()
+        };
+        format!("#{:02X}{:02X}{:02X}", byte(red), byte(green), byte(blue))
+    }
+
+    /// Hex value of this colour, darkened until it has at least `minimum` contrast
+    /// against `background`.
+    pub(super) fn darkened_to(mut self, background: &str, minimum: f64) -> String {
+        loop {
+            let hex = self.to_hex();
+            let passes = contrast_ratio(&hex, background).is_some_and(|ratio| ratio >= minimum);
+            if passes || self.l <= 0.0 {
+                return hex;
+            }
+            self.l = (self.l - DARKEN_STEP).max(0.0);
+        }
+    }
+}
+
+/// Derives a colour theme from a brand colour given as `#RGB` or `#RRGGBB`. Returns
+/// `None` for anything else.
+#[must_use]
+pub fn from_brand(brand: &str) -> Option<ColorTheme> {
+    let base = Hsl::from_hex(brand)?;
+    let Hsl { s, l, .. } = base;
+
+    let background = base.with(0.0, s.min(0.2), 0.99).to_hex();
+    // The darker backdrop, so contrast against it holds for the background too
+    let surface = base.with(0.0, s.min(0.25), 0.96).to_hex();
+    let on_surface = |color: Hsl, minimum| color.darkened_to(&surface, minimum);
+
+    Some(ColorTheme {
+        primary: base.to_hex(),
+        secondary: on_surface(base.with(30.0, s, l.clamp(0.35, 0.55)), MIN_CONTRAST_LARGE),
+        accent: on_surface(base.with(180.0, s.max(0.6), 0.45), MIN_CONTRAST_NORMAL),
+        text: on_surface(base.with(0.0, s.min(0.15), 0.15), MIN_CONTRAST_BODY),
+        muted: on_surface(base.with(0.0, s.min(0.1), 0.45), MIN_CONTRAST_NORMAL),
+        border: base.with(0.0, s.min(0.2), 0.87).to_hex(),
+        h1_color: Some(on_surface(
+            base.with(0.0, s.min(0.5), 0.2),
+            MIN_CONTRAST_NORMAL,
+        )),
+        h2_color: Some(on_surface(base, MIN_CONTRAST_LARGE)),
+        h3_color: Some(on_surface(
+            base.with(0.0, s.min(0.2), 0.3),
+            MIN_CONTRAST_NORMAL,
+        )),
+        background,
+        surface,
+    })
+}
+
+/// Custom theme file for a generated colour theme named `name`, extending the
+/// default theme for everything but its colours.
+///
+/// # Errors
+///
+/// Returns [`CvError::InvalidTheme`] if `brand` is not a hex colour.
+pub fn theme_file(name: &str, brand: &str) -> Result<String> {
+    let colors = from_brand(brand).ok_or_else(|| CvError::InvalidTheme {
+        theme: name.to_string(),
+        reason: format!(
+            "the brand colour must be a hex colour such as \"#0066CC\", not \"{brand}\""
+        ),
+    })?;
+
+    let mut fields = Mapping::new();
+    fields.insert(EXTENDS_KEY.into(), DEFAULT_THEME.into());
+    if let Value::Mapping(colors) = serde_yaml::to_value(&colors)? {
+        // Unset styling fields come from the extended theme
+        fields.extend(colors.into_iter().filter(|(_, value)| !value.is_null()));
+    }
+
+    Ok(format!(
+        "# {name}: colour theme generated from {brand} by `cv themes generate`\n{}",
+        serde_yaml::to_string(&ThemeOverrides(fields))?
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::themes::contrast;
+
+    #[test]
+    fn test_hsl_round_trip() {
+        for hex in [
+            "#0066CC", "#84CC16", "#FFFFFF", "#000000", "#EC4899", "#808080",
+        ] {
+            let hsl = Hsl::from_hex(hex).expect("Valid colour");
+            assert_eq!(hsl.to_hex(), hex);
+        }
+    }
+
+    #[test]
+    fn test_generated_themes_pass_contrast_checks() {
+        // Dark, mid, light and grey brands all end up readable
+        for brand in [
+            "#0066CC", "#FFFF00", "#84CC16", "#1A1A2E", "#F0F", "#777777",
+        ] {
+            let colors = from_brand(brand).expect("Valid brand colour");
+            for pair in contrast::check(&colors) {
+                assert!(pair.passes(), "{brand}: {pair}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_theme_file_keeps_brand_as_primary() {
+        let file = theme_file("acme", "#0066cc").expect("Theme file generated");
+        assert!(file.starts_with("# acme: colour theme generated from #0066cc"));
+        assert!(file.contains("extends: modern\n"));
+        assert!(file.contains("primary: '#0066CC'\n"));
+        assert!(!file.contains("null"));
+
+        let error = theme_file("acme", "blue").expect_err("Not a hex colour");
+        assert!(error.to_string().contains("not \"blue\""));
+    }
+}
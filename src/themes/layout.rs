@@ -0,0 +1,311 @@
+//! Layout themes: the arrangement of a document, independent of fonts and colours
+//!
+//! A layout sets the header style, how sections are separated, how entry dates are
+//! placed, the overall density, and the spacing around headings. Unless `--layout`
+//! picks one, a document uses the layout of its colour theme.
+
+use crate::constants::{icons, AVAILABLE_LAYOUTS};
+use crate::error::CvError;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// How the name and contact details are set out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderStyle {
+    /// Centred on the page
+    #[default]
+    Centered,
+    /// Aligned with the body text
+    Left,
+    /// Centred on a full-width block of the primary colour
+    Banner,
+}
+
+/// The rule under each section heading
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeparatorStyle {
+    /// A rule across the full width
+    #[default]
+    Line,
+    /// A short accent bar under the start of the heading
+    Short,
+    /// No rule
+    None,
+}
+
+/// Where an entry's dates go
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryLayout {
+    /// Dates follow the role, as written
+    #[default]
+    Stacked,
+    /// The text after the last comma of a role heading is pushed to the right margin
+    DatesRight,
+}
+
+/// Overall spacing, applied on top of the heading spacing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    Compact,
+    #[default]
+    Normal,
+    Relaxed,
+}
+
+impl Density {
+    /// Factor applied to every heading spacing.
+    #[must_use]
+    pub fn scale(self) -> f32 {
+        match self {
+            Self::Compact => 0.75,
+            Self::Normal => 1.0,
+            Self::Relaxed => 1.25,
+        }
+    }
+}
+
+/// Icons shown beside the contact details
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// `FontAwesome` 4 glyphs, which need the font installed
+    #[default]
+    FontAwesome4,
+    /// `FontAwesome` 6 Free and Brands glyphs, which need the fonts installed
+    FontAwesome6,
+    /// Outline icons bundled with `cv_check`, drawn as SVG
+    Svg,
+    /// Text labels such as `Email:` instead of icons
+    None,
+}
+
+impl IconSet {
+    /// Families the set's icon font needs, if it uses one.
+    #[must_use]
+    pub fn font_families(self) -> &'static [&'static str] {
+        match self {
+            Self::FontAwesome4 => &[icons::FONT_NAME],
+            Self::FontAwesome6 => &[icons::fa6::SOLID_FONT, icons::fa6::BRANDS_FONT],
+            Self::Svg | Self::None => &[],
+        }
+    }
+}
+
+/// One-line description of a built-in layout.
+#[must_use]
+pub fn layout_description(name: &str) -> &'static str {
+    match name {
+        "classic" | "sharp" => "Centered header, full rules, even spacing",
+        "modern" => "Centered header, thin rules, airy section spacing",
+        "compact" => "Left header, short rules, dates on the right, tight spacing",
+        "banner" => "Name on a colored banner, thin rules, dates on the right",
+        _ => "Unknown layout",
+    }
+}
+
+/// Name of a style as written in theme files, such as `dates_right`.
+#[must_use]
+pub fn style_name(style: &impl Serialize) -> String {
+    match serde_yaml::to_value(style) {
+        Ok(serde_yaml::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayoutTheme {
+    pub header_style: HeaderStyle,
+    pub separator_style: SeparatorStyle,
+    pub entry_layout: EntryLayout,
+    pub density: Density,
+    pub icons: IconSet,
+    pub separator_thickness: Option<f32>,
+    pub h1_spacing_above: Option<f32>,
+    pub h1_spacing_below: Option<f32>,
+    pub h2_spacing_above: Option<f32>,
+    pub h2_spacing_below: Option<f32>,
+    pub h3_spacing_above: Option<f32>,
+    pub h3_spacing_below: Option<f32>,
+    /// Smallest fraction of the heading spacing `--max-pages` may tighten to
+    pub min_spacing_scale: Option<f32>,
+}
+
+impl LayoutTheme {
+    /// Loads a layout theme by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout name is not recognized.
+    pub fn load(layout_name: &str) -> Result<Self> {
+        match layout_name {
+            "classic" | "sharp" => Ok(Self::default()),
+            "modern" => Ok(Self::modern()),
+            "compact" => Ok(Self::compact()),
+            "banner" => Ok(Self::banner()),
+            _ => Err(CvError::UnknownTheme {
+                theme: layout_name.to_string(),
+                available: AVAILABLE_LAYOUTS.join(", "),
+            }
+            .into()),
+        }
+    }
+
+    fn modern() -> Self {
+        Self {
+            separator_thickness: Some(1.0), // Thinner line
+            h1_spacing_above: Some(2.5),    // Space above H1
+            h1_spacing_below: Some(0.5),    // Space below H1
+            h2_spacing_above: Some(1.2),    // Default H2 spacing
+            h2_spacing_below: Some(0.8),    // Default H2 spacing
+            h3_spacing_above: Some(1.0),    // Increased spacing
+            h3_spacing_below: Some(0.8),    // Increased spacing
+            min_spacing_scale: Some(0.4),   // Generous spacing to give up
+            ..Self::default()
+        }
+    }
+
+    fn compact() -> Self {
+        Self {
+            header_style: HeaderStyle::Left,
+            separator_style: SeparatorStyle::Short,
+            entry_layout: EntryLayout::DatesRight,
+            density: Density::Compact,
+            separator_thickness: Some(3.0), // A bold bar, as it is short
+            min_spacing_scale: Some(0.8),   // Already tight
+            ..Self::default()
+        }
+    }
+
+    fn banner() -> Self {
+        Self {
+            header_style: HeaderStyle::Banner,
+            entry_layout: EntryLayout::DatesRight,
+            separator_thickness: Some(1.0), // Light rules under a heavy header
+            ..Self::default()
+        }
+    }
+
+    /// Rounds so the Typst source shows 0.96em rather than 0.96000004em
+    fn spacing(&self, value: Option<f32>, fallback: f32) -> f32 {
+        (value.unwrap_or(fallback) * self.density.scale() * 100.0).round() / 100.0
+    }
+
+    /// Get separator thickness with fallback to 2pt
+    #[must_use]
+    pub fn get_separator_thickness(&self) -> f32 {
+        self.separator_thickness.unwrap_or(2.0)
+    }
+
+    /// Get H1 spacing above with fallback to 1.5em, scaled by the density
+    #[must_use]
+    pub fn get_h1_spacing_above(&self) -> f32 {
+        self.spacing(self.h1_spacing_above, 1.5)
+    }
+
+    /// Get H1 spacing below with fallback to 0.8em, scaled by the density
+    #[must_use]
+    pub fn get_h1_spacing_below(&self) -> f32 {
+        self.spacing(self.h1_spacing_below, 0.8)
+    }
+
+    /// Get H2 spacing above with fallback to 1.2em, scaled by the density
+    #[must_use]
+    pub fn get_h2_spacing_above(&self) -> f32 {
+        self.spacing(self.h2_spacing_above, 1.2)
+    }
+
+    /// Get H2 spacing below with fallback to 0.8em, scaled by the density
+    #[must_use]
+    pub fn get_h2_spacing_below(&self) -> f32 {
+        self.spacing(self.h2_spacing_below, 0.8)
+    }
+
+    /// Get H3 spacing above with fallback to 0.8em, scaled by the density
+    #[must_use]
+    pub fn get_h3_spacing_above(&self) -> f32 {
+        self.spacing(self.h3_spacing_above, 0.8)
+    }
+
+    /// Get H3 spacing below with fallback to 0.6em, scaled by the density
+    #[must_use]
+    pub fn get_h3_spacing_below(&self) -> f32 {
+        self.spacing(self.h3_spacing_below, 0.6)
+    }
+
+    /// Get the minimum heading spacing scale with fallback to 0.6
+    #[must_use]
+    pub fn get_min_spacing_scale(&self) -> f32 {
+        self.min_spacing_scale.unwrap_or(0.6)
+    }
+
+    /// Returns a copy with every heading spacing multiplied by `scale`. The density
+    /// is folded into the new spacing.
+    #[must_use]
+    pub fn with_spacing_scale(&self, scale: f32) -> Self {
+        let scaled = |value: f32| Some((value * scale * 100.0).round() / 100.0);
+        Self {
+            density: Density::Normal,
+            h1_spacing_above: scaled(self.get_h1_spacing_above()),
+            h1_spacing_below: scaled(self.get_h1_spacing_below()),
+            h2_spacing_above: scaled(self.get_h2_spacing_above()),
+            h2_spacing_below: scaled(self.get_h2_spacing_below()),
+            h3_spacing_above: scaled(self.get_h3_spacing_above()),
+            h3_spacing_below: scaled(self.get_h3_spacing_below()),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_layouts() {
+        for name in AVAILABLE_LAYOUTS {
+            LayoutTheme::load(name).expect("Built-in layout loads");
+        }
+        let error = LayoutTheme::load("grid").expect_err("Unknown layout");
+        assert!(error.to_string().contains("compact"));
+
+        let banner = LayoutTheme::load("banner").expect("Layout loads");
+        assert_eq!(banner.header_style, HeaderStyle::Banner);
+        assert_eq!(style_name(&banner.entry_layout), "dates_right");
+    }
+
+    #[test]
+    fn test_density_scales_spacing() {
+        let compact = LayoutTheme::load("compact").expect("Layout loads");
+        assert!((compact.get_h1_spacing_above() - 1.13).abs() < f32::EPSILON);
+        // Rules keep their thickness
+        assert!((compact.get_separator_thickness() - 3.0).abs() < f32::EPSILON);
+
+        let relaxed = LayoutTheme {
+            density: Density::Relaxed,
+            ..LayoutTheme::default()
+        };
+        assert!((relaxed.get_h2_spacing_above() - 1.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_with_spacing_scale() {
+        let layout = LayoutTheme::load("modern").expect("Failed to load layout");
+        let tight = layout.with_spacing_scale(0.8);
+        assert!((tight.get_h1_spacing_above() - 2.0).abs() < f32::EPSILON);
+        assert!((tight.get_h3_spacing_below() - 0.64).abs() < f32::EPSILON);
+
+        // Fallback spacing is scaled too
+        let classic = LayoutTheme::load("classic").expect("Failed to load layout");
+        let tight = classic.with_spacing_scale(0.5);
+        assert!((tight.get_h1_spacing_above() - 0.75).abs() < f32::EPSILON);
+
+        // The density is applied once
+        let compact = LayoutTheme::load("compact").expect("Failed to load layout");
+        let tight = compact.with_spacing_scale(1.0);
+        assert!((tight.get_h1_spacing_above() - compact.get_h1_spacing_above()).abs() < 1e-6);
+    }
+}
@@ -1,8 +1,15 @@
 pub mod color;
+pub mod contrast;
 pub mod font;
+pub mod generate;
+pub mod layout;
+pub mod overrides;
+pub mod print;
 
 use anyhow::Result;
+use overrides::{is_theme_file, load_theme_file, ThemeOverrides};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Information about a theme including descriptions
 #[derive(Debug, Clone)]
@@ -44,19 +51,97 @@ pub fn get_theme_info(name: &str) -> Option<&'static ThemeInfo> {
 pub struct Theme {
     pub font: font::FontTheme,
     pub color: color::ColorTheme,
+    pub layout: layout::LayoutTheme,
 }
 
 impl Theme {
-    /// Creates a new theme with the specified font and color themes.
+    /// Creates a new theme with the specified font and color themes, and the layout
+    /// that goes with the color theme.
+    ///
+    /// Either name may instead be the path of a custom theme file (`.yaml`), whose
+    /// fonts, or colours and layout, are then used.
     ///
     /// # Errors
     ///
-    /// Returns an error if either theme name is not recognized.
+    /// Returns an error if either theme name is not recognized, or a theme file is
+    /// invalid.
     pub fn new(font_theme_name: &str, color_theme_name: &str) -> Result<Self> {
-        let font = font::FontTheme::load(font_theme_name)?;
-        let color = color::ColorTheme::load(color_theme_name)?;
+        let font = if is_theme_file(font_theme_name) {
+            load_theme_file(Path::new(font_theme_name))?.font
+        } else {
+            font::FontTheme::load(font_theme_name)?
+        };
+        let (color, layout) = if is_theme_file(color_theme_name) {
+            let file = load_theme_file(Path::new(color_theme_name))?;
+            (file.color, file.layout)
+        } else {
+            (
+                color::ColorTheme::load(color_theme_name)?,
+                layout::LayoutTheme::load(color_theme_name)?,
+            )
+        };
+
+        Ok(Self {
+            font,
+            color,
+            layout,
+        })
+    }
 
-        Ok(Self { font, color })
+    /// Returns this theme with the layout of `layout_name` (a built-in layout or a
+    /// theme file) if one is given, keeping the colour theme's layout otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layout name is not recognized, or a theme file is
+    /// invalid.
+    pub fn with_layout(self, layout_name: Option<&str>) -> Result<Self> {
+        let layout = match layout_name {
+            None => return Ok(self),
+            Some(name) if is_theme_file(name) => load_theme_file(Path::new(name))?.layout,
+            Some(name) => layout::LayoutTheme::load(name)?,
+        };
+        Ok(Self { layout, ..self })
+    }
+
+    /// Returns this theme with a document's `theme_overrides` applied, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the overrides name unknown fields or invalid values.
+    pub fn with_overrides(self, overrides: Option<&ThemeOverrides>) -> Result<Self> {
+        match overrides {
+            Some(overrides) if !overrides.is_empty() => overrides.apply(&self, "theme_overrides"),
+            _ => Ok(self),
+        }
+    }
+
+    /// Returns this theme with the frontmatter `icons` set, if one is given.
+    #[must_use]
+    pub fn with_icons(mut self, icons: Option<layout::IconSet>) -> Self {
+        if let Some(icons) = icons {
+            self.layout.icons = icons;
+        }
+        self
+    }
+
+    /// Returns the print-friendly variant of this theme: greyscale, high-contrast
+    /// colours, bold headings and heavier section rules (see [`print`]).
+    #[must_use]
+    pub fn print_safe(&self) -> Self {
+        let mut font = self.font.clone();
+        font.header.weight_bold = font.header.weight_bold.max(print::MIN_HEADING_WEIGHT);
+        let mut layout = self.layout.clone();
+        layout.separator_thickness = Some(
+            layout
+                .get_separator_thickness()
+                .max(print::MIN_SEPARATOR_THICKNESS),
+        );
+        Self {
+            font,
+            color: print::print_safe(&self.color),
+            layout,
+        }
     }
 
     /// Returns lists of available font and color theme names.
@@ -0,0 +1,425 @@
+//! Patching individual theme fields over a base theme
+//!
+//! Overrides come from the `theme_overrides` frontmatter block or from a custom theme
+//! file. Top-level keys patch [`ColorTheme`] fields (colours and heading colours) and
+//! [`LayoutTheme`] fields (styles and spacing); keys under `header` and `body` patch
+//! the [`FontSpec`] of that font:
+//!
+//! ```yaml
+//! extends: modern          # theme files only: the theme being patched
+//! accent: "#D97706"
+//! h2_color: "#1F2937"
+//! h2_spacing_above: 1.0
+//! entry_layout: dates_right
+//! header:
+//!   family: Lato
+//!   weight_bold: 800
+//! ```
+//!
+//! [`FontSpec`]: super::font::FontSpec
+//! [`LayoutTheme`]: super::layout::LayoutTheme
+
+use super::color::ColorTheme;
+use super::font::FontTheme;
+use super::layout::LayoutTheme;
+use super::Theme;
+use crate::error::CvError;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::path::Path;
+
+/// Key naming the base theme in a custom theme file
+pub const EXTENDS_KEY: &str = "extends";
+
+/// Keys holding font overrides; everything else is a colour or layout theme field
+pub const FONT_KEYS: &[&str] = &["header", "body"];
+
+/// How many theme files may extend each other in a chain
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Theme fields to patch over a base theme
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ThemeOverrides(pub Mapping);
+
+impl ThemeOverrides {
+    /// Returns `base` with these overrides merged over it. `source` names where the
+    /// overrides came from, for error messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CvError::InvalidTheme`] if a key is not a theme field, a value has the
+    /// wrong type, or the result fails [`validate`].
+    pub fn apply(&self, base: &Theme, source: &str) -> Result<Theme> {
+        let invalid = |reason: String| CvError::InvalidTheme {
+            theme: source.to_string(),
+            reason,
+        };
+
+        let mut color = to_mapping(&base.color)?;
+        let mut font = to_mapping(&base.font)?;
+        let mut layout = to_mapping(&base.layout)?;
+
+        for (key, value) in &self.0 {
+            let key = key
+                .as_str()
+                .ok_or_else(|| invalid(format!("key {key:?} is not a string")))?;
+
+            if FONT_KEYS.contains(&key) {
+                let Value::Mapping(fields) = value else {
+                    return Err(invalid(format!("`{key}` must be a map of font fields")).into());
+                };
+                let Some(Value::Mapping(spec)) = font.get_mut(key) else {
+                    continue;
+                };
+                patch(spec, fields, &format!("{key}."), &invalid)?;
+            } else {
+                let target = if layout.contains_key(key) {
+                    &mut layout
+                } else if color.contains_key(key) {
+                    &mut color
+                } else {
+                    let known: Vec<&str> = color
+                        .keys()
+                        .chain(layout.keys())
+                        .filter_map(Value::as_str)
+                        .collect();
+                    return Err(invalid(format!(
+                        "unknown field `{key}` (expected one of: {}, header, body)",
+                        known.join(", ")
+                    ))
+                    .into());
+                };
+                patch(
+                    target,
+                    &Mapping::from_iter([(key.into(), value.clone())]),
+                    "",
+                    &invalid,
+                )?;
+            }
+        }
+
+        let theme = Theme {
+            color: serde_yaml::from_value::<ColorTheme>(Value::Mapping(color))
+                .map_err(|e| invalid(e.to_string()))?,
+            font: serde_yaml::from_value::<FontTheme>(Value::Mapping(font))
+                .map_err(|e| invalid(e.to_string()))?,
+            layout: serde_yaml::from_value::<LayoutTheme>(Value::Mapping(layout))
+                .map_err(|e| invalid(e.to_string()))?,
+        };
+        validate(&theme).map_err(invalid)?;
+        Ok(theme)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn to_mapping(value: &impl Serialize) -> Result<Mapping> {
+    match serde_yaml::to_value(value)? {
+        Value::Mapping(mapping) => Ok(mapping),
+        _ => anyhow::bail!("Theme did not serialize to a map"),
+    }
+}
+
+/// Sets each of `fields` on `target`, rejecting fields `target` does not have and
+/// values of a different type than the one they replace.
+fn patch(
+    target: &mut Mapping,
+    fields: &Mapping,
+    prefix: &str,
+    invalid: &impl Fn(String) -> CvError,
+) -> Result<()> {
+    for (field, value) in fields {
+        let Some(slot) = target.get_mut(field) else {
+            let name = field.as_str().unwrap_or("?");
+            let known: Vec<&str> = target.keys().filter_map(Value::as_str).collect();
+            return Err(invalid(format!(
+                "unknown field `{prefix}{name}` (expected one of: {})",
+                known.join(", ")
+            ))
+            .into());
+        };
+
+        let expected = match slot {
+            Value::Number(_) if !value.is_number() => Some("a number"),
+            Value::String(_) if !value.is_string() => Some("a string"),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            let name = field.as_str().unwrap_or("?");
+            return Err(invalid(format!("`{prefix}{name}` must be {expected}")).into());
+        }
+        *slot = value.clone();
+    }
+    Ok(())
+}
+
+/// Whether `name` refers to a custom theme file rather than a built-in theme.
+#[must_use]
+pub fn is_theme_file(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+}
+
+/// Loads a custom theme file: its `extends` theme, built-in or another file relative
+/// to it, with the file's fields patched over it.
+///
+/// # Errors
+///
+/// Returns an error if the file is missing or invalid, or its `extends` chain is
+/// broken or too long.
+pub fn load_theme_file(path: &Path) -> Result<Theme> {
+    load_theme_file_at(path, 0)
+}
+
+fn load_theme_file_at(path: &Path, depth: usize) -> Result<Theme> {
+    let source = path.display().to_string();
+    let invalid = |reason: String| CvError::InvalidTheme {
+        theme: source.clone(),
+        reason,
+    };
+
+    if depth >= MAX_EXTENDS_DEPTH {
+        return Err(invalid(format!(
+            "more than {MAX_EXTENDS_DEPTH} themes extend each other; is there a cycle?"
+        ))
+        .into());
+    }
+    if !path.exists() {
+        return Err(CvError::FileNotFound(path.to_path_buf()).into());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let mut fields: Mapping = serde_yaml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let extends = match fields.remove(EXTENDS_KEY) {
+        Some(Value::String(extends)) => extends,
+        Some(_) => return Err(invalid(format!("`{EXTENDS_KEY}` must be a theme name")).into()),
+        None => {
+            return Err(invalid(format!(
+                "`{EXTENDS_KEY}` is required, e.g. `{EXTENDS_KEY}: modern`"
+            ))
+            .into())
+        }
+    };
+
+    let base = if is_theme_file(&extends) {
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        load_theme_file_at(&dir.join(extends), depth + 1)?
+    } else {
+        Theme::new(&extends, &extends)?
+    };
+
+    ThemeOverrides(fields).apply(&base, &source)
+}
+
+/// Checks that colours are hex codes, sizes are Typst lengths and numbers are in
+/// range.
+///
+/// # Errors
+///
+/// Returns a description of the first invalid field.
+pub fn validate(theme: &Theme) -> Result<(), String> {
+    let color = &theme.color;
+    for (field, value) in [
+        ("primary", Some(&color.primary)),
+        ("secondary", Some(&color.secondary)),
+        ("accent", Some(&color.accent)),
+        ("text", Some(&color.text)),
+        ("muted", Some(&color.muted)),
+        ("background", Some(&color.background)),
+        ("surface", Some(&color.surface)),
+        ("border", Some(&color.border)),
+        ("h1_color", color.h1_color.as_ref()),
+        ("h2_color", color.h2_color.as_ref()),
+        ("h3_color", color.h3_color.as_ref()),
+    ] {
+        if let Some(value) = value {
+            if !is_hex_color(value) {
+                return Err(format!(
+                    "`{field}` must be a hex colour such as \"#0066CC\", not \"{value}\""
+                ));
+            }
+        }
+    }
+
+    let layout = &theme.layout;
+    for (field, value) in [
+        ("separator_thickness", layout.separator_thickness),
+        ("h1_spacing_above", layout.h1_spacing_above),
+        ("h1_spacing_below", layout.h1_spacing_below),
+        ("h2_spacing_above", layout.h2_spacing_above),
+        ("h2_spacing_below", layout.h2_spacing_below),
+        ("h3_spacing_above", layout.h3_spacing_above),
+        ("h3_spacing_below", layout.h3_spacing_below),
+    ] {
+        if value.is_some_and(|v| !(0.0..=10.0).contains(&v)) {
+            return Err(format!("`{field}` must be between 0 and 10"));
+        }
+    }
+    if layout
+        .min_spacing_scale
+        .is_some_and(|v| !(0.0..=1.0).contains(&v))
+    {
+        return Err("`min_spacing_scale` must be between 0 and 1".to_string());
+    }
+
+    for (name, spec) in [("header", &theme.font.header), ("body", &theme.font.body)] {
+        if spec.family.trim().is_empty() {
+            return Err(format!("`{name}.family` must not be empty"));
+        }
+        for (field, weight) in [
+            ("weight_regular", spec.weight_regular),
+            ("weight_bold", spec.weight_bold),
+        ] {
+            if !(100..=900).contains(&weight) {
+                return Err(format!("`{name}.{field}` must be between 100 and 900"));
+            }
+        }
+        for (field, size) in [
+            ("size_name", &spec.size_name),
+            ("size_section", &spec.size_section),
+            ("size_subsection", &spec.size_subsection),
+            ("size_role", &spec.size_role),
+            ("size_normal", &spec.size_normal),
+            ("size_small", &spec.size_small),
+            ("min_size_normal", &spec.min_size_normal),
+        ] {
+            if !is_length(size) {
+                return Err(format!(
+                    "`{name}.{field}` must be a length such as \"11pt\", not \"{size}\""
+                ));
+            }
+        }
+        if let Some(spacing) = &spec.letter_spacing {
+            if !is_length(spacing) {
+                return Err(format!(
+                    "`{name}.letter_spacing` must be a length such as \"-0.02em\", not \"{spacing}\""
+                ));
+            }
+        }
+        if !(0.5..=3.0).contains(&spec.line_height) {
+            return Err(format!("`{name}.line_height` must be between 0.5 and 3"));
+        }
+    }
+
+    Ok(())
+}
+
+/// `#RGB` or `#RRGGBB`
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// A number with a unit Typst understands, such as `11pt` or `-0.02em`
+fn is_length(value: &str) -> bool {
+    ["pt", "em", "mm", "cm", "in"].iter().any(|unit| {
+        value
+            .strip_suffix(unit)
+            .is_some_and(|number| number.parse::<f64>().is_ok_and(f64::is_finite))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::themes::layout::EntryLayout;
+
+    fn overrides(yaml: &str) -> ThemeOverrides {
+        serde_yaml::from_str(yaml).expect("Valid YAML")
+    }
+
+    fn modern() -> Theme {
+        Theme::new("modern", "modern").expect("Theme loads")
+    }
+
+    #[test]
+    fn test_apply_patches_only_given_fields() {
+        let theme = overrides(
+            "accent: \"#D97706\"\nh2_spacing_above: 1.0\nentry_layout: dates_right\nheader:\n  family: Lato\n  weight_bold: 800\n",
+        )
+        .apply(&modern(), "test")
+        .expect("Overrides apply");
+
+        assert_eq!(theme.color.accent, "#D97706");
+        assert_eq!(theme.layout.h2_spacing_above, Some(1.0));
+        assert_eq!(theme.layout.entry_layout, EntryLayout::DatesRight);
+        assert_eq!(theme.layout.header_style, modern().layout.header_style);
+        assert_eq!(theme.color.primary, modern().color.primary);
+        assert_eq!(theme.font.header.family, "Lato");
+        assert_eq!(theme.font.header.weight_bold, 800);
+        assert_eq!(theme.font.body.family, "Open Sans");
+    }
+
+    #[test]
+    fn test_apply_rejects_bad_fields() {
+        for (yaml, expected) in [
+            ("acent: \"#000000\"", "unknown field `acent`"),
+            ("header:\n  famly: Lato", "unknown field `header.famly`"),
+            ("header: Lato", "`header` must be a map"),
+            ("primary: blue", "hex colour"),
+            (
+                "h2_spacing_above: wide",
+                "`h2_spacing_above` must be a number",
+            ),
+            (
+                "body:\n  size_normal: 11",
+                "`body.size_normal` must be a string",
+            ),
+            ("body:\n  size_normal: 11px", "length"),
+            ("header_style: boxed", "unknown variant `boxed`"),
+            ("header:\n  weight_bold: 1000", "between 100 and 900"),
+        ] {
+            let error = overrides(yaml)
+                .apply(&modern(), "test")
+                .expect_err("Overrides are rejected")
+                .to_string();
+            assert!(error.contains(expected), "{yaml}: {error}");
+        }
+    }
+
+    #[test]
+    fn test_theme_files_extend_each_other() {
+        let dir = tempfile::TempDir::new().expect("Failed to create temp dir");
+        std::fs::write(
+            dir.path().join("brand.yaml"),
+            "extends: classic\nprimary: \"#112233\"\n",
+        )
+        .expect("Failed to write theme");
+        std::fs::write(
+            dir.path().join("team.yml"),
+            "extends: brand.yaml\naccent: \"#445566\"\n",
+        )
+        .expect("Failed to write theme");
+
+        let theme = load_theme_file(&dir.path().join("team.yml")).expect("Theme loads");
+        assert_eq!(theme.color.primary, "#112233");
+        assert_eq!(theme.color.accent, "#445566");
+        assert_eq!(theme.font.header.family, "Georgia");
+
+        std::fs::write(dir.path().join("loop.yaml"), "extends: loop.yaml\n")
+            .expect("Failed to write theme");
+        assert!(load_theme_file(&dir.path().join("loop.yaml")).is_err());
+
+        std::fs::write(dir.path().join("orphan.yaml"), "primary: \"#112233\"\n")
+            .expect("Failed to write theme");
+        assert!(load_theme_file(&dir.path().join("orphan.yaml")).is_err());
+    }
+
+    #[test]
+    fn test_lengths_and_colors() {
+        assert!(is_length("10.5pt"));
+        assert!(is_length("-0.02em"));
+        assert!(!is_length("pt"));
+        assert!(!is_length("11"));
+        assert!(is_hex_color("#abc"));
+        assert!(!is_hex_color("#abcd"));
+        assert!(!is_hex_color("red"));
+    }
+}
@@ -0,0 +1,127 @@
+//! Print-friendly greyscale variant of a colour theme (`cv build --print-safe`)
+//!
+//! Each colour becomes the grey of the same luminance, on a plain white page. Text,
+//! headings and section rules are then darkened until they meet a contrast that
+//! survives cheap printers and photocopies. With colour gone, headings are told
+//! apart by weight and by heavier rules under each section.
+
+use super::color::ColorTheme;
+use super::contrast::{relative_luminance, MIN_CONTRAST_LARGE, MIN_CONTRAST_NORMAL};
+use super::generate::Hsl;
+
+/// Page colour of a print-safe theme
+pub const PAGE: &str = "#FFFFFF";
+
+/// Panel colour of a print-safe theme, light enough to print as a faint tint
+pub const SURFACE: &str = "#F5F5F5";
+
+/// Contrast body text and headings are held to (WCAG AAA)
+const MIN_CONTRAST_TEXT: f64 = 7.0;
+
+/// Thinnest section rule of a print-safe theme, in points
+pub const MIN_SEPARATOR_THICKNESS: f32 = 2.0;
+
+/// Lightest bold weight for headings in a print-safe theme
+pub const MIN_HEADING_WEIGHT: u16 = 700;
+
+/// Greyscale, high-contrast variant of `colors`.
+#[must_use]
+pub fn print_safe(colors: &ColorTheme) -> ColorTheme {
+    let grey = |hex: &str, minimum| greyscale(hex).darkened_to(SURFACE, minimum);
+    let heading = |hex: &Option<String>| hex.as_deref().map(|hex| grey(hex, MIN_CONTRAST_TEXT));
+
+    ColorTheme {
+        primary: grey(&colors.primary, MIN_CONTRAST_TEXT),
+        secondary: grey(&colors.secondary, MIN_CONTRAST_NORMAL),
+        // Section rules and links
+        accent: grey(&colors.accent, MIN_CONTRAST_NORMAL),
+        text: grey(&colors.text, MIN_CONTRAST_TEXT),
+        muted: grey(&colors.muted, MIN_CONTRAST_NORMAL),
+        background: PAGE.to_string(),
+        surface: SURFACE.to_string(),
+        border: grey(&colors.border, MIN_CONTRAST_LARGE),
+        h1_color: heading(&colors.h1_color),
+        h2_color: heading(&colors.h2_color),
+        h3_color: heading(&colors.h3_color),
+    }
+}
+
+/// The grey with the luminance of `hex`, or black if `hex` is not a colour.
+fn greyscale(hex: &str) -> Hsl {
+    let level = relative_luminance(hex).map_or(0.0, |luminance| {
+        // Back from linear light to an sRGB channel value
+        if luminance <= 0.003_130_8 {
+            luminance * 12.92
+        } else {
+            1.055 * luminance.powf(1.0 / 2.4) - 0.055
+        }
+    });
+    Hsl::grey(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::themes::{contrast, Theme};
+
+    fn is_grey(hex: &str) -> bool {
+        hex.len() == 7 && hex[1..3] == hex[3..5] && hex[3..5] == hex[5..7]
+    }
+
+    #[test]
+    fn test_print_safe_themes_are_grey_and_readable() {
+        for name in ["classic", "modern", "sharp"] {
+            let colors = print_safe(&ColorTheme::load(name).expect("Theme loads"));
+            for field in [
+                "primary",
+                "secondary",
+                "accent",
+                "text",
+                "muted",
+                "background",
+                "surface",
+                "border",
+                "h1",
+                "h2",
+                "h3",
+            ] {
+                let hex = colors.hex(field).expect("Colour set");
+                assert!(is_grey(hex), "{name} {field}: {hex}");
+            }
+            // Every pair passes, including the ones the colour theme fails
+            for pair in contrast::check(&colors) {
+                assert!(pair.passes(), "{name}: {pair}");
+            }
+            assert_eq!(colors.background, PAGE);
+        }
+    }
+
+    #[test]
+    fn test_theme_print_safe_bolds_headings_and_thickens_rules() {
+        let mut theme = Theme::new("modern", "modern").expect("Theme loads");
+        theme.font.header.weight_bold = 600;
+        theme.layout.separator_thickness = Some(0.5);
+
+        let print = theme.print_safe();
+        assert_eq!(print.font.header.weight_bold, MIN_HEADING_WEIGHT);
+        assert!(
+            (print.layout.get_separator_thickness() - MIN_SEPARATOR_THICKNESS).abs() < f32::EPSILON
+        );
+        assert!(is_grey(&print.color.primary));
+
+        // Heavier settings are kept
+        theme.font.header.weight_bold = 800;
+        theme.layout.separator_thickness = Some(3.0);
+        let print = theme.print_safe();
+        assert_eq!(print.font.header.weight_bold, 800);
+        assert!(print.layout.get_separator_thickness() >= 3.0);
+    }
+
+    #[test]
+    fn test_greyscale_keeps_luminance() {
+        assert_eq!(greyscale("#FFFFFF").to_hex(), "#FFFFFF");
+        assert_eq!(greyscale("#000").to_hex(), "#000000");
+        assert_eq!(greyscale("#767676").to_hex(), "#767676");
+        assert_eq!(greyscale("not a colour").to_hex(), "#000000");
+    }
+}
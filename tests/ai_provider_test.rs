@@ -0,0 +1,87 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Writes a global config with an offline profile into `config_dir`.
+fn write_config(config_dir: &Path, ai: &str) {
+    fs::write(config_dir.join("config.yaml"), ai).expect("Failed to write config");
+}
+
+fn tailor(config_dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.env("CV_CHECK_CONFIG", config_dir.join("config.yaml"))
+        .env("CV_CHECK_NO_OPEN", "1")
+        .env_remove("AI_PROFILE")
+        .args([
+            "tailor",
+            "-c",
+            "examples/cv.md",
+            "-j",
+            "examples/jd-example.pdf",
+            "-F",
+            "md",
+        ]);
+    cmd
+}
+
+#[test]
+fn test_tailor_offline_with_mock_profile() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_config(
+        temp_dir.path(),
+        "ai:\n  profiles:\n    offline:\n      provider: mock\n",
+    );
+    let output = temp_dir.path().join("tailored.md");
+
+    tailor(temp_dir.path())
+        .args(["--profile", "offline", "-o"])
+        .arg(&output)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Provider: mock"));
+
+    let tailored = fs::read_to_string(&output).expect("Tailored CV exists");
+    assert!(tailored.contains("Example Corp"));
+    assert!(tailored.contains("offline mock provider"));
+}
+
+#[test]
+fn test_tailor_default_profile_with_fixture_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let reply = include_str!("../src/ai/fixtures/tailored_cv.json")
+        .replace("Example Corp", "Fixture Industries");
+    fs::write(temp_dir.path().join("reply.json"), reply).expect("Failed to write fixture");
+    // The fixture path is relative to the config file, not the working directory
+    write_config(
+        temp_dir.path(),
+        "ai:\n  default_profile: offline\n  profiles:\n    offline:\n      provider: mock\n      fixture: reply.json\n",
+    );
+    let output = temp_dir.path().join("tailored.md");
+
+    tailor(temp_dir.path())
+        .arg("-o")
+        .arg(&output)
+        .arg("-q")
+        .assert()
+        .success();
+
+    let tailored = fs::read_to_string(&output).expect("Tailored CV exists");
+    assert!(tailored.contains("Fixture Industries"));
+}
+
+#[test]
+fn test_tailor_unknown_profile_fails() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_config(
+        temp_dir.path(),
+        "ai:\n  profiles:\n    offline:\n      provider: mock\n",
+    );
+
+    tailor(temp_dir.path())
+        .args(["--profile", "cloud", "-q"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown profile 'cloud'"));
+}
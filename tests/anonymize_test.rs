@@ -0,0 +1,87 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CV: &str = "---
+name: Jane Doe
+email: jane@example.com
+phone: +44 1234 567890
+location: London, UK
+github: janedoe
+linkedin: jane-doe
+photo: jane.jpg
+pronouns: she/her
+summary: Born 1 May 1990, she leads her team.
+---
+
+# Experience
+
+## Goldman Sachs (London)
+
+Jane Doe rebuilt the trading desk's reporting pipeline.
+";
+
+fn cv(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(dir).env("CV_CHECK_NO_OPEN", "1");
+    cmd
+}
+
+#[test]
+fn test_anonymized_build_leaves_source_alone() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("cv.md"), CV).expect("Failed to write CV");
+    fs::write(
+        temp_dir.path().join("blind.yaml"),
+        "name: Candidate 17\nemployers:\n  Goldman Sachs: Tier-1 bank\n",
+    )
+    .expect("Failed to write map");
+
+    cv(temp_dir.path())
+        .args(["build", "cv.md", "-F", "txt,html", "-o", "out"])
+        .args(["--anonymize", "--anonymize-map", "blind.yaml"])
+        .assert()
+        .success();
+
+    for output in ["out/cv.txt", "out/cv.html"] {
+        let content = fs::read_to_string(temp_dir.path().join(output)).expect("Output exists");
+        assert!(content.contains("Candidate 17"), "{output}");
+        assert!(content.contains("Tier-1 bank"), "{output}");
+        for personal in [
+            "Jane",
+            "jane@example.com",
+            "567890",
+            "London, UK",
+            "janedoe",
+            "jane-doe",
+            "Goldman",
+        ] {
+            assert!(!content.contains(personal), "{output} contains {personal}");
+        }
+    }
+
+    let source = fs::read_to_string(temp_dir.path().join("cv.md")).expect("Source exists");
+    assert_eq!(source, CV);
+}
+
+#[test]
+fn test_anonymize_map_errors() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("cv.md"), CV).expect("Failed to write CV");
+    fs::write(temp_dir.path().join("bad.yaml"), "employer: typo\n").expect("Failed to write map");
+
+    cv(temp_dir.path())
+        .args(["build", "cv.md", "-F", "txt", "--anonymize-map", "bad.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--anonymize"));
+
+    cv(temp_dir.path())
+        .args(["build", "cv.md", "-F", "txt", "--anonymize"])
+        .args(["--anonymize-map", "bad.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid anonymization map"));
+}
@@ -0,0 +1,93 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::io::Read;
+use tempfile::TempDir;
+
+fn project() -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::copy("examples/cv.md", temp_dir.path().join("cv.md")).expect("Failed to copy CV");
+    fs::copy(
+        "examples/cover-letter.md",
+        temp_dir.path().join("letter.md"),
+    )
+    .expect("Failed to copy letter");
+    temp_dir
+}
+
+#[test]
+fn test_bundle_rejects_missing_attachment() {
+    let project = project();
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args(["bundle", "--cv", "cv.md", "--attach", "missing.pdf"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing.pdf"));
+    assert!(!project.path().join("application.pdf").exists());
+}
+
+#[test]
+fn test_bundle_rejects_non_pdf_attachment() {
+    let project = project();
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args(["bundle", "--cv", "cv.md", "--attach", "letter.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Attachments must be PDF files"));
+}
+
+#[test]
+fn test_bundle_merges_letter_and_cv() {
+    let project = project();
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args(["bundle", "--cv", "cv.md", "--letter", "letter.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cover Letter"))
+        .stdout(predicate::str::contains("Curriculum Vitae"));
+
+    let merged = lopdf::Document::load(project.path().join("application.pdf"))
+        .expect("Bundle is a valid PDF");
+    assert!(merged.get_pages().len() >= 2);
+}
+
+#[test]
+fn test_bundle_zip_includes_text_versions() {
+    let project = project();
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args([
+            "bundle",
+            "--cv",
+            "cv.md",
+            "--letter",
+            "letter.md",
+            "--zip",
+            "-o",
+            "out/pack.zip",
+        ])
+        .assert()
+        .success();
+
+    let file = fs::File::open(project.path().join("out/pack.zip")).expect("Zip exists");
+    let mut archive = zip::ZipArchive::new(file).expect("Valid zip");
+    let names: Vec<String> = archive.file_names().map(String::from).collect();
+    for name in ["letter.pdf", "letter.txt", "cv.pdf", "cv.txt"] {
+        assert!(names.contains(&name.to_string()), "Missing {name}");
+    }
+
+    let mut text = String::new();
+    archive
+        .by_name("cv.txt")
+        .expect("CV text exists")
+        .read_to_string(&mut text)
+        .expect("Readable text");
+    assert!(!text.is_empty());
+}
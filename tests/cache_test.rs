@@ -0,0 +1,122 @@
+use assert_cmd::Command;
+use chrono::NaiveDate;
+use cv_check::build::BuildJob;
+use cv_check::cli::cache::key;
+use cv_check::parser::Document;
+use cv_check::themes::Theme;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CV: &str = "---\nname: Jane Doe\nemail: jane@example.com\n---\n\n# Experience\nWork\n";
+
+fn build(dir: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(dir)
+        .env("CV_CHECK_NO_OPEN", "1")
+        .arg("build")
+        .args(args)
+        .assert()
+        .success()
+}
+
+#[test]
+fn test_unchanged_output_is_not_rebuilt() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("cv.md"), CV).expect("Failed to write CV");
+    let args = ["cv.md", "-F", "txt", "-o", "out/cv.txt"];
+
+    build(temp_dir.path(), &args).stdout(predicate::str::contains("unchanged").not());
+    assert!(temp_dir.path().join("out/.cv-cache/cv.txt.json").exists());
+
+    build(temp_dir.path(), &args).stdout(predicate::str::contains("(unchanged"));
+
+    // --force renders again
+    let mut forced = args.to_vec();
+    forced.push("--force");
+    build(temp_dir.path(), &forced).stdout(predicate::str::contains("unchanged").not());
+
+    // So does a change to the document, a theme or a deleted output
+    fs::write(
+        temp_dir.path().join("cv.md"),
+        CV.replace("Work", "More work"),
+    )
+    .expect("Failed to write CV");
+    build(temp_dir.path(), &args).stdout(predicate::str::contains("unchanged").not());
+
+    let mut themed = args.to_vec();
+    themed.extend(["--color-theme", "sharp"]);
+    build(temp_dir.path(), &themed).stdout(predicate::str::contains("unchanged").not());
+
+    fs::remove_file(temp_dir.path().join("out/cv.txt")).expect("Failed to remove output");
+    build(temp_dir.path(), &themed).stdout(predicate::str::contains("unchanged").not());
+    assert!(temp_dir.path().join("out/cv.txt").exists());
+}
+
+#[test]
+fn test_batch_reports_unchanged_outputs() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("cv.md"), CV).expect("Failed to write CV");
+    let args = ["cv.md", "-F", "txt,html", "-o", "out"];
+
+    build(temp_dir.path(), &args).stdout(predicate::str::contains("unchanged").not());
+    build(temp_dir.path(), &args).stdout(predicate::str::contains(
+        "2 outputs generated successfully! (2 unchanged)",
+    ));
+}
+
+#[test]
+fn test_single_and_batch_builds_share_the_cache() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("cv.md"), CV).expect("Failed to write CV");
+
+    let single = ["cv.md", "-F", "txt", "-o", "out/cv.txt", "--anonymize"];
+    build(temp_dir.path(), &single).stdout(predicate::str::contains("unchanged").not());
+    build(
+        temp_dir.path(),
+        &["cv.md", "-F", "txt,html", "-o", "out", "--anonymize"],
+    )
+    .stdout(predicate::str::contains(
+        "2 outputs generated successfully! (1 unchanged)",
+    ));
+    build(temp_dir.path(), &single).stdout(predicate::str::contains("(unchanged"));
+}
+
+#[test]
+fn test_undated_cover_letter_is_keyed_on_the_build_date() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("letter.md");
+    let job = BuildJob {
+        input: input.clone(),
+        format: "pdf".to_string(),
+        output: temp_dir.path().join("letter.pdf"),
+        font_theme: "modern".to_string(),
+        color_theme: "modern".to_string(),
+        layout: None,
+        template: None,
+        dpi: 144,
+        pdf_a: false,
+        print_safe: false,
+        review: None,
+        max_pages: None,
+        anonymize: None,
+    };
+    let theme = Theme::new("modern", "modern").expect("Theme loads");
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).expect("Valid date");
+    let next_week = NaiveDate::from_ymd_opt(2026, 10, 25).expect("Valid date");
+    let keys = |letter: &str| {
+        fs::write(&input, letter).expect("Failed to write letter");
+        let doc = Document::from_file(&input).expect("Letter parses");
+        [today, next_week].map(|date| key(&job, &doc, &theme, date).expect("Key computes"))
+    };
+
+    // The letter prints today's date, so it is rendered again next week
+    let letter = "---\nname: Jane Doe\nemail: jane@example.com\nrecipient:\n  company: Acme\n---\nDear Sir\n";
+    let [now, later] = keys(letter);
+    assert_ne!(now, later);
+
+    // A fixed date is printed instead, and the output stays valid
+    let [now, later] = keys(&letter.replace("recipient:", "date: 1 March 2026\nrecipient:"));
+    assert_eq!(now, later);
+}
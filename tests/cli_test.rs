@@ -1,4 +1,6 @@
-use cv_check::cli::{BuildOptions, CvGenerator};
+use cv_check::cli::theme_list::ThemeSections;
+use cv_check::cli::{BuildOptions, CacheMode, CvGenerator};
+use cv_check::render::RenderOptions;
 use std::fs;
 use std::path::Path;
 use tempfile::tempdir;
@@ -21,9 +23,15 @@ fn test_build_options_creation() {
         input: input_path,
         font_theme: "modern",
         color_theme: "classic",
+        layout: None,
         output: Some(output_path),
         format: "pdf",
         template: None,
+        render: RenderOptions::default(),
+        max_pages: None,
+        fail_on_warnings: false,
+        cache: CacheMode::default(),
+        anonymize: None,
         verbose: false,
         quiet: true,
     };
@@ -71,25 +79,42 @@ fn test_new_letter_creates_file() {
     // Expected to fail until template is created
 }
 
+fn sections(fonts: bool, colors: bool, layouts: bool) -> ThemeSections {
+    ThemeSections {
+        fonts,
+        colors,
+        layouts,
+    }
+}
+
 #[test]
 fn test_list_themes_fonts_only() {
-    // Function no longer returns Result, so just call it
-    CvGenerator::list_themes(true, false);
-    // Test passes if no panic occurs
+    CvGenerator::list_themes(sections(true, false, false)).expect("Themes list");
 }
 
 #[test]
 fn test_list_themes_colors_only() {
-    // Function no longer returns Result, so just call it
-    CvGenerator::list_themes(false, true);
-    // Test passes if no panic occurs
+    CvGenerator::list_themes(sections(false, true, false)).expect("Themes list");
 }
 
 #[test]
 fn test_list_themes_both() {
-    // Function no longer returns Result, so just call it
-    CvGenerator::list_themes(true, true);
-    // Test passes if no panic occurs
+    CvGenerator::list_themes(sections(true, true, false)).expect("Themes list");
+}
+
+#[test]
+fn test_list_themes_all() {
+    CvGenerator::list_themes(ThemeSections::ALL).expect("Themes list");
+}
+
+#[test]
+fn test_themes_json_lists_selected_sections() {
+    let json = CvGenerator::themes_json(sections(false, true, false)).expect("Themes list");
+    let listing: serde_json::Value = serde_json::from_str(&json).expect("Valid JSON");
+    assert!(listing.get("fonts").is_none());
+    assert_eq!(listing["colors"][0]["name"], "classic");
+    assert_eq!(listing["colors"][0]["palette"][0]["role"], "primary");
+    assert_eq!(listing["colors"][0]["palette"][0]["hex"], "#2C3E50");
 }
 
 #[test]
@@ -142,9 +167,15 @@ fn test_build_with_parent_directory_creation() {
         input: &input_path,
         font_theme: "modern",
         color_theme: "modern",
+        layout: None,
         output: Some(&nested_output),
         format: "pdf",
         template: None,
+        render: RenderOptions::default(),
+        max_pages: None,
+        fail_on_warnings: false,
+        cache: CacheMode::default(),
+        anonymize: None,
         verbose: false,
         quiet: true, // But quiet mode should suppress auto-open
     };
@@ -170,9 +201,15 @@ fn test_build_with_invalid_input_stem() {
         input: &input,
         font_theme: "modern",
         color_theme: "modern",
+        layout: None,
         output: None,
         format: "html", // Test non-pdf format
         template: None,
+        render: RenderOptions::default(),
+        max_pages: None,
+        fail_on_warnings: false,
+        cache: CacheMode::default(),
+        anonymize: None,
         verbose: false,
         quiet: true, // Prevent auto-opening in tests
     };
@@ -195,9 +232,15 @@ fn test_open_file_auto_open_disabled() {
         input: &input,
         font_theme: "modern",
         color_theme: "modern",
+        layout: None,
         output: Some(&output_path),
         format: "pdf",
         template: None,
+        render: RenderOptions::default(),
+        max_pages: None,
+        fail_on_warnings: false,
+        cache: CacheMode::default(),
+        anonymize: None,
         verbose: false,
         quiet: true, // Prevent auto-opening in tests
     };
@@ -226,9 +269,15 @@ color_theme: classic
         input: &input_file,
         font_theme: "modern",
         color_theme: "classic",
+        layout: None,
         output: None, // Test default output path generation
         format: "pdf",
         template: None,
+        render: RenderOptions::default(),
+        max_pages: None,
+        fail_on_warnings: false,
+        cache: CacheMode::default(),
+        anonymize: None,
         verbose: false,
         quiet: true,
     };
@@ -0,0 +1,23 @@
+//! Helpers shared by the CLI integration tests
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+/// The `cv` binary, run in `dir` without opening its outputs
+pub fn cv(dir: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(dir.path()).env("CV_CHECK_NO_OPEN", "1");
+    cmd
+}
+
+/// Writes a small `cv.md` to `dir`, with `frontmatter` appended to its contact details
+pub fn write_cv(dir: &TempDir, frontmatter: &str) {
+    fs::write(
+        dir.path().join("cv.md"),
+        format!(
+            "---\nname: Jane Doe\nemail: jane@example.com\nphone: +44 20 7946 0000\ngithub: janedoe\n{frontmatter}---\n# Experience\n\n## Acme Corp\n\n### Engineer, 2020 - Present\n"
+        ),
+    )
+    .expect("Failed to write CV");
+}
@@ -1,5 +1,7 @@
 use approx::assert_abs_diff_eq;
-use cv_check::config::{DocumentMetadata, GlobalConfig, LayoutOptions, Margins, RecipientInfo};
+use cv_check::config::{
+    AiConfig, DocumentMetadata, GlobalConfig, LayoutOptions, Margins, RecipientInfo,
+};
 use serde_yaml::Value;
 use std::collections::HashMap;
 
@@ -59,6 +61,7 @@ fn test_global_config_load_with_config_file() {
         custom_themes_dir: Some("/custom/themes".to_string()),
         output_dir: Some("./custom_output".to_string()),
         auto_open: Some(false),
+        ai: AiConfig::default(),
     };
 
     // Test serialization
@@ -91,10 +94,16 @@ fn test_document_metadata_basic() {
         website: Some("https://johndoe.dev".to_string()),
         font_theme: "modern".to_string(),
         color_theme: "classic".to_string(),
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions::default(),
         recipient: None,
         date: None,
         subject: None,
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: HashMap::new(),
     };
 
@@ -132,10 +141,16 @@ fn test_document_metadata_with_recipient() {
         website: None,
         font_theme: "classic".to_string(),
         color_theme: "sharp".to_string(),
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions::default(),
         recipient: Some(recipient),
         date: Some("2025-07-17".to_string()),
         subject: Some("Application for Software Engineer Position".to_string()),
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: HashMap::new(),
     };
 
@@ -186,10 +201,16 @@ fn test_document_metadata_with_custom_fields() {
         website: None,
         font_theme: "sharp".to_string(),
         color_theme: "modern".to_string(),
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions::default(),
         recipient: None,
         date: None,
         subject: None,
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: custom_fields,
     };
 
@@ -217,6 +238,8 @@ fn test_document_metadata_serialization() {
         website: Some("https://test.example.com".to_string()),
         font_theme: "modern".to_string(),
         color_theme: "classic".to_string(),
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions {
             columns: 2,
             margins: Margins {
@@ -230,6 +253,10 @@ fn test_document_metadata_serialization() {
         recipient: None,
         date: None,
         subject: None,
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: HashMap::new(),
     };
 
@@ -330,10 +357,16 @@ fn test_document_metadata_empty_name_email() {
         website: None,
         font_theme: "modern".to_string(),
         color_theme: "modern".to_string(),
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions::default(),
         recipient: None,
         date: None,
         subject: None,
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: HashMap::new(),
     };
 
@@ -378,6 +411,7 @@ fn test_global_config_partial() {
         custom_themes_dir: None,
         output_dir: None,
         auto_open: None,
+        ai: AiConfig::default(),
     };
 
     assert!(partial_config.default_font_theme.is_none());
@@ -402,10 +436,16 @@ fn test_document_metadata_invalid_theme_names() {
         website: None,
         font_theme: "nonexistent-theme".to_string(),
         color_theme: String::new(), // Empty theme name
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions::default(),
         recipient: None,
         date: None,
         subject: None,
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: HashMap::new(),
     };
 
@@ -447,10 +487,16 @@ fn test_custom_fields_with_nested_values() {
         website: None,
         font_theme: "modern".to_string(),
         color_theme: "modern".to_string(),
+        theme_overrides: None,
+        icons: None,
         layout: LayoutOptions::default(),
         recipient: None,
         date: None,
         subject: None,
+        keywords: Vec::new(),
+        lang: None,
+        header: None,
+        footer: None,
         custom: custom_fields,
     };
 
@@ -30,7 +30,6 @@ mod tests {
         assert_eq!(layout::font_sizes::MEDIUM, "12pt");
         assert_eq!(layout::font_sizes::SUBSECTION, "14pt");
         assert_eq!(layout::font_sizes::SECTION, "16pt");
-        assert_eq!(layout::font_sizes::TITLE, "36pt");
         assert_eq!(layout::font_sizes::NAME, "28pt");
     }
 }
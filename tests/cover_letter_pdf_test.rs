@@ -20,6 +20,8 @@ fn test_cover_letter_typst_generation() {
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: Some(RecipientInfo {
                 name: Some("Sarah Johnson".to_string()),
                 title: Some("Engineering Manager".to_string()),
@@ -29,6 +31,10 @@ fn test_cover_letter_typst_generation() {
             date: None,
             subject: Some("Senior Software Engineer Position - Job ID #SE2024".to_string()),
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: "Dear Sarah,\n\nI am writing to express my strong interest in the Senior Software Engineer position at Innovation Labs Inc.\n\nSincerely,\nJane Smith".to_string(),
@@ -39,13 +45,15 @@ fn test_cover_letter_typst_generation() {
         color: cv_check::themes::color::ColorTheme::load("modern")
             .expect("Failed to load color theme"),
         font: cv_check::themes::font::FontTheme::load("modern").expect("Failed to load font theme"),
+        layout: cv_check::themes::layout::LayoutTheme::load("modern")
+            .expect("Failed to load layout theme"),
     };
 
     // Access the test method that's exposed for testing
     let source = renderer.generate_typst_source_for_testing(&doc, &theme);
 
     // Verify document setup
-    assert!(source.contains("#set document(title: \"Jane Smith\", author: \"Jane Smith\")"));
+    assert!(source.contains("#set document(title: \"Jane Smith\", author: \"Jane Smith\","));
     assert!(source.contains("#set page(paper: \"a4\""));
 
     // Verify sender's contact info is present
@@ -95,6 +103,8 @@ fn test_cover_letter_without_optional_fields() {
             website: None,
             font_theme: "classic".to_string(),
             color_theme: "classic".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: Some(RecipientInfo {
                 name: Some("Hiring Manager".to_string()),
                 title: None,
@@ -104,6 +114,10 @@ fn test_cover_letter_without_optional_fields() {
             date: None,
             subject: None,
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: "Dear Hiring Manager,\n\nI am interested in the position.\n\nSincerely,\nJohn Doe"
@@ -116,6 +130,8 @@ fn test_cover_letter_without_optional_fields() {
             .expect("Failed to load color theme"),
         font: cv_check::themes::font::FontTheme::load("classic")
             .expect("Failed to load font theme"),
+        layout: cv_check::themes::layout::LayoutTheme::load("classic")
+            .expect("Failed to load layout theme"),
     };
 
     // Access the test method that's exposed for testing
@@ -129,7 +145,8 @@ fn test_cover_letter_without_optional_fields() {
     assert!(!source.contains("Innovation Labs"));
 
     // Verify classic font theme
-    assert!(source.contains("#set text(font: \"Georgia\""));
+    assert!(source.contains("#set text(font: \"Times New Roman\""));
+    assert!(source.contains("#text(font: \"Georgia\""));
 }
 
 #[test]
@@ -147,6 +164,8 @@ fn test_cover_letter_multiline_address() {
             website: None,
             font_theme: "sharp".to_string(),
             color_theme: "sharp".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: Some(RecipientInfo {
                 name: Some("HR Department".to_string()),
                 title: None,
@@ -156,6 +175,10 @@ fn test_cover_letter_multiline_address() {
             date: None,
             subject: None,
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content:
@@ -168,6 +191,8 @@ fn test_cover_letter_multiline_address() {
         color: cv_check::themes::color::ColorTheme::load("sharp")
             .expect("Failed to load color theme"),
         font: cv_check::themes::font::FontTheme::load("sharp").expect("Failed to load font theme"),
+        layout: cv_check::themes::layout::LayoutTheme::load("sharp")
+            .expect("Failed to load layout theme"),
     };
 
     // Access the test method that's exposed for testing
@@ -183,7 +208,8 @@ fn test_cover_letter_multiline_address() {
     assert!(source.contains("#linebreak()"));
 
     // Verify sharp font theme
-    assert!(source.contains("#set text(font: \"Montserrat\""));
+    assert!(source.contains("#set text(font: \"Roboto\""));
+    assert!(source.contains("#text(font: \"Montserrat\", tracking: -0.03em"));
 }
 
 #[test]
@@ -201,6 +227,8 @@ fn test_cover_letter_professional_formatting() {
             website: Some("https://writer.example.com".to_string()),
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: Some(RecipientInfo {
                 name: Some("Dr. Emily Chen".to_string()),
                 title: Some("Director of Engineering".to_string()),
@@ -210,6 +238,10 @@ fn test_cover_letter_professional_formatting() {
             date: None,
             subject: Some("Application for Technical Writer Position".to_string()),
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: r"Dear Dr. Chen,
@@ -232,6 +264,8 @@ Professional Writer".to_string(),
         color: cv_check::themes::color::ColorTheme::load("modern")
             .expect("Failed to load color theme"),
         font: cv_check::themes::font::FontTheme::load("modern").expect("Failed to load font theme"),
+        layout: cv_check::themes::layout::LayoutTheme::load("modern")
+            .expect("Failed to load layout theme"),
     };
 
     // Access the test method that's exposed for testing
@@ -277,6 +311,8 @@ fn test_cover_letter_without_recipient_info() {
             website: None,
             font_theme: "classic".to_string(),
             color_theme: "classic".to_string(),
+            theme_overrides: None,
+            icons: None,
             recipient: Some(RecipientInfo {
                 name: None,
                 title: None,
@@ -286,6 +322,10 @@ fn test_cover_letter_without_recipient_info() {
             date: None,
             subject: Some("Application for Software Developer Position".to_string()),
             layout: LayoutOptions::default(),
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: HashMap::new(),
         },
         content: "To Whom It May Concern,\n\nI am interested in the Software Developer position.\n\nSincerely,\nJohn Doe".to_string(),
@@ -297,6 +337,8 @@ fn test_cover_letter_without_recipient_info() {
             .expect("Failed to load color theme"),
         font: cv_check::themes::font::FontTheme::load("classic")
             .expect("Failed to load font theme"),
+        layout: cv_check::themes::layout::LayoutTheme::load("classic")
+            .expect("Failed to load layout theme"),
     };
 
     // Access the test method that's exposed for testing
@@ -309,5 +351,6 @@ fn test_cover_letter_without_recipient_info() {
     assert!(source.contains("Subject: Application for Software Developer Position"));
 
     // Verify classic font theme
-    assert!(source.contains("#set text(font: \"Georgia\""));
+    assert!(source.contains("#set text(font: \"Times New Roman\""));
+    assert!(source.contains("#text(font: \"Georgia\""));
 }
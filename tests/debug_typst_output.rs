@@ -15,10 +15,16 @@ fn create_test_document_with_content(content: &str) -> Document {
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             layout: cv_check::config::LayoutOptions::default(),
             recipient: None,
             date: None,
             subject: None,
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: std::collections::HashMap::new(),
         },
         content: content.to_string(),
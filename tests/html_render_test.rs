@@ -0,0 +1,163 @@
+use cv_check::parser::Document;
+use cv_check::render::html::HtmlRenderer;
+use cv_check::render::RenderEngine;
+use cv_check::themes::Theme;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+fn render_html(content: &str, template: Option<&Path>) -> String {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let output_path = temp_dir.path().join("cv.html");
+
+    let doc = Document::from_string(content, &PathBuf::from("test.md"))
+        .expect("Failed to parse document");
+    let theme = Theme::new("modern", "classic").expect("Failed to load theme");
+    let renderer = HtmlRenderer::new(template).expect("Failed to create renderer");
+
+    renderer
+        .render(&doc, &theme, &output_path)
+        .expect("Failed to render HTML");
+    fs::read_to_string(&output_path).expect("Failed to read output file")
+}
+
+const CV: &str = r#"---
+name: Jane Doe
+email: jane@example.com
+phone: "+44 7700 900123"
+location: London, UK
+github: janedoe
+linkedin: janedoe
+---
+# Experience
+
+## Senior Engineer - Acme
+
+- Built things
+
+<!-- pagebreak -->
+
+## Engineer - Initech
+
+- Fixed things
+
+# Education
+
+Some university
+"#;
+
+#[test]
+fn test_html_semantic_sections() {
+    let html = render_html(CV, None);
+
+    assert_eq!(html.matches("<section class=\"cv-section\">").count(), 2);
+    assert_eq!(html.matches("</section>").count(), 2);
+    assert_eq!(html.matches("<article class=\"cv-entry\">").count(), 2);
+    assert_eq!(html.matches("</article>").count(), 2);
+    assert!(html.contains("<div class=\"page-break\"></div>"));
+    assert!(html.contains("<header class=\"cv-header\">"));
+    assert!(html.contains("<address class=\"contact\">"));
+    assert!(html.contains("href=\"tel:+447700900123\""));
+}
+
+#[test]
+fn test_html_json_ld_person() {
+    let html = render_html(CV, None);
+
+    assert!(html.contains("<script type=\"application/ld+json\">"));
+    assert!(html.contains("\"@type\": \"Person\""));
+    assert!(html.contains("\"name\": \"Jane Doe\""));
+    assert!(html.contains("\"addressLocality\": \"London, UK\""));
+    assert!(html.contains("https://github.com/janedoe"));
+}
+
+#[test]
+fn test_html_json_ld_cannot_close_script() {
+    let html = render_html(
+        "---\nname: \"</script><b>x\"\nemail: a@example.com\n---\n",
+        None,
+    );
+
+    assert!(html.contains("<\\/script><b>x"));
+    assert!(html.contains("<h1>&lt;/script&gt;&lt;b&gt;x</h1>"));
+}
+
+#[test]
+fn test_html_theme_variables_and_print_styles() {
+    let html = render_html(CV, None);
+    let theme = Theme::new("modern", "classic").expect("Failed to load theme");
+
+    assert!(html.contains(&format!("--primary: {};", theme.color.primary)));
+    assert!(html.contains(&format!("--font-body: \"{}\";", theme.font.body.family)));
+    assert!(html.contains("@media print"));
+    assert!(html.contains("@page"));
+}
+
+#[test]
+fn test_html_font_theme_variables() {
+    let html = render_html(CV, None);
+
+    // Modern fonts: tracked Inter headings over Open Sans with a 600 bold
+    assert!(html.contains("--font-header-letter-spacing: -0.02em;"));
+    assert!(html.contains("--font-body-weight-bold: 600;"));
+    assert!(html.contains("--font-body-line-height: 1.5;"));
+    assert!(html.contains("--font-header-size-name: 28pt;"));
+    assert!(html.contains("font-size: var(--font-header-size-section);"));
+    assert!(html.contains("line-height: var(--font-body-line-height);"));
+}
+
+#[test]
+fn test_html_custom_template_slots() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let template_path = temp_dir.path().join("page.html");
+    fs::write(
+        &template_path,
+        "<html><title>{{title}}</title><div>{{ header }}</div><nav>{{contact}}</nav><main>{{body}}</main>{{unknown}}</html>",
+    )
+    .expect("Failed to write template");
+
+    let html = render_html(CV, Some(&template_path));
+
+    assert!(html.starts_with("<html><title>Jane Doe - CV</title>"));
+    assert!(html.contains("<h1>Jane Doe</h1>"));
+    assert!(html.contains("<nav>        <address class=\"contact\">"));
+    assert!(html.contains("Senior Engineer - Acme"));
+    assert!(html.contains("{{unknown}}"));
+    assert!(!html.contains("<!DOCTYPE html>"));
+}
+
+#[test]
+fn test_html_cover_letter_title() {
+    let html = render_html(
+        "---\nname: Jane Doe\nemail: jane@example.com\nrecipient:\n  name: Hiring Manager\n---\nDear Hiring Manager,\n",
+        None,
+    );
+
+    assert!(html.contains("<title>Jane Doe - Cover Letter</title>"));
+    assert!(html.contains("<p>Dear Hiring Manager,</p>"));
+}
+
+#[test]
+fn test_html_page_header_and_footer() {
+    let html = render_html(
+        "---\nname: Jane \"JD\" Doe\nemail: jane@example.com\nheader:\n  left: \"{name}\"\n  first_page: false\nfooter:\n  center: Confidential\n  right: \"Page {page} of {pages}\"\n---\n# Experience\n",
+        None,
+    );
+
+    assert!(html.contains(
+        "@top-left { content: \"Jane \\\"JD\\\" Doe\"; font-size: 9pt; color: var(--muted); }"
+    ));
+    assert!(html.contains("@bottom-center { content: \"Confidential\";"));
+    assert!(
+        html.contains("@bottom-right { content: \"Page \" counter(page) \" of \" counter(pages);")
+    );
+    assert!(html.contains("@page :first {\n            @top-left { content: none; }\n        }"));
+    assert!(!html.contains("@bottom-right { content: none; }"));
+}
+
+#[test]
+fn test_html_without_header_has_no_margin_boxes() {
+    let html = render_html(CV, None);
+    assert!(!html.contains("@top-left"));
+    assert!(!html.contains("{{page_css}}"));
+}
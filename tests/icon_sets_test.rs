@@ -0,0 +1,59 @@
+mod common;
+
+use common::{cv, write_cv};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn build(dir: &TempDir, format: &str, output: &str) -> String {
+    cv(dir)
+        .args(["build", "cv.md", "-F", format, "-o", output])
+        .assert()
+        .success();
+    fs::read_to_string(dir.path().join(output)).expect("Output exists")
+}
+
+#[test]
+fn test_svg_icons_from_frontmatter() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "icons: svg\n");
+
+    let source = build(&temp_dir, "typst", "cv.typ");
+    assert!(source.contains("#box(baseline: 0.15em, image(bytes(\"<svg"));
+    assert!(!source.contains("FontAwesome"));
+
+    let html = build(&temp_dir, "html", "cv.html");
+    assert!(html.contains("<span class=\"phone\"><svg"));
+    assert!(html.contains("stroke=\"currentColor\""));
+}
+
+#[test]
+fn test_text_labels_and_theme_icon_set() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "icons: none\n");
+
+    let source = build(&temp_dir, "typst", "cv.typ");
+    assert!(source.contains("Phone: +44 20 7946 0000"));
+    assert!(source.contains("Email: jane\\@example.com"));
+
+    let html = build(&temp_dir, "html", "cv.html");
+    assert!(html.contains("<span class=\"github\">GitHub: <a"));
+
+    // A theme's icon set applies unless the frontmatter picks another
+    write_cv(&temp_dir, "theme_overrides:\n  icons: fontawesome6\n");
+    let source = build(&temp_dir, "typst", "cv.typ");
+    assert!(source.contains("#text(font: \"Font Awesome 6 Brands\")"));
+    assert!(source.contains("#text(font: \"Font Awesome 6 Free\", weight: 900)"));
+}
+
+#[test]
+fn test_unknown_icon_set_is_rejected() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "icons: emoji\n");
+
+    cv(&temp_dir)
+        .args(["build", "cv.md", "-F", "typst", "-o", "cv.typ"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("emoji"));
+}
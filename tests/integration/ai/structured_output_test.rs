@@ -2,7 +2,7 @@
 
 use cv_check::ai::schema_gen::JsonSchema;
 use cv_check::ai::schemas::OptimizedExperience;
-use cv_check::ai::AIClient;
+use cv_check::ai::{AIClient, ChatRequest};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -41,50 +41,20 @@ async fn test_structured_output_simple() {
     };
 
     // Create a simple request that should return structured data
-    let model = std::env::var("AI_MODEL").expect("AI_MODEL not set");
-    let mut request = openai_api_rs::v1::chat_completion::ChatCompletionRequest::new(
-        model,
-        vec![
-            openai_api_rs::v1::chat_completion::ChatCompletionMessage {
-                role: openai_api_rs::v1::chat_completion::MessageRole::system,
-                content: openai_api_rs::v1::chat_completion::Content::Text(
-                    "You are a book reviewer. Extract information about books and provide structured summaries.".to_string()
-                ),
-                name: None,
-                tool_calls: None,
-                tool_call_id: None,
-            },
-            openai_api_rs::v1::chat_completion::ChatCompletionMessage {
-                role: openai_api_rs::v1::chat_completion::MessageRole::user,
-                content: openai_api_rs::v1::chat_completion::Content::Text(
-                    "Please provide a summary of '1984' by George Orwell.".to_string()
-                ),
-                name: None,
-                tool_calls: None,
-                tool_call_id: None,
-            },
-        ],
-    );
-
-    // Add structured output format
-    let response_format = BookSummary::response_format("book_summary");
-    request = request.response_format(response_format);
+    let request = ChatRequest {
+        system: "You are a book reviewer. Extract information about books and provide structured summaries.".to_string(),
+        user: "Please provide a summary of '1984' by George Orwell.".to_string(),
+        response_format: Some(BookSummary::response_format("book_summary")),
+    };
 
     // Make the API call
-    let response = client
-        .chat_completion_raw(request)
+    let content = client
+        .complete(&request)
         .await
         .expect("API call should succeed");
 
-    // Extract and parse the response
-    let content = response
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.as_ref())
-        .expect("Response should contain content");
-
     let book_summary: BookSummary =
-        serde_json::from_str(content).expect("Response should deserialize to BookSummary");
+        serde_json::from_str(&content).expect("Response should deserialize to BookSummary");
 
     // Validate the response
     assert!(
@@ -119,51 +89,21 @@ async fn test_structured_output_nested() {
         relevance_score: 0.9,
     };
 
-    let model = std::env::var("AI_MODEL").expect("AI_MODEL not set");
-    let mut request = openai_api_rs::v1::chat_completion::ChatCompletionRequest::new(
-        model,
-        vec![
-            openai_api_rs::v1::chat_completion::ChatCompletionMessage {
-                role: openai_api_rs::v1::chat_completion::MessageRole::system,
-                content: openai_api_rs::v1::chat_completion::Content::Text(
-                    "You are an HR expert. Optimize the given work experience for a specific job."
-                        .to_string(),
-                ),
-                name: None,
-                tool_calls: None,
-                tool_call_id: None,
-            },
-            openai_api_rs::v1::chat_completion::ChatCompletionMessage {
-                role: openai_api_rs::v1::chat_completion::MessageRole::user,
-                content: openai_api_rs::v1::chat_completion::Content::Text(format!(
-                    "Optimize this experience for a Senior Developer role: {test_experience:?}"
-                )),
-                name: None,
-                tool_calls: None,
-                tool_call_id: None,
-            },
-        ],
-    );
-
-    // Add structured output format
-    let response_format = OptimizedExperience::response_format("optimized_experience");
-    request = request.response_format(response_format);
+    let request = ChatRequest {
+        system: "You are an HR expert. Optimize the given work experience for a specific job."
+            .to_string(),
+        user: format!("Optimize this experience for a Senior Developer role: {test_experience:?}"),
+        response_format: Some(OptimizedExperience::response_format("optimized_experience")),
+    };
 
     // Make the API call
-    let response = client
-        .chat_completion_raw(request)
+    let content = client
+        .complete(&request)
         .await
         .expect("API call should succeed");
 
-    // Extract and parse the response
-    let content = response
-        .choices
-        .first()
-        .and_then(|choice| choice.message.content.as_ref())
-        .expect("Response should contain content");
-
     let optimized: OptimizedExperience =
-        serde_json::from_str(content).expect("Response should deserialize to OptimizedExperience");
+        serde_json::from_str(&content).expect("Response should deserialize to OptimizedExperience");
 
     // Validate the response structure
     assert!(!optimized.title.is_empty());
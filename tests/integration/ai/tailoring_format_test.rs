@@ -348,8 +348,7 @@ fn parse_end_year(duration: &str) -> u32 {
         duration
             .split_whitespace()
             .filter_map(|word| word.parse::<u32>().ok())
-            .filter(|&year| (1900..=2100).contains(&year))
-            .next_back()
+            .rfind(|&year| (1900..=2100).contains(&year))
             .unwrap_or(0)
     }
 }
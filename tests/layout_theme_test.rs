@@ -0,0 +1,89 @@
+mod common;
+
+use common::{cv, write_cv};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn typst_source(dir: &TempDir, args: &[&str]) -> String {
+    cv(dir)
+        .args(["build", "cv.md", "-F", "typst", "-o", "cv.typ"])
+        .args(args)
+        .assert()
+        .success();
+    fs::read_to_string(dir.path().join("cv.typ")).expect("Output exists")
+}
+
+#[test]
+fn test_layout_follows_color_theme_by_default() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "");
+
+    let source = typst_source(&temp_dir, &[]);
+    assert!(source.contains("#align(center)["));
+    assert!(source.contains("#v(2.5em)"));
+    assert!(source.contains("#line(length: 100%, stroke: 1pt + rgb(\"#FF6B35\"))"));
+    assert!(source.contains("Engineer, 2020 - Present"));
+
+    // The classic layout keeps its own spacing under modern colours
+    let source = typst_source(&temp_dir, &["--layout", "classic"]);
+    assert!(source.contains("#v(1.5em)"));
+    assert!(source.contains("stroke: 2pt + rgb(\"#FF6B35\")"));
+}
+
+#[test]
+fn test_compact_and_banner_layouts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "");
+
+    let source = typst_source(&temp_dir, &["--layout", "compact"]);
+    assert!(source.contains("#align(left)["));
+    assert!(source.contains("#line(length: 3em, stroke: 3pt"));
+    assert!(source.contains("#v(1.13em)"));
+    assert!(source.contains("Engineer#h(1fr)2020 - Present"));
+
+    let source = typst_source(&temp_dir, &["--layout", "banner", "-c", "classic"]);
+    assert!(source.contains("#block(width: 100%, fill: rgb(\"#2C3E50\"), inset: 1em)["));
+    assert!(source.contains("#set text(fill: rgb(\"#FAFAFA\"))"));
+
+    cv(&temp_dir)
+        .args(["build", "cv.md", "-F", "html", "--layout", "banner"])
+        .assert()
+        .success();
+    let page = fs::read_to_string(temp_dir.path().join("cv.html")).expect("Output exists");
+    assert!(page.contains("--header-fill: var(--primary);"));
+    assert!(page.contains("Engineer<span class=\"dates\">2020 - Present</span>"));
+}
+
+#[test]
+fn test_layout_from_theme_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "");
+    fs::write(
+        temp_dir.path().join("airy.yaml"),
+        "extends: classic\nseparator_style: none\ndensity: relaxed\n",
+    )
+    .expect("Failed to write theme");
+
+    let source = typst_source(&temp_dir, &["--layout", "airy.yaml"]);
+    assert!(!source.contains("#line("));
+    assert!(source.contains("#v(1.88em)"));
+
+    cv(&temp_dir)
+        .args(["build", "cv.md", "--layout", "grid"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown theme 'grid'"));
+}
+
+#[test]
+fn test_themes_lists_layouts() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    cv(&temp_dir)
+        .args(["themes", "--layouts"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Layout Themes:"))
+        .stdout(predicate::str::contains("banner"))
+        .stdout(predicate::str::contains("Font Themes:").not());
+}
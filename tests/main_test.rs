@@ -118,6 +118,26 @@ fn test_themes_colors_only() {
         .stdout(predicate::str::contains("Font Themes").not());
 }
 
+#[test]
+fn test_themes_help_lists_preview() {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args(["themes", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--preview"))
+        .stdout(predicate::str::contains("--gallery"));
+}
+
+#[test]
+fn test_build_help_lists_image_options() {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args(["build", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("png, svg"))
+        .stdout(predicate::str::contains("--dpi"));
+}
+
 #[test]
 fn test_check_valid_file() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -256,29 +276,37 @@ fn test_pdf_generation_with_actual_content() {
     assert!(output_file.exists(), "PDF should be created");
 
     // Since we can't easily extract text from PDF in tests, verify the generated Typst source
-    // which should have been saved in debug mode
-    let typst_debug_path = std::path::Path::new("/tmp/cv_debug/generated.typ");
-    if typst_debug_path.exists() {
-        let typst_content =
-            fs::read_to_string(typst_debug_path).expect("Failed to read debug Typst file");
-
-        // Verify all expected content is in the generated Typst file
-        for expected in &expected_content {
-            assert!(
-                typst_content.contains(expected),
-                "Generated Typst should contain '{expected}' from the markdown input"
-            );
-        }
-    } else {
-        // If no debug file, at least verify PDF is substantial
-        let pdf_size = fs::metadata(&output_file)
-            .expect("Failed to get PDF metadata")
-            .len();
+    let typst_file = temp_dir.path().join("test-output.typ");
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        example_path.to_str().expect("Invalid path"),
+        "--output",
+        typst_file.to_str().expect("Invalid path"),
+        "--format",
+        "typst",
+        "--quiet",
+    ])
+    .assert()
+    .success();
+
+    let typst_content = fs::read_to_string(&typst_file).expect("Failed to read Typst source");
+
+    // Verify all expected content is in the generated Typst file
+    for expected in &expected_content {
         assert!(
-            pdf_size > 15000,
-            "PDF with full CV content should be at least 15KB, but was only {pdf_size} bytes"
+            typst_content.contains(expected),
+            "Generated Typst should contain '{expected}' from the markdown input"
         );
     }
+
+    let pdf_size = fs::metadata(&output_file)
+        .expect("Failed to get PDF metadata")
+        .len();
+    assert!(
+        pdf_size > 15000,
+        "PDF with full CV content should be at least 15KB, but was only {pdf_size} bytes"
+    );
 }
 
 #[test]
@@ -417,3 +445,199 @@ email: test@example.com
     .failure()
     .stderr(predicate::str::contains("Invalid output format"));
 }
+
+#[test]
+fn test_build_typst_format_writes_source() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input_file = temp_dir.path().join("cv.md");
+    let output_file = temp_dir.path().join("cv.typ");
+    fs::write(
+        &input_file,
+        "---\nname: Test User\nemail: test@example.com\n---\n\n# Experience\nTest content\n",
+    )
+    .expect("Failed to write test file");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        input_file.to_str().expect("Invalid path"),
+        "--output",
+        output_file.to_str().expect("Invalid path"),
+        "-F",
+        "typst",
+        "--quiet",
+    ])
+    .assert()
+    .success();
+
+    let source = fs::read_to_string(&output_file).expect("Failed to read Typst source");
+    assert!(source.contains("#set document(title: \"Test User\""));
+    assert!(source.contains("Test content"));
+}
+
+#[test]
+fn test_build_multiple_formats_and_inputs() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let out_dir = temp_dir.path().join("out");
+    for name in ["alice", "bob"] {
+        fs::write(
+            temp_dir.path().join(format!("{name}.md")),
+            format!("---\nname: {name}\nemail: {name}@example.com\n---\n\n# Experience\nWork\n"),
+        )
+        .expect("Failed to write test file");
+    }
+    let pattern = temp_dir.path().join("*.md");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        pattern.to_str().expect("Invalid path"),
+        "-F",
+        "html,txt,typst",
+        "-o",
+        out_dir.to_str().expect("Invalid path"),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Pages"))
+    .stdout(predicate::str::contains("6 outputs generated successfully"));
+
+    for name in ["alice", "bob"] {
+        for ext in ["html", "txt", "typ"] {
+            assert!(out_dir.join(format!("{name}.{ext}")).exists());
+        }
+    }
+    let text = fs::read_to_string(out_dir.join("bob.txt")).expect("Failed to read text output");
+    assert!(text.starts_with("bob\nbob@example.com\n"));
+    assert!(text.contains("EXPERIENCE"));
+}
+
+#[test]
+fn test_build_batch_larger_than_worker_pool() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let out_dir = temp_dir.path().join("out");
+    for i in 0..40 {
+        fs::write(
+            temp_dir.path().join(format!("cv{i}.md")),
+            format!("---\nname: Person {i}\nemail: p{i}@example.com\n---\n# CV\n"),
+        )
+        .expect("Failed to write test file");
+    }
+    let pattern = temp_dir.path().join("*.md");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        pattern.to_str().expect("Invalid path"),
+        "-F",
+        "html,txt",
+        "-o",
+        out_dir.to_str().expect("Invalid path"),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "80 outputs generated successfully",
+    ));
+
+    for i in 0..40 {
+        assert!(out_dir.join(format!("cv{i}.html")).exists());
+        assert!(out_dir.join(format!("cv{i}.txt")).exists());
+    }
+}
+
+#[test]
+fn test_build_batch_reports_failures() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let good = temp_dir.path().join("good.md");
+    let bad = temp_dir.path().join("bad.md");
+    fs::write(
+        &good,
+        "---\nname: Good\nemail: good@example.com\n---\n# CV\n",
+    )
+    .expect("Failed to write test file");
+    fs::write(&bad, "no frontmatter here").expect("Failed to write test file");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(temp_dir.path())
+        .args([
+            "build",
+            good.to_str().expect("Invalid path"),
+            bad.to_str().expect("Invalid path"),
+            "-F",
+            "html",
+            "--quiet",
+        ])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("failed"))
+        .stderr(predicate::str::contains("1 of 2 outputs failed"));
+
+    assert!(temp_dir.path().join("good.html").exists());
+}
+
+#[test]
+fn test_build_invalid_format_in_list() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("cv.md");
+    fs::write(&input, "---\nname: Test\nemail: t@example.com\n---\n").expect("Failed to write");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        input.to_str().expect("Invalid path"),
+        "-F",
+        "pdf,rtf",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("Invalid output format: rtf"));
+}
+
+#[test]
+fn test_build_max_pages_needs_paged_format() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("cv.md");
+    fs::write(&input, "---\nname: Test\nemail: t@example.com\n---\n").expect("Failed to write");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        input.to_str().expect("Invalid path"),
+        "-F",
+        "html",
+        "--max-pages",
+        "1",
+        "--quiet",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("needs a paged format"));
+
+    assert!(!temp_dir.path().join("cv.html").exists());
+}
+
+#[test]
+fn test_build_fail_on_warnings_passes_clean_build() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let input = temp_dir.path().join("cv.md");
+    fs::write(
+        &input,
+        "---\nname: Test\nemail: t@example.com\n---\n# Skills\nRust\n",
+    )
+    .expect("Failed to write");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "build",
+        input.to_str().expect("Invalid path"),
+        "-F",
+        "txt,html",
+        "-o",
+        temp_dir.path().to_str().expect("Invalid path"),
+        "--fail-on-warnings",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("2 outputs generated successfully"));
+}
@@ -0,0 +1,145 @@
+use assert_cmd::Command;
+use cv_check::manifest::{Manifest, MANIFEST_FILE};
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_doc(dir: &Path, name: &str) {
+    fs::write(
+        dir.join(format!("{name}.md")),
+        format!("---\nname: {name}\nemail: {name}@example.com\n---\n\n# Experience\nWork\n"),
+    )
+    .expect("Failed to write document");
+}
+
+fn project() -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::create_dir_all(temp_dir.path().join("people")).expect("Failed to create dir");
+    write_doc(&temp_dir.path().join("people"), "alice");
+    write_doc(&temp_dir.path().join("people"), "bob");
+
+    fs::write(
+        temp_dir.path().join(MANIFEST_FILE),
+        r#"
+[defaults]
+formats = ["txt"]
+output_dir = "out"
+
+[[documents]]
+input = "people/alice.md"
+formats = ["txt", "html"]
+tags = ["eng"]
+
+[[documents]]
+input = "people/bob.md"
+color_theme = "sharp"
+output = "final/bob.txt"
+tags = ["sales"]
+"#,
+    )
+    .expect("Failed to write manifest");
+
+    temp_dir
+}
+
+#[test]
+fn test_build_all_from_manifest() {
+    let project = project();
+    let nested = project.path().join("people");
+
+    // The manifest is found from a subdirectory, and paths resolve against it
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(&nested)
+        .args(["build", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 outputs generated successfully"));
+
+    assert!(project.path().join("out/alice.txt").exists());
+    assert!(project.path().join("out/alice.html").exists());
+    assert!(project.path().join("final/bob.txt").exists());
+}
+
+#[test]
+fn test_build_all_filters_by_tag() {
+    let project = project();
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args(["build", "--all", "--tag", "sales", "--quiet"])
+        .assert()
+        .success();
+
+    assert!(project.path().join("final/bob.txt").exists());
+    assert!(!project.path().join("out/alice.txt").exists());
+}
+
+#[test]
+fn test_build_all_rejects_input_arguments() {
+    let project = project();
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args(["build", "--all", "people/alice.md"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_build_all_without_manifest() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(temp_dir.path())
+        .args(["build", "--all"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No cv.toml found"));
+}
+
+#[test]
+fn test_check_all_reports_invalid_documents() {
+    let project = project();
+    fs::write(project.path().join("people/bob.md"), "no frontmatter")
+        .expect("Failed to overwrite document");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(project.path())
+        .args(["check", "--all"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("people/alice.md"))
+        .stdout(predicate::str::contains(
+            "bob.md: Invalid markdown structure",
+        ))
+        .stderr(predicate::str::contains("1 of 2 documents are invalid"));
+}
+
+#[test]
+fn test_check_all_with_explicit_manifest() {
+    let project = project();
+    let manifest = project.path().join(MANIFEST_FILE);
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.args([
+        "check",
+        "--all",
+        "--manifest",
+        manifest.to_str().expect("Invalid path"),
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("All documents are valid"));
+}
+
+#[test]
+fn test_manifest_load_reports_bad_toml() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let path = temp_dir.path().join(MANIFEST_FILE);
+    fs::write(&path, "[[documents]]\ninput = 3\n").expect("Failed to write manifest");
+
+    let err = Manifest::load(&path).expect_err("Invalid manifest should fail");
+    assert!(err.to_string().contains("Invalid manifest"));
+}
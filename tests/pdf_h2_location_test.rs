@@ -29,10 +29,16 @@ _Software Engineer_, 2018 - 2020
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             layout: cv_check::config::LayoutOptions::default(),
             recipient: None,
             date: None,
             subject: None,
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: std::collections::HashMap::new(),
         },
         content: content.to_string(),
@@ -79,10 +85,16 @@ B.S. Computer Science, 2010 - 2014";
             website: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             layout: cv_check::config::LayoutOptions::default(),
             recipient: None,
             date: None,
             subject: None,
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: std::collections::HashMap::new(),
         },
         content: content.to_string(),
@@ -16,10 +16,16 @@ fn create_test_document() -> Document {
             location: None,
             font_theme: "modern".to_string(),
             color_theme: "modern".to_string(),
+            theme_overrides: None,
+            icons: None,
             layout: cv_check::config::LayoutOptions::default(),
             recipient: None,
             date: None,
             subject: None,
+            keywords: Vec::new(),
+            lang: None,
+            header: None,
+            footer: None,
             custom: std::collections::HashMap::new(),
         },
         content: "# Test Document\n\nThis is a test.".to_string(),
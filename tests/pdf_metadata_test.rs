@@ -0,0 +1,84 @@
+use cv_check::parser::Document;
+use cv_check::render::pdf::PdfRenderer;
+use cv_check::themes::Theme;
+use std::path::PathBuf;
+
+fn typst_source(markdown: &str) -> String {
+    let doc = Document::from_string(markdown, &PathBuf::from("test.md"))
+        .expect("Failed to parse document");
+    let theme = Theme::new("modern", "modern").expect("Failed to load theme");
+    PdfRenderer::new(None)
+        .expect("Failed to create renderer")
+        .generate_typst_source_for_testing(&doc, &theme)
+}
+
+#[test]
+fn test_document_metadata_from_frontmatter() {
+    let source = typst_source(
+        "---\nname: Jane \"JD\" Doe\nemail: jane@example.com\nkeywords: [rust, \"cloud native\"]\nlang: en-GB\n---\n# Experience\n",
+    );
+
+    assert!(source
+        .contains("#set document(title: \"Jane \\\"JD\\\" Doe\", author: \"Jane \\\"JD\\\" Doe\""));
+    assert!(source.contains("description: \"Curriculum vitae of Jane \\\"JD\\\" Doe\""));
+    assert!(source.contains("keywords: (\"rust\",\"cloud native\",)"));
+    assert!(source.contains("date: datetime(year: "));
+    assert!(source.contains("#set text(lang: \"en\", region: \"GB\")"));
+}
+
+#[test]
+fn test_document_metadata_defaults() {
+    let source = typst_source(
+        "---\nname: Jane Doe\nemail: jane@example.com\nsubject: Application for Engineer\nrecipient:\n  name: Hiring Manager\n---\nDear Hiring Manager,\n",
+    );
+
+    assert!(source.contains("description: \"Application for Engineer\""));
+    assert!(source.contains("keywords: ()"));
+    assert!(source.contains("#set text(lang: \"en\")"));
+}
+
+#[test]
+fn test_invalid_language_falls_back_to_english() {
+    let source = typst_source("---\nname: Jane\nemail: jane@example.com\nlang: english!\n---\n");
+    assert!(source.contains("#set text(lang: \"en\")"));
+}
+
+#[test]
+fn test_outline_bookmarks_for_sections_and_entries() {
+    let source = typst_source(
+        "---\nname: Jane\nemail: jane@example.com\n---\n# Experience\n\n## Acme Corp (London)\n\n### Engineer\n\n# Education\n",
+    );
+
+    assert!(source.contains(
+        "#place(hide(heading(level: 1, outlined: false, bookmarked: true, \"Experience\")))"
+    ));
+    assert!(source.contains(
+        "#place(hide(heading(level: 2, outlined: false, bookmarked: true, \"Acme Corp (London)\")))"
+    ));
+    assert!(source.contains(
+        "#place(hide(heading(level: 1, outlined: false, bookmarked: true, \"Education\")))"
+    ));
+    assert!(!source.contains("\"Engineer\")))"));
+}
+
+#[test]
+fn test_page_header_and_footer() {
+    let source = typst_source(
+        "---\nname: Jane\nemail: jane@example.com\ndate: 2 June 2025\nheader:\n  left: \"{name} - CV\"\n  right: \"{date}\"\n  first_page: false\nfooter:\n  right: \"Page {page} of {pages}\"\n---\n# Experience\n",
+    );
+
+    assert!(source.contains("#set page(header: context {"));
+    assert!(source.contains(
+        "if counter(page).get().first() > 1 { grid(columns: (1fr, auto, 1fr), align: (left, center, right), \"Jane - CV\", [], \"2 June 2025\") }"
+    ));
+    assert!(source.contains(
+        "footer: context {\n  set text(size: 10pt, fill: rgb(\"#666666\"))\n  grid(columns: (1fr, auto, 1fr), align: (left, center, right), [], [], \"Page \" + str(counter(page).get().first()) + \" of \" + str(counter(page).final().first()))\n})"
+    ));
+}
+
+#[test]
+fn test_no_page_header_by_default() {
+    let source = typst_source("---\nname: Jane\nemail: jane@example.com\n---\n");
+    assert!(!source.contains("header: context"));
+    assert!(!source.contains("footer: context"));
+}
@@ -0,0 +1,78 @@
+use cv_check::render::pdf::PdfRenderer;
+use cv_check::test_utils::{create_cover_letter_document, create_full_cv_document};
+use cv_check::themes::Theme;
+use std::fs;
+use tempfile::TempDir;
+
+fn renderer_with_template(temp_dir: &TempDir, template: &str) -> PdfRenderer {
+    let template_path = temp_dir.path().join("custom.typ");
+    fs::write(&template_path, template).expect("Failed to write template");
+    PdfRenderer::new(Some(&template_path)).expect("Failed to create renderer")
+}
+
+#[test]
+fn test_custom_template_receives_document_data() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let renderer = renderer_with_template(&temp_dir, "#cv.name\n#cv.body\n");
+    let doc = create_full_cv_document();
+    let theme = Theme::new("modern", "sharp").expect("Failed to create theme");
+
+    let source = renderer.generate_typst_source_for_testing(&doc, &theme);
+
+    // Data preamble comes first, then the template text unchanged
+    assert!(source.starts_with("// Document data provided by cv_check"));
+    assert!(source.contains("#let cv = ("));
+    assert!(source.ends_with("#cv.name\n#cv.body\n"));
+
+    // Metadata
+    assert!(source.contains("  kind: \"cv\","));
+    assert!(source.contains(&format!("  name: \"{}\",", doc.metadata.name)));
+    assert!(source.contains("  recipient: none,"));
+
+    // Theme values
+    assert!(source.contains("primary: rgb(\"#6B46C1\")"));
+    assert!(source.contains("header: (family: \"Inter\""));
+    assert!(source.contains("h1-above: "));
+}
+
+#[test]
+fn test_custom_template_sections_are_split_by_h1() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let renderer = renderer_with_template(&temp_dir, "");
+    let doc = create_full_cv_document();
+    let theme = Theme::new("modern", "modern").expect("Failed to create theme");
+
+    let source = renderer.generate_typst_source_for_testing(&doc, &theme);
+
+    for line in doc.content.lines() {
+        if let Some(title) = line.strip_prefix("# ") {
+            assert!(
+                source.contains(&format!("(title: \"{}\", body: [", title.trim())),
+                "Missing section entry for '{title}'"
+            );
+        }
+    }
+    assert!(source.contains("  body: ["));
+}
+
+#[test]
+fn test_custom_template_letter_data() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let renderer = renderer_with_template(&temp_dir, "");
+    let doc = create_cover_letter_document();
+    let theme = Theme::new("modern", "modern").expect("Failed to create theme");
+
+    let source = renderer.generate_typst_source_for_testing(&doc, &theme);
+
+    assert!(source.contains("  kind: \"letter\","));
+    assert!(source.contains("  recipient: (name: "));
+}
+
+#[test]
+fn test_example_template_uses_contract() {
+    let template = fs::read_to_string("examples/templates/default.typ")
+        .expect("Example template should exist");
+    assert!(template.contains("cv.theme.colors"));
+    assert!(template.contains("#cv.body"));
+    assert!(template.contains("cv.kind"));
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{cv, write_cv};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_print_safe_html_is_greyscale() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "");
+
+    cv(&temp_dir)
+        .args([
+            "build",
+            "cv.md",
+            "-F",
+            "html",
+            "-c",
+            "sharp",
+            "--print-safe",
+        ])
+        .assert()
+        .success();
+    let page = fs::read_to_string(temp_dir.path().join("cv.html")).expect("Output exists");
+    assert!(page.contains("--background: #FFFFFF;"));
+    assert!(page.contains("--surface: #F5F5F5;"));
+    // Sharp's purple primary and lime accent are gone
+    assert!(!page.contains("#6B46C1"));
+    assert!(!page.contains("#84CC16"));
+}
+
+#[test]
+fn test_print_safe_typst_has_heavier_rules() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "");
+
+    cv(&temp_dir)
+        .args(["build", "cv.md", "-F", "typst", "-o", "colour.typ"])
+        .assert()
+        .success();
+    cv(&temp_dir)
+        .args([
+            "build",
+            "cv.md",
+            "-F",
+            "typst",
+            "-o",
+            "print.typ",
+            "--print-safe",
+        ])
+        .assert()
+        .success();
+
+    let colour = fs::read_to_string(temp_dir.path().join("colour.typ")).expect("Output exists");
+    let print = fs::read_to_string(temp_dir.path().join("print.typ")).expect("Output exists");
+    assert!(colour.contains("stroke: 1pt + rgb(\"#FF6B35\")"));
+    assert!(print.contains("stroke: 2pt + rgb(\"#"));
+    assert!(!print.contains("#0066CC"));
+}
+
+#[test]
+fn test_print_safe_is_not_served_from_the_cache() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    write_cv(&temp_dir, "");
+
+    cv(&temp_dir)
+        .args(["build", "cv.md", "-F", "html"])
+        .assert()
+        .success();
+    cv(&temp_dir)
+        .args(["build", "cv.md", "-F", "html", "--print-safe"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("unchanged").not());
+}
@@ -106,7 +106,7 @@ fn test_pdf_renderer_render() {
 
     let result = renderer.render(&doc, &theme, &output_path);
 
-    if let Ok(()) = result {
+    if let Ok(_report) = result {
         // Verify output file was created
         assert!(output_path.exists());
         let content = fs::read(&output_path).expect("Failed to read output file");
@@ -142,7 +142,7 @@ fn test_docx_renderer_render() {
 
     let result = renderer.render(&doc, &theme, &output_path);
 
-    if let Ok(()) = result {
+    if let Ok(_report) = result {
         // Verify output file was created
         assert!(output_path.exists());
         let content = fs::read(&output_path).expect("Failed to read output file");
@@ -179,7 +179,7 @@ fn test_html_renderer_render() {
     let result = renderer.render(&doc, &theme, &output_path);
 
     match result {
-        Ok(()) => {
+        Ok(_report) => {
             // Verify output file was created
             assert!(output_path.exists());
             let content = fs::read_to_string(&output_path).expect("Failed to read output file");
@@ -208,7 +208,7 @@ fn test_renderer_render_pdf() {
 
     let result = renderer.render(&doc, &theme, &output_path);
 
-    if let Ok(()) = result {
+    if let Ok(_report) = result {
         // Verify output file was created
         assert!(output_path.exists());
     }
@@ -227,7 +227,7 @@ fn test_renderer_render_html() {
     let result = renderer.render(&doc, &theme, &output_path);
 
     match result {
-        Ok(()) => {
+        Ok(_report) => {
             // Verify output file was created
             assert!(output_path.exists());
             let content = fs::read_to_string(&output_path).expect("Failed to read output file");
@@ -251,9 +251,81 @@ fn test_renderer_render_docx() {
 
     let result = renderer.render(&doc, &theme, &output_path);
 
-    if let Ok(()) = result {
+    if let Ok(_report) = result {
         // Verify output file was created
         assert!(output_path.exists());
     }
     // This is expected until full implementation if result is Err
 }
+
+#[test]
+fn test_renderer_new_typst() {
+    let renderer = Renderer::new("typst", None);
+    assert!(renderer.is_ok());
+}
+
+#[test]
+fn test_output_extension() {
+    assert_eq!(cv_check::render::output_extension("pdf"), "pdf");
+    assert_eq!(cv_check::render::output_extension("html"), "html");
+    assert_eq!(cv_check::render::output_extension("typst"), "typ");
+}
+
+#[test]
+fn test_renderer_new_image_formats() {
+    let options = cv_check::render::RenderOptions {
+        dpi: 300,
+        ..Default::default()
+    };
+    assert!(Renderer::with_options("png", None, &options).is_ok());
+    assert!(Renderer::with_options("svg", None, &options).is_ok());
+    assert_eq!(cv_check::render::output_extension("png"), "png");
+    assert_eq!(cv_check::render::output_extension("svg"), "svg");
+}
+
+#[test]
+fn test_shorter_image_render_replaces_old_pages() {
+    let temp_dir = tempdir().expect("Failed to create temp dir");
+    let output = temp_dir.path().join("cv.png");
+    let renderer = Renderer::new("png", None).expect("Failed to create renderer");
+    let theme = create_test_theme();
+    let document = |content: &str| {
+        Document::from_string(
+            &format!("---\nname: Test User\nemail: test@example.com\n---\n{content}"),
+            Path::new("cv.md"),
+        )
+        .expect("Failed to parse document")
+    };
+
+    let long = document("# One\n\n<!-- pagebreak -->\n\n# Two\n\n<!-- pagebreak -->\n\n# Three\n");
+    let report = renderer
+        .render(&long, &theme, &output)
+        .expect("Long render");
+    assert_eq!(report.pages, Some(3));
+
+    let report = renderer
+        .render(&document("# One\n"), &theme, &output)
+        .expect("Short render");
+    assert_eq!(report.pages, Some(1));
+    assert!(temp_dir.path().join("cv-1.png").exists());
+    assert!(!temp_dir.path().join("cv-2.png").exists());
+    assert!(!temp_dir.path().join("cv-3.png").exists());
+}
+
+#[test]
+fn test_primary_output_for_image_formats() {
+    use cv_check::render::primary_output;
+
+    assert_eq!(
+        primary_output("png", Path::new("out/cv.png")),
+        PathBuf::from("out/cv-1.png")
+    );
+    assert_eq!(
+        primary_output("svg", Path::new("out/page-{page}.svg")),
+        PathBuf::from("out/page-1.svg")
+    );
+    assert_eq!(
+        primary_output("pdf", Path::new("out/cv.pdf")),
+        PathBuf::from("out/cv.pdf")
+    );
+}
@@ -0,0 +1,140 @@
+use assert_cmd::Command;
+use cv_check::parser::Document;
+use cv_check::render::html::HtmlRenderer;
+use cv_check::render::pdf::PdfRenderer;
+use cv_check::render::review::Review;
+use cv_check::render::{RenderEngine, RenderOptions};
+use cv_check::themes::Theme;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CV: &str = "---
+name: Jane Doe
+email: jane@example.com
+---
+# Experience
+
+## Senior Engineer - Acme
+
+<!-- note: Quantify the latency win -->
+
+- Cut latency <!-- note: by how much? --> across the API
+<!-- keep this private -->
+
+<!-- note: tighten
+this bullet -->
+
+- Led the platform team <!-- note: name
+the team -->
+";
+
+fn options(notes: bool) -> RenderOptions {
+    RenderOptions {
+        review: Some(Review {
+            watermark: "FOR REVIEW".to_string(),
+            stamp: "1a2b3c4".to_string(),
+            notes,
+        }),
+        ..Default::default()
+    }
+}
+
+fn typst_source(options: &RenderOptions) -> String {
+    let doc = Document::from_string(CV, Path::new("cv.md")).expect("Valid document");
+    let theme = Theme::new("modern", "modern").expect("Theme loads");
+    PdfRenderer::with_options(None, options)
+        .expect("Renderer builds")
+        .generate_typst_source_for_testing(&doc, &theme)
+}
+
+fn html(options: &RenderOptions) -> String {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let output = dir.path().join("cv.html");
+    let doc = Document::from_string(CV, Path::new("cv.md")).expect("Valid document");
+    let theme = Theme::new("modern", "modern").expect("Theme loads");
+    HtmlRenderer::with_options(None, options)
+        .expect("Renderer builds")
+        .render(&doc, &theme, &output)
+        .expect("HTML renders");
+    fs::read_to_string(output).expect("Output exists")
+}
+
+#[test]
+fn test_pdf_review_build_has_watermark_and_notes() {
+    let source = typst_source(&options(true));
+
+    assert!(source.contains("rotate(-45deg"));
+    assert!(source.contains("\"FOR REVIEW\""));
+    assert!(source.contains("\"FOR REVIEW · 1a2b3c4\""));
+    assert!(source.contains("right: 5cm"));
+    assert!(source.contains("\"Quantify the latency win\""));
+    assert!(source.contains("\"by how much?\""));
+    assert!(!source.contains("keep this private"));
+    // Notes spanning lines, as a block and inline
+    assert!(source.contains("\"tighten\\nthis bullet\""));
+    assert!(source.contains("\"name\\nthe team\""));
+
+    // Watermark only: the notes stay out and the margin is unchanged
+    let source = typst_source(&options(false));
+    assert!(source.contains("\"FOR REVIEW\""));
+    assert!(!source.contains("Quantify"));
+    assert!(!source.contains("tighten"));
+    assert!(!source.contains("the team"));
+    assert!(source.contains("right: 2cm"));
+}
+
+#[test]
+fn test_final_build_has_no_review_marks() {
+    let source = typst_source(&RenderOptions::default());
+    assert!(!source.contains("rotate(-45deg"));
+    assert!(!source.contains("Quantify"));
+
+    let page = html(&RenderOptions::default());
+    assert!(!page.contains("Quantify"));
+    assert!(!page.contains("tighten"));
+    assert!(!page.contains("the team"));
+    assert!(!page.contains("review-note"));
+    assert!(!page.contains("body::before"));
+}
+
+#[test]
+fn test_html_review_build_has_watermark_and_notes() {
+    let page = html(&options(true));
+
+    assert!(page.contains("body::before { content: \"FOR REVIEW\""));
+    assert!(page.contains("content: \"FOR REVIEW · 1a2b3c4\""));
+    assert!(page.contains("<aside class=\"review-note\">Quantify the latency win</aside>"));
+    assert!(page.contains("<aside class=\"review-note\">by how much?</aside>"));
+    assert!(page.contains("<aside class=\"review-note\">tighten\nthis bullet</aside>"));
+    assert!(page.contains("<aside class=\"review-note\">name\nthe team</aside>"));
+
+    // Without notes, multi-line notes are dropped rather than copied as comments
+    let page = html(&options(false));
+    assert!(!page.contains("tighten"));
+    assert!(!page.contains("the team"));
+}
+
+#[test]
+fn test_draft_flags() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(dir.path().join("cv.md"), CV).expect("Failed to write CV");
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(dir.path())
+        .env("CV_CHECK_NO_OPEN", "1")
+        .args(["build", "cv.md", "-F", "html", "--draft", "--notes"])
+        .assert()
+        .success();
+    let page = fs::read_to_string(dir.path().join("cv.html")).expect("Output exists");
+    assert!(page.contains("content: \"DRAFT · "));
+    assert!(page.contains("review-note"));
+
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(dir.path())
+        .args(["build", "cv.md", "-F", "html", "--notes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--draft"));
+}
@@ -0,0 +1,61 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn cv() -> Command {
+    Command::cargo_bin("cv").expect("Failed to find binary")
+}
+
+#[test]
+fn test_themes_check_reports_each_pair() {
+    cv().args(["themes", "--check", "sharp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("sharp - 2 of 8 pairs fail"))
+        .stdout(predicate::str::contains(
+            "accent on background (links): 1.89:1, needs 4.5:1",
+        ))
+        .stdout(predicate::str::contains(
+            "text on background (body text): 16.63:1",
+        ))
+        .stdout(predicate::str::contains("classic").not());
+
+    cv().args(["themes", "--check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("classic - "))
+        .stdout(predicate::str::contains("modern - "));
+}
+
+#[test]
+fn test_check_warns_about_low_contrast_overrides() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let cv_path = temp_dir.path().join("cv.md");
+    fs::write(
+        &cv_path,
+        "---\nname: Jane Doe\nemail: jane@example.com\ncolor_theme: modern\ntheme_overrides:\n  text: \"#EEEEEE\"\n---\n# Experience\n",
+    )
+    .expect("Failed to write CV");
+
+    cv().arg("check")
+        .arg(&cv_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Low contrast from theme_overrides: text on background (body text)",
+        ))
+        .stdout(predicate::str::contains("is valid!"));
+
+    // Failures already in the base theme are not blamed on the document
+    fs::write(
+        &cv_path,
+        "---\nname: Jane Doe\nemail: jane@example.com\ncolor_theme: modern\ntheme_overrides:\n  h2_color: \"#123456\"\n---\n# Experience\n",
+    )
+    .expect("Failed to write CV");
+    cv().arg("check")
+        .arg(&cv_path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Low contrast").not());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{cv, write_cv};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_generated_theme_is_ready_to_use() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    cv(&temp_dir)
+        .args(["themes", "generate", "--from", "#0066CC", "--name", "acme"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("written to acme.yaml"));
+
+    let theme = fs::read_to_string(temp_dir.path().join("acme.yaml")).expect("Theme written");
+    assert!(theme.contains("extends: modern"));
+    for field in [
+        "secondary",
+        "accent",
+        "muted",
+        "surface",
+        "border",
+        "h3_color",
+    ] {
+        assert!(theme.contains(&format!("\n{field}: '#")), "{field}");
+    }
+
+    cv(&temp_dir)
+        .args(["themes", "--check", "acme.yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("acme.yaml - all pairs pass"));
+
+    write_cv(&temp_dir, "");
+    cv(&temp_dir)
+        .args(["build", "cv.md", "-F", "html", "-c", "acme.yaml"])
+        .assert()
+        .success();
+    let page = fs::read_to_string(temp_dir.path().join("cv.html")).expect("Output exists");
+    assert!(page.contains("--primary: #0066CC;"));
+}
+
+#[test]
+fn test_generate_rejects_invalid_colour() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+
+    cv(&temp_dir)
+        .args(["themes", "generate", "--from", "blue", "--name", "acme"])
+        .args(["-o", "themes/acme.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be a hex colour"));
+    assert!(!temp_dir.path().join("themes").exists());
+}
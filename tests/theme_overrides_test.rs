@@ -0,0 +1,87 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CV: &str = "---
+name: Jane Doe
+email: jane@example.com
+theme_overrides:
+  primary: \"#123456\"
+  h2_color: \"#654321\"
+  header:
+    family: Lato
+---
+# Experience
+
+## Engineer - Acme
+
+- Built things
+";
+
+fn cv(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("cv").expect("Failed to find binary");
+    cmd.current_dir(dir).env("CV_CHECK_NO_OPEN", "1");
+    cmd
+}
+
+#[test]
+fn test_frontmatter_overrides_reach_output_and_verbose() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("cv.md"), CV).expect("Failed to write CV");
+
+    cv(temp_dir.path())
+        .args(["build", "cv.md", "-F", "typst", "--verbose"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resolved theme:"))
+        .stdout(predicate::str::contains("family: Lato"))
+        .stdout(predicate::str::contains("primary: '#123456'"));
+
+    let source = fs::read_to_string(temp_dir.path().join("cv.typ")).expect("Output exists");
+    assert!(source.contains("rgb(\"#654321\")"));
+}
+
+#[test]
+fn test_theme_file_extends_built_in_theme() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(
+        temp_dir.path().join("cv.md"),
+        "---\nname: Jane Doe\nemail: jane@example.com\n---\n# Experience\n",
+    )
+    .expect("Failed to write CV");
+    fs::write(
+        temp_dir.path().join("brand.yaml"),
+        "extends: sharp\nh2_color: \"#0A0B0C\"\n",
+    )
+    .expect("Failed to write theme");
+
+    cv(temp_dir.path())
+        .args(["build", "cv.md", "-F", "html", "-c", "brand.yaml"])
+        .assert()
+        .success();
+
+    let page = fs::read_to_string(temp_dir.path().join("cv.html")).expect("Output exists");
+    assert!(page.contains("--h2: #0A0B0C;"));
+    // Everything else comes from the sharp colours
+    assert!(page.contains("--primary: #6B46C1;"));
+}
+
+#[test]
+fn test_invalid_overrides_are_reported() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(
+        temp_dir.path().join("cv.md"),
+        CV.replace("primary: \"#123456\"", "primry: \"#123456\""),
+    )
+    .expect("Failed to write CV");
+
+    cv(temp_dir.path())
+        .args(["build", "cv.md", "-F", "txt"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid theme theme_overrides: unknown field `primry`",
+        ));
+}